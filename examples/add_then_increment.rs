@@ -0,0 +1,51 @@
+use std::process;
+
+use turing::prelude::*;
+use turing::rules::compose::Compose;
+
+// Rewrites the adder's final tape, e.g. `_+++IIIO_`, as a plain number, `____1110_`, and
+// stops in `Unmarked` on its last digit: rewinds to the blank left of the tape, then
+// blanks the pluses and turns the markers back into digits on the way right.
+fn unmark() -> RuleTable {
+    let state = |name: &str| State::Named(String::from(name));
+    let mut rules = RuleTable::new();
+    for symbol in ['0', '1', '+', 'I', 'O'] {
+        rules.insert((state("Rewind"), symbol), (symbol, Direction::Left, state("Rewind")));
+    }
+    rules.insert((state("Rewind"), '_'), ('_', Direction::Right, state("Unmark")));
+    rules.insert((state("Unmark"), '0'), ('0', Direction::Right, state("Unmark")));
+    rules.insert((state("Unmark"), '1'), ('1', Direction::Right, state("Unmark")));
+    rules.insert((state("Unmark"), '+'), ('_', Direction::Right, state("Unmark")));
+    rules.insert((state("Unmark"), 'I'), ('1', Direction::Right, state("Unmark")));
+    rules.insert((state("Unmark"), 'O'), ('0', Direction::Right, state("Unmark")));
+    rules.insert((state("Unmark"), '_'), ('_', Direction::Left, state("Unmarked")));
+    rules
+}
+
+// Builds "add then increment" as one machine out of the adder, `unmark` and the
+// incrementer, and runs it on a few sums. tests/compose.rs checks it computes a + b + 1.
+fn main() {
+    let named = |name: &str| State::Named(String::from(name));
+    let increment = rules::increment().namespaced("inc.");
+    let rules = rules::adder()
+        .namespaced("add.")
+        .merge(&unmark(), &[(named("add.GetLast"), named("Rewind"))])
+        .and_then(|rules| rules.merge(&increment, &[(named("Unmarked"), named("inc.Increment"))]))
+        .unwrap_or_else(|error| {
+            eprintln!("could not compose the machine: {}", error);
+            process::exit(1);
+        });
+
+    println!("add then increment: {} rules", rules.len());
+    for (a, b) in [("1011", "11"), ("0", "0"), ("111", "1")] {
+        let tape = build_tape(a, b, LeadingZeros::Strip).unwrap();
+        let mut machine = TuringMachine::new(tape, rules.clone());
+        machine.set_state(named("add.FindPlus"));
+        machine.set_final_states(vec![named("inc.Done")]);
+        machine.set_result_strategy(ResultStrategy::WholeTapeTrimmed);
+        match machine.run_bounded(1_000_000) {
+            Ok(steps) => println!("{}+{}+1 = {} in {} steps", a, b, machine.result(), steps),
+            Err(error) => println!("{}+{}+1: {}", a, b, error),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use turing::decode_result;
+use turing::machine::pipeline::Pipeline;
+use turing::prelude::*;
+use turing::tape::input::chain_tape;
+
+// Rewrites a number on the tape (a plain `_n_` input or an adder's final tape) as `_n+1_`.
+fn increment_input(tape: &[char]) -> Vec<char> {
+    chain_tape(tape, "1", LeadingZeros::Strip).expect("adder output is binary")
+}
+
+fn main() {
+    // Adding 3 by incrementing three times, each stage being the adder with a `+1` appended.
+    let pipeline = Pipeline::new(10_000)
+        .stage(rules::adder(), increment_input)
+        .stage(rules::adder(), increment_input)
+        .stage(rules::adder(), increment_input);
+
+    let input = vec!['_', '1', '0', '1', '1', '_'];
+    match pipeline.run(&input) {
+        Ok(output) => {
+            for (index, stage) in output.stages.iter().enumerate() {
+                println!("Stage {}: {} steps, tape length {}", index, stage.steps, stage.tape_len);
+            }
+            println!("1011 + 3 = {}", decode_result(&output.tape));
+        }
+        Err(error) => println!("Stage {} failed: {:?}", error.stage, error.error),
+    }
+}
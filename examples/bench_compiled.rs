@@ -0,0 +1,34 @@
+use std::time::Instant;
+
+use turing::prelude::*;
+
+// Compares the HashMap-driven machine against the compiled one on a large addition,
+// with and without scanning over self-loop rules. tests/compiled.rs checks that all three
+// give the same answers in the same number of steps.
+// Run with `cargo run --release --example bench_compiled`.
+fn main() {
+    let a: String = (0..4096).map(|i| if i % 3 == 0 { '0' } else { '1' }).collect();
+    let b: String = (0..4096).map(|i| if i % 5 == 0 { '0' } else { '1' }).collect();
+    let tape = build_tape(&a, &b, LeadingZeros::Strip).unwrap();
+
+    let mut machine = TuringMachine::new(tape.clone(), rules::adder());
+    let start = Instant::now();
+    machine.run();
+    let hashed = start.elapsed();
+
+    let mut compiled = TuringMachine::new(tape.clone(), rules::adder()).compile().unwrap();
+    compiled.set_scanning(false);
+    let start = Instant::now();
+    compiled.run();
+    let dense = start.elapsed();
+
+    let mut scanning = TuringMachine::new(tape, rules::adder()).compile().unwrap();
+    let start = Instant::now();
+    scanning.run();
+    let scanned = start.elapsed();
+
+    println!("{} steps", machine.steps());
+    println!("HashMap:  {:?}", hashed);
+    println!("Compiled: {:?}", dense);
+    println!("Scanning: {:?}", scanned);
+}
@@ -0,0 +1,39 @@
+use std::env;
+use std::time::Instant;
+
+use turing::prelude::*;
+use turing::tape::input;
+
+// Computes 2^n with the deliberately slow `double` machine, the stress test for long runs,
+// with the HashMap-driven machine and the compiled one. tests/long_run.rs checks the
+// results and step counts.
+// Run with `cargo run --release --example bench_long_run [N]`; N defaults to 12.
+fn main() {
+    let n = env::args().nth(1).map_or(12, |n| n.parse().expect("N should be a number"));
+    let start = Instant::now();
+    let (ones, steps) = double(n);
+    let hashed = start.elapsed();
+
+    let definition = registry::get("double").unwrap();
+    let mut machine = TuringMachine::new(input::doubling_tape(n), definition.rules);
+    machine.set_state(definition.start);
+    machine.set_result_strategy(definition.result);
+    let mut compiled = machine.compile().unwrap();
+    let start = Instant::now();
+    compiled.run();
+    let dense = start.elapsed();
+
+    println!("2^{} = {} ones: {} steps", n, ones, steps);
+    println!("HashMap:  {:?}", hashed);
+    println!("Compiled: {:?}", dense);
+}
+
+// Runs the `double` machine on `n`, returning the ones it computed and the steps taken.
+fn double(n: usize) -> (usize, usize) {
+    let definition = registry::get("double").unwrap();
+    let mut machine = TuringMachine::new(input::doubling_tape(n), definition.rules);
+    machine.set_state(definition.start);
+    machine.set_result_strategy(definition.result);
+    machine.run();
+    (machine.result().len(), machine.steps())
+}
@@ -0,0 +1,48 @@
+use std::env;
+use std::fs;
+use std::process::{self, Command};
+
+use turing::prelude::*;
+use turing::tape::input::DEMO_TAPE;
+
+// The checked-in outputs the reference adder must keep giving, under tests/golden/.
+const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden");
+
+// Rewrites the golden files tests/golden.rs compares the adder's outputs with: the full
+// trace of the built-in example, the SMT-LIB dump of the rules, the rules in their
+// canonical order, the plain tape diff `tm add 1011+11 --diff` prints and the sentences
+// `tm trace 1+1 --explain` prints. A change to the rules that alters them has to come
+// with an intentional update of the golden files: run `cargo run --example golden --
+// --bless` and commit the result.
+fn main() {
+    if !env::args().any(|arg| arg == "--bless") {
+        eprintln!("Usage: cargo run --example golden -- --bless");
+        process::exit(2);
+    }
+    let mut machine = TuringMachine::new(DEMO_TAPE.chars().collect(), rules::adder());
+    machine.set_tracing(true);
+    machine.run();
+    let before = build_tape("1011", "11", LeadingZeros::Strip).unwrap();
+    let mut addition = TuringMachine::new(before.clone(), rules::adder());
+    addition.preserve_operands();
+    addition.run();
+    let after: Vec<char> = addition.tape().iter().copied().collect();
+    let diff = trace::render_tape_diff(&before, &after, addition.grown_left(), false);
+    let explained = Command::new(env!("CARGO"))
+        .args(["run", "-q", "--", "trace", "1+1", "--explain"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("cargo should run tm");
+    let outputs = [
+        ("demo_trace.txt", trace::trace_to_text(machine.trace())),
+        ("adder.smt2", rules::to_smtlib(&rules::adder())),
+        ("adder_rules.txt", rules::to_text(&rules::adder())),
+        ("adder_diff.txt", format!("{}\n{}\n", diff, addition.equation().unwrap())),
+        ("adder_explain.txt", String::from_utf8(explained.stdout).expect("tm prints UTF-8")),
+    ];
+    for (name, actual) in outputs {
+        let path = format!("{}/{}", GOLDEN_DIR, name);
+        fs::write(&path, &actual).expect("the golden file should be writable");
+        println!("updated {}", path);
+    }
+}
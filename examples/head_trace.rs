@@ -0,0 +1,14 @@
+use std::io;
+
+use turing::machine::stream::HeadTrace;
+use turing::prelude::*;
+
+// Runs the adder on `1011+11` printing the head's trajectory every 10 steps, as
+// `tm run --head-trace FILE --head-trace-every 10` writes it. tests/head_trace.rs checks
+// the rows.
+fn main() {
+    let mut machine = TuringMachine::new("_1011+11_".chars().collect(), rules::adder());
+    let mut trace = HeadTrace::new(io::stdout(), 10).unwrap();
+    machine.run_observed(|configuration| trace.observe(configuration)).unwrap();
+    trace.finish().unwrap();
+}
@@ -0,0 +1,33 @@
+use std::env;
+
+use turing::prelude::*;
+use turing::tape::bounded;
+
+// Decides palindromes over a and b with a linear bounded automaton, whose tape is the
+// word between the end markers `<` and `>`, and prints the verdict for each word given
+// (by default a few of each kind). tests/lba.rs checks it on every short word.
+// Run with `cargo run --example lba_palindrome -- abba abab`.
+fn main() {
+    let state = |name: &str| State::Named(name.to_string());
+    let mut words: Vec<String> = env::args().skip(1).collect();
+    if words.is_empty() {
+        words = ["abba", "aba", "ab", ""].map(String::from).to_vec();
+    }
+    for word in words {
+        let mut machine = match bounded::machine(&word, rules::palindrome(), state("Pick"), '<', '>') {
+            Ok(machine) => machine,
+            Err(error) => {
+                eprintln!("<{}>: {}", word, error);
+                continue;
+            }
+        };
+        machine.set_final_states(vec![state("Accept"), state("Reject")]);
+        match machine.run_bounded(10_000) {
+            Ok(steps) => match machine.halt_reason() {
+                Some(HaltReason::Finished(verdict)) => println!("<{}>: {} after {} steps", word, verdict, steps),
+                reason => println!("<{}>: halted with {:?} after {} steps", word, reason, steps),
+            },
+            Err(error) => eprintln!("<{}>: {}", word, error),
+        }
+    }
+}
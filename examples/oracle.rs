@@ -0,0 +1,49 @@
+use turing::prelude::*;
+
+// Answers whether the binary number left of the '#' just behind the head is prime,
+// writing 'Y' or 'N' under the head.
+fn primality_oracle(tape: &mut Tape, head: isize) {
+    let head = head as usize;
+    let digits: String = tape.iter().take(head - 1).filter(|c| **c == '0' || **c == '1').collect();
+    let n = u64::from_str_radix(&digits, 2).unwrap_or(0);
+    let prime = n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d));
+    tape[head] = if prime { 'Y' } else { 'N' };
+}
+
+fn main() {
+    let scan = || State::Named("Scan".to_string());
+    let query = || State::Named("Query".to_string());
+    let accept = || State::Named("Accept".to_string());
+    let reject = || State::Named("Reject".to_string());
+
+    // Walk right to the '#', step onto the cell after it and ask the oracle, then
+    // move into Accept or Reject depending on its answer.
+    let rules = vec![
+        (scan(), '_', '_', Direction::Right, scan()),
+        (scan(), '0', '0', Direction::Right, scan()),
+        (scan(), '1', '1', Direction::Right, scan()),
+        (scan(), '#', '#', Direction::Right, query()),
+        (query(), 'Y', 'Y', Direction::Right, accept()),
+        (query(), 'N', 'N', Direction::Right, reject()),
+    ];
+
+    for number in ["111", "1001", "1011", "1"] {
+        let mut tape = vec!['_'];
+        tape.extend(number.chars());
+        tape.extend(['#', '_']);
+
+        let mut machine = TuringMachine::with_rules(tape, rules.clone()).expect("no duplicate rules");
+        machine.set_state(scan());
+        machine.set_oracle(query(), primality_oracle);
+
+        let mut last = None;
+        machine.run_observing(|_, _, _, _, to| last = Some(to.clone()));
+        println!(
+            "{} prime: {} ({} steps, {} oracle calls)",
+            number,
+            last == Some(accept()),
+            machine.steps(),
+            machine.oracle_calls()
+        );
+    }
+}
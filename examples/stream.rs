@@ -0,0 +1,52 @@
+use std::io::{self, Write};
+use std::sync::mpsc::{sync_channel, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use turing::machine::progress::adder_remaining_steps;
+use turing::machine::stream::{Backpressure, StepEvent};
+use turing::prelude::*;
+
+// Adds two 400-bit numbers on a worker thread at full speed while the main thread
+// draws the latest step at 30 frames a second. tests/stream.rs covers what each
+// kind of backpressure sends and how a dropped receiver cancels the run.
+// Run with `cargo run --release --example stream`.
+fn main() {
+    let a = "10".repeat(200);
+    let b = "1101".repeat(100);
+    let (events, received) = sync_channel(1);
+    let worker = thread::spawn(move || {
+        let tape = build_tape(&a, &b, LeadingZeros::Strip).unwrap();
+        let mut machine = TuringMachine::new(tape, rules::adder());
+        machine.run_streaming(events, Backpressure::DropOldest(64))
+    });
+
+    let frame = Duration::from_secs(1) / 30;
+    let mut latest: Option<StepEvent> = None;
+    let mut frames = 0;
+    loop {
+        let started = Instant::now();
+        let done = loop {
+            match received.try_recv() {
+                Ok(event) => latest = Some(event),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        if let Some(event) = &latest {
+            let tape: Tape = event.snapshot.tape.iter().copied().collect();
+            let remaining = adder_remaining_steps(&tape, event.snapshot.head, &event.snapshot.state).unwrap_or(0);
+            let progress = event.step as f64 / (event.step + remaining) as f64;
+            print!("\rstep {:>8}  {:>5.1}%  {:<12}", event.step, progress * 100.0, event.next.to_string());
+            io::stdout().flush().unwrap();
+            frames += 1;
+        }
+        if done {
+            break;
+        }
+        thread::sleep(frame.saturating_sub(started.elapsed()));
+    }
+    let steps = worker.join().unwrap().unwrap();
+    println!();
+    println!("{} steps in {} frames", steps, frames);
+}
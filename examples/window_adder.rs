@@ -0,0 +1,53 @@
+use turing::prelude::*;
+use turing::testing::adder_vectors;
+
+// Runs an adder on `a+b`, returning its answer and step count.
+fn add(rules: &RuleTable, a: &str, b: &str) -> (String, usize) {
+    let tape = build_tape(a, b, LeadingZeros::Strip).expect("test vectors are binary");
+    let mut machine = TuringMachine::new(tape, rules.clone());
+    machine.run_bounded(1_000_000).expect("adders halt");
+    (machine.result(), machine.steps())
+}
+
+// Number of distinct states named in a rule table, `Halt` included.
+fn state_count(rules: &RuleTable) -> usize {
+    let mut states: Vec<&State> = rules.iter().flat_map(|((state, _), (_, _, next))| [state, next]).collect();
+    states.sort();
+    states.dedup();
+    states.len()
+}
+
+// Compares the window adder with the classic one: their sizes, and their step counts on
+// the test vectors. tests/window_adder.rs checks the numbers.
+// Run with `cargo run --example window_adder`.
+fn main() {
+    let classic = rules::adder();
+    let window = rules::window_adder();
+    let single_cell = window.to_single_cell();
+    println!("classic adder: {} states, {} rules", state_count(&classic), classic.len());
+    println!(
+        "window adder:  {} states, {} rules of width {} ({} states, {} rules as single-cell rules)",
+        window.states().len(),
+        window.len(),
+        window.width(),
+        state_count(&single_cell),
+        single_cell.len()
+    );
+
+    println!("{:>10} {:>10} {:>12} {:>12}", "a", "b", "classic", "window");
+    let (mut classic_total, mut window_total) = (0, 0);
+    for (a, b, sum) in adder_vectors() {
+        let (classic_sum, classic_steps) = add(&classic, &a, &b);
+        let (window_sum, window_steps) = add(&single_cell, &a, &b);
+        if classic_sum != sum || window_sum != sum {
+            println!("{}+{}: classic {}, window {}, expected {}", a, b, classic_sum, window_sum, sum);
+        }
+        if a.len() + b.len() > 6 {
+            println!("{:>10} {:>10} {:>12} {:>12}", a, b, classic_steps, window_steps);
+        }
+        classic_total += classic_steps;
+        window_total += window_steps;
+    }
+    // Every window step is three single-cell steps, so the window adder's own steps are a third of those.
+    println!("total steps: classic {}, window {} ({} window steps)", classic_total, window_total, window_total / 3);
+}
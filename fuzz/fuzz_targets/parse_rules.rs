@@ -0,0 +1,19 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use turing::rules;
+
+// Rule files may come from anywhere: parsing must return `Err`, never panic, and
+// whatever parses must survive a round trip through the text export.
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(definition) = rules::parse_machine(text) else {
+        return;
+    };
+    let exported = rules::to_text_described(&definition.rules, &definition.descriptions);
+    let reparsed = rules::parse_machine(&exported).expect("exported rules must parse");
+    assert_eq!(reparsed.rules, definition.rules);
+    assert_eq!(reparsed.descriptions, definition.descriptions);
+});
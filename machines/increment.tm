@@ -0,0 +1,17 @@
+# Adds one to a binary number. Input: _n_ or n, with the head on the first cell.
+start Start
+final Done
+result whole_tape_trimmed
+
+# step onto the number, then walk right to the blank after it
+Start _ _ R Seek
+Start 0 0 R Seek
+Start 1 1 R Seek
+Seek 0 0 R Seek
+Seek 1 1 R Seek
+Seek _ _ L Increment
+
+# turn trailing ones into zeros until a zero (or the blank before the number) takes the one
+Increment 1 0 L Increment
+Increment 0 1 L Done
+Increment _ 1 L Done
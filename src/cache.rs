@@ -0,0 +1,46 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// A result remembered from an earlier run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResult {
+    pub result: String, // The decoded result.
+    pub steps: usize,   // Steps the machine took to produce it.
+}
+
+/// An on-disk cache of run results, keyed by the fingerprints of the rule table and
+/// the input tape. A changed rule table has a new fingerprint, so its old entries are
+/// simply never looked up again.
+pub struct ResultCache {
+    dir: PathBuf, // One file per entry lives here.
+}
+
+impl ResultCache {
+    /// Opens the cache in `dir`, creating the directory if needed.
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(ResultCache { dir })
+    }
+
+    // The file holding the entry for a (rules, tape) pair.
+    fn path(&self, rules: u64, tape: u64) -> PathBuf {
+        self.dir.join(format!("{:016x}-{:016x}", rules, tape))
+    }
+
+    /// Looks up the result for a (rules, tape) fingerprint pair. Unreadable entries count as misses.
+    pub fn get(&self, rules: u64, tape: u64) -> Option<CachedResult> {
+        let text = fs::read_to_string(self.path(rules, tape)).ok()?;
+        let (result, steps) = text.trim().split_once(' ')?;
+        Some(CachedResult {
+            result: result.to_string(),
+            steps: steps.parse().ok()?,
+        })
+    }
+
+    /// Stores the result for a (rules, tape) fingerprint pair.
+    pub fn put(&self, rules: u64, tape: u64, entry: &CachedResult) -> io::Result<()> {
+        fs::write(self.path(rules, tape), format!("{} {}\n", entry.result, entry.steps))
+    }
+}
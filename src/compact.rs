@@ -0,0 +1,102 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::tape::input::{is_cell_symbol, ParseError};
+use crate::{Configuration, RuleTable, Snapshot, State};
+
+pub use crate::error::ConfigParseError;
+
+impl Configuration<'_> {
+    /// The configuration on one line, as other simulators write it: the state, a space,
+    /// then the tape with the cell under the head bracketed, e.g. `GetLast _1010[1]+11_`.
+    pub fn to_compact_string(&self) -> String {
+        compact(self.state, self.tape.iter(), self.head)
+    }
+
+    /// The configuration on two lines: the state, a colon and the tape, then a line with
+    /// a caret under the cell the head is on.
+    pub fn to_caret_string(&self) -> String {
+        let prefix = format!("{}:", self.state).chars().count();
+        let tape: String = self.tape.iter().collect();
+        format!("{}:{}\n{:>width$}", self.state, tape, '^', width = prefix + self.head + 1)
+    }
+}
+
+impl Snapshot {
+    /// The snapshot on one line, see `Configuration::to_compact_string`.
+    pub fn to_compact_string(&self) -> String {
+        compact(&self.state, self.tape.iter(), self.head)
+    }
+}
+
+// Writes `state`, a space, then the tape with the head's cell bracketed.
+fn compact<'a>(state: &State, tape: impl Iterator<Item = &'a char>, head: usize) -> String {
+    let mut text = format!("{} ", state);
+    for (index, &cell) in tape.enumerate() {
+        if index == head {
+            text.extend(['[', cell, ']']);
+        } else {
+            text.push(cell);
+        }
+    }
+    text
+}
+
+/// Reads a configuration written by another simulator, or by `to_compact_string` or
+/// `to_caret_string`, to resume it with `TuringMachine::from_snapshot`.
+///
+/// The state comes first, followed by a space or a colon, then the tape cell by cell.
+/// The head is marked by bracketing its cell, `10[1]1+11`, or by a caret on the next
+/// line under its cell; there must be exactly one marker. The state must be one the
+/// rules start in or lead to, so a numbered state of a loaded machine is just `3`.
+pub fn parse_configuration(text: &str, rules: &RuleTable) -> Result<Snapshot, ConfigParseError> {
+    let mut lines = text.lines().skip_while(|line| line.trim().is_empty());
+    let line = lines.next().unwrap_or_default().trim_end();
+    let caret_line = lines.next().unwrap_or_default().trim_end();
+
+    let split = line.find([' ', ':']).filter(|&split| split > 0).ok_or(ConfigParseError::MissingState)?;
+    let (name, cells) = (&line[..split], &line[split + 1..]);
+    let prefix = name.chars().count() + 1;
+
+    let (mut tape, mut heads) = (Vec::new(), Vec::new());
+    let mut chars = cells.chars();
+    while let Some(c) = chars.next() {
+        if c == '[' {
+            let (Some(cell), Some(']')) = (chars.next(), chars.next()) else {
+                return Err(ConfigParseError::UnclosedBracket);
+            };
+            heads.push(tape.len());
+            tape.push(cell);
+        } else {
+            tape.push(c);
+        }
+    }
+    for (column, c) in caret_line.chars().enumerate() {
+        match c {
+            ' ' => {}
+            '^' if column >= prefix && column - prefix < tape.len() => heads.push(column - prefix),
+            '^' => return Err(ConfigParseError::CaretOffTape(column)),
+            c => return Err(ConfigParseError::CaretLine(c)),
+        }
+    }
+    let [head] = heads[..] else {
+        return Err(ConfigParseError::HeadMarkers(heads.len()));
+    };
+    if let Some((cell, &symbol)) = tape.iter().enumerate().find(|&(_, &symbol)| !is_cell_symbol(symbol)) {
+        return Err(ConfigParseError::Tape(ParseError::TapeSymbol { cell, symbol }));
+    }
+
+    let state = State::from_name(name);
+    let known = state == State::Halt
+        || rules.iter().any(|((from, _), (_, _, to))| *from == state || *to == state);
+    if !known {
+        return Err(ConfigParseError::UnknownState(String::from(name)));
+    }
+    Ok(Snapshot {
+        tape,
+        head,
+        state,
+        partial_value: None,
+    })
+}
@@ -0,0 +1,752 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::{Snapshot, State, TapeMode};
+
+/// Errors that can end a run before the machine halts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    StepLimitExceeded(usize),                             // The machine did not halt within the given number of steps.
+    HeadOutOfBounds { head: usize, len: usize },          // The head is off the tape, in `TapeMode::Strict` or `Fixed`.
+    Corrupted { step: usize, cell: usize, symbol: char }, // A sanity check found a symbol no rule knows on the tape.
+    Cycle { step: usize, period: usize },                 // The machine came back to a configuration, so it never halts.
+    Cancelled(usize),                                     // Whoever the run reported to went away after this many steps.
+    PastMarker { step: usize, marker: char },             // A move would have passed this end marker.
+    StuckOnMarker { state: State, marker: char },         // No rule applied on an end marker.
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::StepLimitExceeded(limit) => write!(f, "the machine did not halt within {} steps", limit),
+            RunError::HeadOutOfBounds { head, len } => {
+                write!(f, "the head is at cell {} but the tape has only {} cells", head, len)
+            }
+            RunError::Corrupted { step, cell, symbol } => {
+                write!(f, "after {} steps cell {} holds '{}', which no rule reads or writes", step, cell, symbol)
+            }
+            RunError::Cancelled(steps) => write!(f, "the run was cancelled after {} steps", steps),
+            RunError::Cycle { step, period } => {
+                let message = "the machine is back where it was";
+                write!(f, "after {} steps {} {} steps before, so it never halts", step, message, period)
+            }
+            RunError::PastMarker { step, marker } => {
+                write!(f, "step {} would have moved the head past the end marker '{}'", step, marker)
+            }
+            RunError::StuckOnMarker { state, marker } => {
+                write!(f, "state {} has no rule for the end marker '{}'", state, marker)
+            }
+        }
+    }
+}
+
+/// A move that is none of the accepted spellings, see `Direction`'s `FromStr`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectionParseError(pub String);
+
+impl fmt::Display for DirectionParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "'{}' is not a move, use L, Left, R, Right, S, Stay or N (in any case)", self.0)
+    }
+}
+
+/// A safety property that is none of the accepted spellings, see `Property`'s `FromStr`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyParseError(pub String);
+
+impl fmt::Display for PropertyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "'{}' is not a property, use no-write:SYMBOL or no-write-left", self.0)
+    }
+}
+
+/// Errors found while turning operands into a tape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidSymbol(char),                      // An operand contained something other than '0' or '1'.
+    ReservedMarker(char),                     // An operand contained 'I' or 'O', which the adder writes to mark the sum.
+    TooLarge(usize),                          // A binary number needs more bits than a `u64` holds; how many.
+    MissingOperator,                          // The input has no '+', so the machine would search for one forever.
+    ExtraOperator,                            // The input has more than one '+'.
+    MissingOperand { second: bool },          // Nothing before the '+', or after it when `second`.
+    EmptyInput,                               // The input is empty.
+    InvalidDecimalDigit(char),                // A decimal operand contained something other than a digit.
+    TapeSymbol { cell: usize, symbol: char }, // A tape cell holds something other than a printable ASCII symbol.
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidSymbol(symbol) => write!(f, "'{}' is not a binary digit", symbol),
+            ParseError::ReservedMarker(symbol) => write!(
+                f,
+                "'{}' is not a binary digit, and is reserved: the adder marks the sum's digits with 'I' and 'O'",
+                symbol
+            ),
+            ParseError::MissingOperator => write!(f, "the input has no '+' between the operands"),
+            ParseError::ExtraOperator => write!(f, "the input has more than one '+', but the adder adds two operands"),
+            ParseError::MissingOperand { second } => {
+                let side = if *second { "second operand after" } else { "first operand before" };
+                write!(f, "the {} the '+' is missing", side)
+            }
+            ParseError::EmptyInput => write!(f, "the input is empty, give two operands as in 1011+11"),
+            ParseError::TooLarge(bits) => write!(f, "a {}-bit number does not fit in 64 bits", bits),
+            ParseError::InvalidDecimalDigit(symbol) => write!(f, "'{}' is not a decimal digit", symbol),
+            ParseError::TapeSymbol { cell, symbol } => {
+                write!(f, "cell {} holds {:?} (U+{:04X}), but a cell holds one printable ASCII symbol", cell, symbol, *symbol as u32)
+            }
+        }
+    }
+}
+
+/// Errors found while reading a compact configuration, see `tape::parse_configuration`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigParseError {
+    MissingState,         // Nothing comes before the tape to name the state.
+    UnknownState(String), // The state is not one of the machine's.
+    HeadMarkers(usize),   // There is not exactly one head marker; how many there are.
+    UnclosedBracket,      // A `[` is not followed by one cell and a `]`.
+    CaretOffTape(usize),  // The caret is not under a cell of the tape; its column, from 0.
+    CaretLine(char),      // The line under the tape holds something besides spaces and a caret.
+    Tape(ParseError),     // The tape holds a symbol no cell can.
+}
+
+impl fmt::Display for ConfigParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigParseError::MissingState => write!(f, "the configuration does not start with a state"),
+            ConfigParseError::UnknownState(state) => write!(f, "the machine has no state '{}'", state),
+            ConfigParseError::HeadMarkers(0) => {
+                write!(f, "the configuration has no head marker, bracket a cell or put a ^ under it")
+            }
+            ConfigParseError::HeadMarkers(count) => {
+                write!(f, "the configuration has {} head markers, it needs exactly one", count)
+            }
+            ConfigParseError::UnclosedBracket => write!(f, "a '[' must be followed by one cell and a ']'"),
+            ConfigParseError::CaretOffTape(column) => write!(f, "the caret at column {} is not under a cell", column),
+            ConfigParseError::Tape(error) => error.fmt(f),
+            ConfigParseError::CaretLine(c) => write!(f, "'{}' in the caret line, which may hold only spaces and a ^", c),
+        }
+    }
+}
+
+/// Errors found while opening a JSON artifact's envelope, see `formats::open`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+    WrongFormat { expected: &'static str, found: String },                   // The file is another kind of artifact.
+    UnsupportedVersion { format: &'static str, version: i64, current: i64 }, // Neither `current` nor the one before.
+    Field(&'static str),                                                     // A missing or malformed envelope field.
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatError::WrongFormat { expected, found } => {
+                write!(f, "this is a {} file, not a {} file", found, expected)
+            }
+            FormatError::UnsupportedVersion { format, version, current } => write!(
+                f,
+                "this is version {} of the {} format, this version of tm reads versions {} and {}",
+                version,
+                format,
+                current - 1,
+                current
+            ),
+            FormatError::Field(field) => write!(f, "the envelope's `{}` is missing or malformed", field),
+        }
+    }
+}
+
+/// Where a JSON document stops making sense, see `Json`'s `FromStr`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    pub offset: usize,          // Byte offset of the problem in the document.
+    pub expected: &'static str, // What should have been there.
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid JSON at byte {}: expected {}", self.offset, self.expected)
+    }
+}
+
+/// Why a recorded trace doesn't hold together, see `replay::Trace::verify_consistency`.
+/// Events are numbered from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceError {
+    Json(JsonError),                                       // The trace file is not JSON.
+    Format(FormatError),                                   // The file is not a trace this version reads.
+    Field { event: Option<usize>, field: &'static str },   // A field is missing or malformed; in an event, or at the top.
+    State { event: usize, expected: State, found: State }, // The event's state isn't the one the previous event went to.
+    Head { event: usize, expected: isize, found: isize },  // The event's head isn't where the previous moves left it.
+    Read { event: usize, expected: char, found: char },    // The event read something other than what the tape holds.
+    FinalTape { expected: String, found: String },         // The claimed final tape isn't the one the events leave.
+    Result { claimed: String, actual: String },            // The claimed result isn't the one the events leave.
+}
+
+impl TraceError {
+    /// The event at fault, if the error is in one.
+    pub fn event(&self) -> Option<usize> {
+        match self {
+            TraceError::Field { event, .. } => *event,
+            TraceError::State { event, .. } | TraceError::Head { event, .. } | TraceError::Read { event, .. } => {
+                Some(*event)
+            }
+            TraceError::Json(_) | TraceError::Format(_) => None,
+            TraceError::FinalTape { .. } | TraceError::Result { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TraceError::Json(error) => error.fmt(f),
+            TraceError::Format(error) => error.fmt(f),
+            TraceError::Field { event: Some(event), field } => {
+                write!(f, "event {}: `{}` is missing or malformed", event, field)
+            }
+            TraceError::Field { event: None, field } => write!(f, "the trace's `{}` is missing or malformed", field),
+            TraceError::State { event, expected, found } => {
+                write!(f, "event {}: the machine is in state {} but the event says {}", event, expected, found)
+            }
+            TraceError::Head { event, expected, found } => {
+                write!(f, "event {}: the head is at cell {} but the event says {}", event, expected, found)
+            }
+            TraceError::Read { event, expected, found } => {
+                write!(f, "event {}: the cell holds '{}' but the event read '{}'", event, expected, found)
+            }
+            TraceError::FinalTape { expected, found } => {
+                write!(f, "the events leave the tape {} but the trace claims {}", found, expected)
+            }
+            TraceError::Result { claimed, actual } => {
+                write!(f, "the events leave the result {} but the trace claims {}", actual, claimed)
+            }
+        }
+    }
+}
+
+/// Errors found while assembling a rule table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    Duplicate(State, char), // Two rules share the same (state, symbol) key.
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleError::Duplicate(state, symbol) => {
+                write!(f, "there are two rules for state {} reading '{}'", state, symbol)
+            }
+        }
+    }
+}
+
+/// Errors found while composing rule tables, see `compose::Compose::merge`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComposeError {
+    Collision(State),    // A state with rules in one table appears in the other too.
+    UnknownState(State), // A state to rewire is not in its table, or an entry state has no rules.
+}
+
+impl fmt::Display for ComposeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ComposeError::Collision(state) => {
+                write!(f, "both tables use state {}, namespace one of them first", state)
+            }
+            ComposeError::UnknownState(state) => write!(f, "there is no state {} to wire", state),
+        }
+    }
+}
+
+/// Rules or a tape that break the bound of `TapeMode::Bounded`, see `bounded`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoundedError {
+    OverwritesMarker { state: State, marker: char, write: char }, // A rule reading an end marker writes something else.
+    WritesMarker { state: State, read: char, marker: char },      // A rule writes an end marker over another symbol.
+    MissingMarker(char),                                          // The tape doesn't start or end with this marker.
+    StrayMarker { cell: usize, marker: char },                    // An end marker inside the input.
+}
+
+impl fmt::Display for BoundedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoundedError::OverwritesMarker { state, marker, write } => {
+                write!(f, "the rule for {} reading '{}' overwrites the end marker with '{}'", state, marker, write)
+            }
+            BoundedError::WritesMarker { state, read, marker } => {
+                write!(f, "the rule for {} reading '{}' writes the end marker '{}'", state, read, marker)
+            }
+            BoundedError::MissingMarker(marker) => write!(f, "the tape is not bounded by the end marker '{}'", marker),
+            BoundedError::StrayMarker { cell, marker } => {
+                write!(f, "cell {} holds the end marker '{}' inside the input", cell, marker)
+            }
+        }
+    }
+}
+
+/// Errors found while assembling a window rule table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowRuleError {
+    Width { width: usize, symbols: String }, // A rule reads or writes a window of the wrong size.
+    Duplicate(State, String),                // Two rules share the same (state, window) key.
+}
+
+impl fmt::Display for WindowRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowRuleError::Width { width, symbols } => {
+                write!(f, "'{}' is not a window of {} symbols", symbols, width)
+            }
+            WindowRuleError::Duplicate(state, window) => {
+                write!(f, "there are two rules for state {} reading '{}'", state, window)
+            }
+        }
+    }
+}
+
+/// A line of a rule file that could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleParseError {
+    pub line: usize,              // 1-based line number in the rule file.
+    pub kind: RuleParseErrorKind, // What is wrong with it.
+}
+
+/// What is wrong with a line of a rule file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleParseErrorKind {
+    FieldCount(usize),              // The line doesn't have exactly five fields.
+    Symbol(String),                 // A symbol field is not a single printable ASCII character.
+    Direction(DirectionParseError), // The move is not one `Direction` accepts.
+    Duplicate(State, char),         // An earlier line already has a rule for this (state, symbol).
+    Directive(String),              // A `start`, `final` or `result` line that doesn't make sense.
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for RuleParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleParseErrorKind::FieldCount(count) => {
+                write!(f, "expected `state read write move next`, found {} fields", count)
+            }
+            RuleParseErrorKind::Symbol(symbol) => write!(f, "{:?} is not a single printable ASCII symbol", symbol),
+            RuleParseErrorKind::Direction(error) => error.fmt(f),
+            RuleParseErrorKind::Duplicate(state, symbol) => {
+                write!(f, "a rule for state {} reading '{}' already exists", state, symbol)
+            }
+            RuleParseErrorKind::Directive(line) => write!(f, "'{}' is not a valid start, final or result line", line),
+        }
+    }
+}
+
+/// Errors found while reading a machine in the JSON rule format, see `rules::from_json`.
+/// Rules are numbered from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleJsonError {
+    Json(JsonError),                                    // The file is not JSON.
+    Format(FormatError),                                // The file is not a machine this version reads.
+    Field { rule: Option<usize>, field: &'static str }, // A field is missing or malformed; in a rule, or at the top.
+    Rule { rule: usize, kind: RuleParseErrorKind },     // A rule doesn't make sense.
+    Result(RuleParseErrorKind),                         // The `result` strategy doesn't make sense.
+}
+
+impl fmt::Display for RuleJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleJsonError::Json(error) => error.fmt(f),
+            RuleJsonError::Format(error) => error.fmt(f),
+            RuleJsonError::Field { rule: Some(rule), field } => {
+                write!(f, "rule {}: `{}` is missing or malformed", rule, field)
+            }
+            RuleJsonError::Field { rule: None, field } => {
+                write!(f, "the machine's `{}` is missing or malformed", field)
+            }
+            RuleJsonError::Rule { rule, kind } => write!(f, "rule {}: {}", rule, kind),
+            RuleJsonError::Result(kind) => write!(f, "result: {}", kind),
+        }
+    }
+}
+
+/// Errors found while reading a test vector file, see `vectors::parse`. Vectors are
+/// numbered from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VectorsError {
+    Json(JsonError),                                      // The file is not JSON.
+    Format(FormatError),                                  // The file is not a vector file this version reads.
+    Field { vector: Option<usize>, field: &'static str }, // A field is missing or malformed, in a vector or at the top.
+}
+
+impl fmt::Display for VectorsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VectorsError::Json(error) => error.fmt(f),
+            VectorsError::Format(error) => error.fmt(f),
+            VectorsError::Field { vector: Some(vector), field } => {
+                write!(f, "vector {}: `{}` is missing or malformed", vector, field)
+            }
+            VectorsError::Field { vector: None, field } => write!(f, "the file's `{}` is missing or malformed", field),
+        }
+    }
+}
+
+/// A line of a turingmachine.io file that could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportError {
+    pub line: usize,           // 1-based line number in the file.
+    pub kind: ImportErrorKind, // What is wrong with it.
+}
+
+/// What is wrong with a turingmachine.io file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportErrorKind {
+    Syntax(String),         // A line that is not in the subset of YAML the importer reads.
+    Symbol(String),         // A symbol that is not a single printable ASCII character (or the blank).
+    Move(String),           // A transition without an `L`, `R` or `S` move.
+    Duplicate(State, char), // Two transitions for the same (state, symbol).
+    MissingTable,           // There is no `table`.
+    MissingStartState,      // There is no `start state`.
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ImportErrorKind::Syntax(text) => write!(f, "cannot read '{}'", text),
+            ImportErrorKind::Symbol(symbol) => write!(f, "{:?} is not a single printable ASCII symbol", symbol),
+            ImportErrorKind::Move(text) => write!(f, "'{}' has no L, R or S move", text),
+            ImportErrorKind::Duplicate(state, symbol) => {
+                write!(f, "a transition for state {} reading '{}' already exists", state, symbol)
+            }
+            ImportErrorKind::MissingTable => write!(f, "the file has no `table`"),
+            ImportErrorKind::MissingStartState => write!(f, "the file has no `start state`"),
+        }
+    }
+}
+
+/// A line of a bundle that could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleError {
+    pub line: usize,           // 1-based line number in the bundle.
+    pub kind: BundleErrorKind, // What is wrong with it.
+}
+
+/// What is wrong with a bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleErrorKind {
+    Syntax(String),              // A line that is not in the subset of TOML bundles use.
+    UnknownKey(String),          // A key bundles don't have.
+    Blank(String),               // A blank other than '_', which the machines can't use.
+    Machine(RuleParseErrorKind), // A line of the embedded rule file is wrong.
+    Tape(ParseError),            // An alphabet or example input holds a symbol no cell can.
+    Markers(String),             // `markers` isn't pairs of symbols like `I1 O0`.
+    MissingMachine,              // There is no `machine`.
+    IncompleteExample,           // An `[[example]]` without both `input` and `output`.
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            BundleErrorKind::Syntax(text) => write!(f, "cannot read '{}'", text),
+            BundleErrorKind::UnknownKey(key) => write!(f, "'{}' is not a bundle key", key),
+            BundleErrorKind::Blank(blank) => write!(f, "the blank must be '_', not '{}'", blank),
+            BundleErrorKind::Machine(kind) => kind.fmt(f),
+            BundleErrorKind::Tape(error) => error.fmt(f),
+            BundleErrorKind::Markers(text) => {
+                write!(f, "'{}' is not a list of markers, each followed by what it stands for, as in \"I1 O0\"", text)
+            }
+            BundleErrorKind::MissingMachine => write!(f, "the bundle has no `machine`"),
+            BundleErrorKind::IncompleteExample => write!(f, "an example needs both an `input` and an `output`"),
+        }
+    }
+}
+
+/// The rules of a procedural machine, which a function computes, can't be listed, see
+/// `TuringMachine::finite_rules`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotEnumerable;
+
+impl fmt::Display for NotEnumerable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hint = "list them over its states and alphabet with `enumerate_rules`";
+        write!(f, "the machine computes its rules with a function, so they can't be listed; {}", hint)
+    }
+}
+
+/// Why a machine can't be compiled, see `TuringMachine::compile`: its rules can't be
+/// listed, or it has a setting the compiled machine doesn't honour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileError {
+    NotEnumerable(NotEnumerable), // The machine computes its rules with a function.
+    TapeMode(TapeMode),           // The tape mode is not `TapeMode::Grow`.
+    TapeLimit(usize),             // The tape may grow to at most this many cells.
+    Sentinels,                    // End markers bound the tape.
+    Oracle(State),                // An oracle is registered on this state.
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unsupported = "which a compiled machine doesn't support";
+        match self {
+            CompileError::NotEnumerable(error) => error.fmt(f),
+            CompileError::TapeMode(mode) => write!(f, "the machine runs in tape mode {:?}, {}", mode, unsupported),
+            CompileError::TapeLimit(cells) => {
+                write!(f, "the machine's tape may grow to at most {} cells, {}", cells, unsupported)
+            }
+            CompileError::Sentinels => write!(f, "the machine's tape is bounded by sentinels, {}", unsupported),
+            CompileError::Oracle(state) => write!(f, "the machine has an oracle on {}, {}", state, unsupported),
+        }
+    }
+}
+
+impl From<NotEnumerable> for CompileError {
+    fn from(error: NotEnumerable) -> Self {
+        CompileError::NotEnumerable(error)
+    }
+}
+
+/// Errors found while building a machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    HeadOutOfRange { head: usize, len: usize },      // The starting head position is not on the tape.
+    TapeTooLong { required: usize, allowed: usize }, // The input alone is longer than the tape may grow.
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::HeadOutOfRange { head, len } => {
+                write!(f, "the head starts at cell {} but the tape has only {} cells", head, len)
+            }
+            BuildError::TapeTooLong { required, allowed } => write!(
+                f,
+                "the input needs {} cells but the tape may hold only {}, so the machine can never finish",
+                required, allowed
+            ),
+        }
+    }
+}
+
+/// A run that failed in one of the stages of a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineError {
+    pub stage: usize,    // Index of the stage that failed.
+    pub error: RunError, // Why it failed.
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "stage {}: {}", self.stage, self.error)
+    }
+}
+
+/// An invariant that failed during a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvariantViolation {
+    pub step: usize,        // Steps taken when the violation was found.
+    pub message: String,    // What the invariant reported.
+    pub snapshot: Snapshot, // The offending configuration.
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invariant violated after {} steps: {}", self.step, self.message)
+    }
+}
+
+/// Any error from this library, for callers that would rather handle just one type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Run(RunError),                  // A run did not finish.
+    Parse(ParseError),              // Operands could not be turned into a tape.
+    ConfigParse(ConfigParseError),  // A compact configuration could not be read.
+    Direction(DirectionParseError), // A move could not be read.
+    Property(PropertyParseError),   // A safety property could not be read.
+    Rule(RuleError),                // Rules could not be assembled into a table.
+    Compose(ComposeError),          // Rule tables could not be merged.
+    Bounded(BoundedError),          // Rules or a tape break the bound of a bounded tape.
+    WindowRule(WindowRuleError),    // Window rules could not be assembled into a table.
+    RuleParse(RuleParseError),      // A rule file could not be read.
+    Import(ImportError),            // A turingmachine.io file could not be imported.
+    Bundle(BundleError),            // A bundle could not be read.
+    Build(BuildError),              // A machine could not be built.
+    NotEnumerable(NotEnumerable),   // A procedural machine's rules were asked for as a table.
+    Compile(CompileError),          // A machine could not be compiled.
+    Pipeline(PipelineError),        // A pipeline stage failed.
+    Invariant(InvariantViolation),  // An invariant failed during a run.
+    Json(JsonError),                // A JSON document could not be read.
+    Format(FormatError),            // A JSON artifact is of another kind or version.
+    Trace(TraceError),              // A recorded trace could not be read or doesn't hold together.
+    RuleJson(RuleJsonError),        // A machine in the JSON rule format could not be read.
+    Vectors(VectorsError),          // A test vector file could not be read.
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Run(error) => error.fmt(f),
+            Error::Parse(error) => error.fmt(f),
+            Error::ConfigParse(error) => error.fmt(f),
+            Error::Direction(error) => error.fmt(f),
+            Error::Property(error) => error.fmt(f),
+            Error::Rule(error) => error.fmt(f),
+            Error::Compose(error) => error.fmt(f),
+            Error::Bounded(error) => error.fmt(f),
+            Error::WindowRule(error) => error.fmt(f),
+            Error::RuleParse(error) => error.fmt(f),
+            Error::Import(error) => error.fmt(f),
+            Error::Bundle(error) => error.fmt(f),
+            Error::Build(error) => error.fmt(f),
+            Error::NotEnumerable(error) => error.fmt(f),
+            Error::Compile(error) => error.fmt(f),
+            Error::Pipeline(error) => error.fmt(f),
+            Error::Invariant(error) => error.fmt(f),
+            Error::Json(error) => error.fmt(f),
+            Error::Format(error) => error.fmt(f),
+            Error::Trace(error) => error.fmt(f),
+            Error::RuleJson(error) => error.fmt(f),
+            Error::Vectors(error) => error.fmt(f),
+        }
+    }
+}
+
+// Lets `?` turn each specific error into an `Error`.
+macro_rules! wrap {
+    ($($variant:ident($error:ty)),* $(,)?) => {
+        $(
+            impl From<$error> for Error {
+                fn from(error: $error) -> Self {
+                    Error::$variant(error)
+                }
+            }
+        )*
+    };
+}
+
+wrap!(
+    Run(RunError),
+    Parse(ParseError),
+    ConfigParse(ConfigParseError),
+    Direction(DirectionParseError),
+    Property(PropertyParseError),
+    Rule(RuleError),
+    Compose(ComposeError),
+    Bounded(BoundedError),
+    WindowRule(WindowRuleError),
+    RuleParse(RuleParseError),
+    Import(ImportError),
+    Bundle(BundleError),
+    Build(BuildError),
+    NotEnumerable(NotEnumerable),
+    Compile(CompileError),
+    Pipeline(PipelineError),
+    Invariant(InvariantViolation),
+    Json(JsonError),
+    Format(FormatError),
+    Trace(TraceError),
+    RuleJson(RuleJsonError),
+    Vectors(VectorsError),
+);
+
+#[cfg(feature = "std")]
+mod std_error {
+    use super::*;
+
+    impl std::error::Error for RunError {}
+    impl std::error::Error for ParseError {}
+    impl std::error::Error for ConfigParseError {}
+    impl std::error::Error for DirectionParseError {}
+    impl std::error::Error for PropertyParseError {}
+    impl std::error::Error for RuleError {}
+    impl std::error::Error for ComposeError {}
+    impl std::error::Error for BoundedError {}
+    impl std::error::Error for WindowRuleError {}
+    impl std::error::Error for RuleParseError {}
+    impl std::error::Error for ImportError {}
+    impl std::error::Error for BundleError {}
+    impl std::error::Error for BuildError {}
+    impl std::error::Error for NotEnumerable {}
+    impl std::error::Error for InvariantViolation {}
+    impl std::error::Error for JsonError {}
+    impl std::error::Error for FormatError {}
+
+    impl std::error::Error for CompileError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                CompileError::NotEnumerable(error) => Some(error),
+                _ => None,
+            }
+        }
+    }
+
+    impl std::error::Error for TraceError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                TraceError::Json(error) => Some(error),
+                TraceError::Format(error) => Some(error),
+                _ => None,
+            }
+        }
+    }
+
+    impl std::error::Error for RuleJsonError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                RuleJsonError::Json(error) => Some(error),
+                RuleJsonError::Format(error) => Some(error),
+                _ => None,
+            }
+        }
+    }
+
+    impl std::error::Error for VectorsError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                VectorsError::Json(error) => Some(error),
+                VectorsError::Format(error) => Some(error),
+                _ => None,
+            }
+        }
+    }
+
+    impl std::error::Error for PipelineError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.error)
+        }
+    }
+
+    impl std::error::Error for Error {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                Error::Run(error) => error.source(),
+                Error::Parse(error) => error.source(),
+                Error::ConfigParse(error) => error.source(),
+                Error::Direction(error) => error.source(),
+                Error::Property(error) => error.source(),
+                Error::Rule(error) => error.source(),
+                Error::Compose(error) => error.source(),
+                Error::Bounded(error) => error.source(),
+                Error::WindowRule(error) => error.source(),
+                Error::RuleParse(error) => error.source(),
+                Error::Import(error) => error.source(),
+                Error::Bundle(error) => error.source(),
+                Error::Build(error) => error.source(),
+                Error::NotEnumerable(error) => error.source(),
+                Error::Compile(error) => error.source(),
+                Error::Pipeline(error) => error.source(),
+                Error::Invariant(error) => error.source(),
+                Error::Json(error) => error.source(),
+                Error::Format(error) => error.source(),
+                Error::Trace(error) => error.source(),
+                Error::RuleJson(error) => error.source(),
+                Error::Vectors(error) => error.source(),
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use core::hash::{Hash, Hasher};
+
+use crate::{rules, RuleTable, Tape, TuringMachine};
+
+/// A content hash that stays the same across runs, platforms and compiler versions,
+/// unlike `std`'s `DefaultHasher`. Used to key cached results.
+pub trait Fingerprint {
+    /// A 64-bit FNV-1a hash of the value's canonical form.
+    fn fingerprint(&self) -> u64;
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// Feeds the UTF-8 encoding of `chars` into an FNV-1a hash.
+fn fnv1a(chars: impl IntoIterator<Item = char>) -> u64 {
+    let mut hasher = Fnv(FNV_OFFSET);
+    let mut buffer = [0; 4];
+    for c in chars {
+        hasher.write(c.encode_utf8(&mut buffer).as_bytes());
+    }
+    hasher.finish()
+}
+
+// An FNV-1a hasher, for values that only have a `Hash` impl.
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Fingerprint for RuleTable {
+    // Hashes the sorted text form, so insertion order doesn't matter.
+    fn fingerprint(&self) -> u64 {
+        fnv1a(rules::to_text(self).chars())
+    }
+}
+
+impl Fingerprint for Tape {
+    fn fingerprint(&self) -> u64 {
+        fnv1a(self.iter().copied())
+    }
+}
+
+impl Fingerprint for [char] {
+    // Same as the `Tape` holding these symbols.
+    fn fingerprint(&self) -> u64 {
+        fnv1a(self.iter().copied())
+    }
+}
+
+impl TuringMachine {
+    /// A 64-bit key for the state, head and tape up to its last non-blank, for `set_cycle_detection`.
+    pub fn config_key(&self) -> u64 {
+        let mut hasher = Fnv(FNV_OFFSET);
+        self.state().hash(&mut hasher);
+        self.head().hash(&mut hasher);
+        let end = self.tape().iter().rposition(|&symbol| symbol != '_').map_or(0, |last| last + 1);
+        for symbol in self.tape().range(..end) {
+            symbol.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
@@ -0,0 +1,1360 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use error::{BuildError, NotEnumerable};
+use tape::display::DisplayMap;
+use tape::input;
+
+pub use error::{DirectionParseError, Error, RunError};
+pub use machine::builder::TuringMachineBuilder;
+pub use machine::compiled::CompiledMachine;
+pub use machine::simulate::{simulate, Simulation};
+pub use tape::extract::ResultStrategy;
+pub use tape::layout::TapeLayout;
+pub use trace::{Snapshot, Verbosity};
+
+pub mod error;
+pub mod machine;
+pub mod prelude;
+pub mod registry;
+pub mod rules;
+pub mod tape;
+#[doc(hidden)]
+pub mod testing;
+pub mod trace;
+
+mod compact;
+mod fingerprint;
+#[doc(hidden)]
+mod reference;
+#[doc(hidden)]
+mod rng;
+
+/// Represents the possible states of the Turing machine can be in.
+///
+/// States are ordered as declared, so they can key a `BTreeMap`: the adder's states
+/// in the order they are entered, then `Named` states by name, then `Halt`.
+#[derive(Hash, Eq, PartialEq, PartialOrd, Ord, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum State {
+    FindPlus,//moves to the + symbol
+    GetLast,//gets the last digit to the left of the +
+    AddOne,//remembers that a 1 has to be added to the end of the second number
+    AddZero,//remembers that a 0 has to be added to the end of the second number
+    AddDigitZero,//adds zero to the last non-altered digit of the second number(turns 0 to O and 1 to I)
+    AddDigitOne,//adds one to the last non-altered digit of the second number
+    Carry,//carries an additional one to the digits further left of the last addDigitOne if a 1 was encountered
+    BackToStart,//moves back to start of the tape
+    Named(String),//a state of some other machine, for rule tables that aren't the adder
+    Halt,//We did it! :D
+}
+
+impl State {
+    /// Looks a state up by name: one of the adder's states, or else a `Named` state.
+    pub fn from_name(name: &str) -> State {
+        match name {
+            "FindPlus" => State::FindPlus,
+            "GetLast" => State::GetLast,
+            "AddOne" => State::AddOne,
+            "AddZero" => State::AddZero,
+            "AddDigitZero" => State::AddDigitZero,
+            "AddDigitOne" => State::AddDigitOne,
+            "Carry" => State::Carry,
+            "BackToStart" => State::BackToStart,
+            "Halt" => State::Halt,
+            name => State::Named(name.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for State {
+    // Prints the bare name, which `State::from_name` reads back.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            State::Named(name) => write!(f, "{}", name),
+            state => write!(f, "{:?}", state),
+        }
+    }
+}
+
+/// Represents the direction the head can move on the tape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    Left,  // Move one step to the left.
+    Right, // Move one step to the right.
+    Stay,  // Stay on the same cell.
+}
+
+impl fmt::Display for Direction {
+    // The short form used in rule files and exports: `L`, `R` or `S`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Direction::Left => "L",
+            Direction::Right => "R",
+            Direction::Stay => "S",
+        })
+    }
+}
+
+impl core::str::FromStr for Direction {
+    type Err = DirectionParseError;
+
+    /// Reads `L`/`Left`, `R`/`Right` or `S`/`Stay`/`N` (for "no move"), in any case.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_ascii_lowercase().as_str() {
+            "l" | "left" => Ok(Direction::Left),
+            "r" | "right" => Ok(Direction::Right),
+            "s" | "stay" | "n" => Ok(Direction::Stay),
+            _ => Err(DirectionParseError(text.to_string())),
+        }
+    }
+}
+
+/// What the machine does when its head is off the tape, which only happens when it was
+/// started that way, e.g. on an empty tape, and whether the tape may grow at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TapeMode {
+    #[default]
+    Grow,   // Pad the tape with blanks up to the head, as when the head walks off either end.
+    Strict, // Halt; the bounded runs report `RunError::HeadOutOfBounds`.
+    Fixed,  // As `Strict`, and the tape keeps its length, like a register: a move off an end halts with `HaltReason::Overflow`.
+    // As `Fixed`, with the input between two end markers: a linear bounded automaton, see `bounded`.
+    Bounded { left_marker: char, right_marker: char },
+}
+
+/// End-of-input markers that bound the tape, e.g. `<` and `>` on `_<1011>_`, see
+/// `TuringMachine::set_sentinels`. The head never moves onto either: such a move
+/// reflects or halts, so the tape doesn't grow past them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sentinels {
+    pub left: char,             // Marks the left end of the input.
+    pub right: char,            // Marks the right end of the input.
+    pub on_contact: OnSentinel, // What a move onto either does.
+}
+
+/// What a move onto a sentinel does, see `Sentinels`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnSentinel {
+    #[default]
+    Halt,    // Halt with `HaltReason::Sentinel`, after the rule's write.
+    Reflect, // Stay on the cell instead; the rule's write and next state still apply.
+}
+
+/// Why a machine halted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HaltReason {
+    Finished(State),                              // It stopped in one of its final states, or a rule sent it to `Halt`.
+    Stuck { state: State, symbol: char },         // No rule applied in a state that isn't final.
+    HeadOutOfBounds,                              // The head was off the tape in `TapeMode::Strict` or `Fixed`.
+    Overflow(Direction),                          // A move this way would have grown the tape in `TapeMode::Fixed`.
+    Sentinel(char),                               // A move would have put the head on this sentinel, see `Sentinels`.
+    TapeLimitExceeded(usize),                     // A move would have grown the tape past this many cells.
+    PastMarker(char),                             // A move would have passed this end marker, in `TapeMode::Bounded`.
+    StuckOnMarker { state: State, marker: char }, // No rule applied on an end marker, in `TapeMode::Bounded`.
+}
+
+/// The tape: a row of symbols that grows with blanks ('_') whenever the head walks off either end.
+pub type Tape = VecDeque<char>;
+
+/// A hook that reads and answers on the tape when the machine enters its oracle state.
+/// It gets the head position, but can't move the head. It is `Send`, so a machine with an
+/// oracle can still be handed to another thread.
+pub type Oracle = Box<dyn FnMut(&mut Tape, isize) + Send>;
+
+/// The transition rules: (current state, read symbol) -> (write symbol, move, next state).
+pub type RuleTable = HashMap<(State, char), (char, Direction, State)>;
+
+/// Computes the rule for a state and the symbol read, as `(write, move, next state)`, or
+/// `None` to halt, for a machine defined by a function rather than a table, see
+/// `TuringMachine::with_transition_fn`. It is `Send`, so procedural machines can be handed
+/// to another thread as any machine can.
+pub type TransitionFn = Box<dyn Fn(&State, char) -> Option<(char, Direction, State)> + Send>;
+
+/// Explanations of rules in words, kept apart from the rule table so lookups stay lean.
+pub type RuleDescriptions = HashMap<(State, char), String>;
+
+/// Decodes the adder's sum from a final tape, see `TuringMachine::result`.
+pub fn decode_result<'a>(tape: impl IntoIterator<Item = &'a char>) -> String {
+    ResultStrategy::adder().extract(&tape.into_iter().copied().collect(), None)
+}
+
+// The rule for `state` reading `read`: computed if the machine has a transition function,
+// else looked up in the table. Takes the fields rather than the machine, so that a step
+// can update the rest of the machine while it holds the rule.
+fn lookup<'a>(
+    rules: &'a RuleTable,
+    transition_fn: &Option<TransitionFn>,
+    state: &State,
+    read: char,
+) -> Option<Cow<'a, (char, Direction, State)>> {
+    match transition_fn {
+        Some(transition_fn) => transition_fn(state, read).map(Cow::Owned),
+        None => rules.get(&(state.clone(), read)).map(Cow::Borrowed),
+    }
+}
+
+// Warns about a tape a machine is given that doesn't start with a blank.
+fn warn_unblanked(tape: &[char]) {
+    if tape.first() != Some(&'_') {
+        log::warn!("the tape does not start with a blank, so it will grow left if the head walks off it");
+    }
+}
+
+/// A borrowed view of the machine's configuration, handed to observers during a run.
+#[derive(Debug, Clone, Copy)]
+pub struct Configuration<'a> {
+    pub tape: &'a Tape,      // The current contents of the tape.
+    pub head: usize,         // The current position of the head on the tape.
+    pub logical_head: isize, // The head's logical cell, see `TuringMachine::logical_head`.
+    pub state: &'a State,    // The current state of the machine.
+    pub steps: usize,        // Number of rules applied so far.
+}
+
+/// Represents the Turing machine.
+pub struct TuringMachine {
+    tape: Tape,                         // The tape holds symbols (e.g., '0', '1', '+', '_'). A deque so growing left is O(1).
+    head: usize,                        // The current position of the head on the tape.
+    state: State,                       // The current state of the machine.
+    start: State,                       // The state it was in before its first step, see `reset_with_input`.
+    rules: Arc<RuleTable>,              // Transition rules, shared with other machines running them.
+    procedural: Option<TransitionFn>,   // Computes the rules in place of `rules`, for a procedural machine.
+    descriptions: RuleDescriptions,     // Explanations of the rules, for `Verbosity::Explain`.
+    steps: usize,                       // Number of rules applied so far.
+    head_travel: usize,                 // Cells the head has moved so far, counting every move as one.
+    separator_consumed: bool,           // Whether a rule wrote over a '+', see `separator_consumed`.
+    grown_left: usize,                  // Cells the tape has grown on the left, see `logical_head`.
+    halted_in: Option<State>,           // The state in which no rule applied, once halted that way.
+    halt_reason: Option<HaltReason>,    // Why the machine halted, once it has.
+    final_states: Vec<State>,           // States that halting in counts as finishing, see `HaltReason`.
+    result_strategy: ResultStrategy,    // How `result` reads the answer.
+    #[cfg(feature = "std")]
+    verbosity: Verbosity,               // What `run` logs at every step.
+    oracle: Option<(State, Oracle)>,    // The oracle state and its hook, if any.
+    oracle_calls: usize,                // Number of times the oracle was consulted.
+    tracing: bool,                      // Whether `run` records a snapshot at every step.
+    partial_values: bool,               // Whether snapshots carry the answer region's value.
+    sanity_interval: Option<usize>,     // Steps between the bounded runs' checks of the tape's symbols, if checked.
+    tape_mode: TapeMode,                // What to do when the head is off the tape.
+    sentinels: Option<Sentinels>,       // The markers bounding the tape, if any.
+    max_tape_len: Option<usize>,        // The most cells the tape may grow to, if limited.
+    cycle_detection: Option<bool>,      // Whether the bounded runs stop at a repeated configuration, `None` to follow the tape mode.
+    layout: Option<TapeLayout>,         // Named regions of the tape, kept up to date as it runs, if known.
+    operands: Option<(String, String)>, // The adder's operands as they were before the run, once captured.
+    trace: Vec<Snapshot>,               // The recorded snapshots, oldest first.
+    snapshot_capacity: usize,           // How many of the latest configurations `last_snapshots` keeps.
+    last_snapshots: VecDeque<Snapshot>, // The latest configurations, oldest first, see `last_snapshots`.
+    #[cfg(feature = "std")]
+    window: Option<usize>,              // Cells shown around the head in printed tapes longer than this, `None` for the full tape.
+    display_map: DisplayMap,            // How printed tapes and rules show the symbols.
+}
+
+impl fmt::Debug for TuringMachine {
+    // One line, even with `{:#?}`: the rules would drown everything else.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tape: String = self.tape.iter().collect();
+        write!(
+            f,
+            "TuringMachine {{ state: {:?}, head: {}, tape: {:?}, steps: {} }}",
+            self.state, self.head, tape, self.steps
+        )
+    }
+}
+
+/// Two machines are equal when they are in the same configuration, the same tape cell
+/// for cell with the head on the same cell in the same state, and have the same rules,
+/// so they will also go on the same way. Everything else is left out: the steps taken,
+/// the settings, the trace and why they halted. Tapes that differ only in blanks grown
+/// at the ends differ; see `same_configuration` to leave the rules out too.
+impl PartialEq for TuringMachine {
+    fn eq(&self, other: &Self) -> bool {
+        // Functions can't be compared, so procedural machines only equal themselves.
+        let same_rules = match (&self.procedural, &other.procedural) {
+            (None, None) => self.rules == other.rules,
+            (Some(f), Some(g)) => core::ptr::addr_eq(f.as_ref(), g.as_ref()),
+            _ => false,
+        };
+        self.same_configuration(other) && same_rules
+    }
+}
+
+impl Eq for TuringMachine {}
+
+/// The tape's symbols, left to right: `for symbol in &machine`.
+impl<'a> IntoIterator for &'a TuringMachine {
+    type Item = char;
+    type IntoIter = core::iter::Copied<alloc::collections::vec_deque::Iter<'a, char>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tape.iter().copied()
+    }
+}
+
+impl TuringMachine {
+    /// Creates a new Turing machine with the given tape and rules.
+    pub fn new(tape: Vec<char>, rules: RuleTable) -> Self {
+        Self::with_shared_rules(tape, Arc::new(rules))
+    }
+
+    /// Creates a new Turing machine like `new`, on rules it shares rather than owns, so
+    /// that many machines, e.g. one per batch worker, can run one table without copying it.
+    pub fn with_shared_rules(tape: Vec<char>, rules: Arc<RuleTable>) -> Self {
+        warn_unblanked(&tape);
+        TuringMachine {
+            tape: tape.into(),
+            head: 0, // Start at the first position on the tape.
+            state: State::FindPlus, // Initial state to locate the '+'.
+            start: State::FindPlus,
+            rules,
+            procedural: None,
+            descriptions: RuleDescriptions::new(),
+            steps: 0,
+            head_travel: 0,
+            separator_consumed: false,
+            grown_left: 0,
+            halted_in: None,
+            halt_reason: None,
+            final_states: vec![State::GetLast], // The adder halts when GetLast finds no digit left.
+            result_strategy: ResultStrategy::adder(),
+            #[cfg(feature = "std")]
+            verbosity: Verbosity::Quiet,
+            oracle: None,
+            oracle_calls: 0,
+            tracing: false,
+            partial_values: false,
+            sanity_interval: None,
+            tape_mode: TapeMode::Grow,
+            sentinels: None,
+            max_tape_len: None,
+            cycle_detection: None,
+            layout: None,
+            operands: None,
+            trace: Vec::new(),
+            snapshot_capacity: 0,
+            last_snapshots: VecDeque::new(),
+            #[cfg(feature = "std")]
+            window: Some(trace::DEFAULT_WINDOW),
+            display_map: DisplayMap::new(),
+        }
+    }
+
+    /// Creates an adder that adds `operand` to the answer on `previous`, a final tape of
+    /// an earlier addition. See `input::chain_tape` for the tape it starts on.
+    pub fn chained(previous: &Tape, operand: &str) -> Result<Self, input::ParseError> {
+        let tape = input::chain_tape(previous, operand, input::LeadingZeros::Strip)?;
+        Ok(Self::new(tape, rules::adder()))
+    }
+
+    /// Creates a machine adding two operands given as bits, most significant first, see
+    /// `input::bits_tape`. Read the answer back with `result_bits` or `result_bytes`.
+    pub fn from_bits(a: &[bool], b: &[bool], rules: RuleTable) -> Self {
+        Self::new(input::bits_tape(a, b), rules)
+    }
+
+    /// Creates a machine adding two big-endian byte strings, every bit of them, see `from_bits`.
+    pub fn from_bytes(a: &[u8], b: &[u8], rules: RuleTable) -> Self {
+        Self::from_bits(&input::bytes_to_bits(a), &input::bytes_to_bits(b), rules)
+    }
+
+    /// Appends another operand to the tape: trims its trailing blanks, keeping the first
+    /// cell, then adds `+value` and a blank, so `_1011+11_` becomes `_1011+11+101_`.
+    /// Checks that `value` is binary, keeping any leading zeros. Call it before running.
+    ///
+    /// The adder itself only adds two operands: to add more with it, chain additions
+    /// with `chained` instead.
+    pub fn push_operand(&mut self, value: &str) -> Result<(), input::ParseError> {
+        let value = input::operand(value, input::LeadingZeros::Preserve)?;
+        while self.tape.len() > 1 && self.tape.back() == Some(&'_') {
+            self.tape.pop_back();
+        }
+        self.tape.push_back('+');
+        self.tape.extend(value.chars());
+        self.tape.push_back('_');
+        Ok(())
+    }
+
+    /// Creates a new Turing machine from flat `(state, read, write, move, next state)` rules.
+    pub fn with_rules(tape: Vec<char>, rules: impl IntoIterator<Item = rules::Rule>) -> Result<Self, rules::RuleError> {
+        Ok(Self::new(tape, rules::from_rules(rules)?))
+    }
+
+    /// Enables or disables logging the configuration at every step of `run`.
+    #[cfg(feature = "std")]
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbosity = if verbose { Verbosity::Steps } else { Verbosity::Quiet };
+    }
+
+    /// What `run` logs at every step.
+    #[cfg(feature = "std")]
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    /// Sets what `run` logs at every step; `Verbosity::Rules` also echoes the rule that fires.
+    #[cfg(feature = "std")]
+    pub fn set_verbosity(&mut self, verbosity: Verbosity) {
+        self.verbosity = verbosity;
+    }
+
+    /// Sets the explanations of the rules that `Verbosity::Explain` narrates with.
+    /// Rules without one are explained from their fields alone.
+    pub fn set_descriptions(&mut self, descriptions: RuleDescriptions) {
+        self.descriptions = descriptions;
+    }
+
+    /// The explanations of the rules, see `set_descriptions`.
+    pub fn descriptions(&self) -> &RuleDescriptions {
+        &self.descriptions
+    }
+
+    /// Sets how many cells around the head the printed trace shows once the tape is longer
+    /// than that. `None` always prints the full tape.
+    #[cfg(feature = "std")]
+    pub fn set_trace_window(&mut self, window: Option<usize>) {
+        self.window = window;
+    }
+
+    /// Sets how printed tapes and rules show the symbols, e.g. `DisplayMap::adder` to show
+    /// the adder's markers as the digits they stand for. The tape and the rules are left
+    /// as they are. The default shows every symbol as itself.
+    pub fn set_display_map(&mut self, display_map: DisplayMap) {
+        self.display_map = display_map;
+    }
+
+    /// How printed tapes and rules show the symbols.
+    pub fn display_map(&self) -> &DisplayMap {
+        &self.display_map
+    }
+
+    /// Sets the state the machine is in, e.g. the start state of a machine that isn't the adder.
+    /// Before the first step, this is also the state `reset_with_input` goes back to.
+    pub fn set_state(&mut self, state: State) {
+        if self.steps == 0 {
+            self.start = state.clone();
+        }
+        self.state = state;
+    }
+
+    /// Makes the machine ready to run again, on `tape`, without rebuilding it: the head
+    /// goes back to the first cell and the machine to the state it started in, and the step
+    /// count, the other counters, the halt reason, the layout and the recorded snapshots
+    /// start over. The rules and every setting stay, and operands recorded with
+    /// `preserve_operands` are recorded again from the new tape. A tape limit stays as well,
+    /// stopping the new tape from growing past it.
+    pub fn reset_with_input(&mut self, tape: Vec<char>) {
+        warn_unblanked(&tape);
+        self.tape = tape.into();
+        self.head = 0;
+        self.state = self.start.clone();
+        self.steps = 0;
+        self.head_travel = 0;
+        self.separator_consumed = false;
+        self.grown_left = 0;
+        self.halted_in = None;
+        self.halt_reason = None;
+        self.oracle_calls = 0;
+        self.layout = None;
+        if self.operands.is_some() {
+            self.preserve_operands();
+        }
+        self.trace.clear();
+        self.set_snapshot_capacity(self.snapshot_capacity);
+    }
+
+    /// Registers `oracle` to be called whenever a rule moves the machine into `state`,
+    /// before the next step. Only one oracle can be registered at a time.
+    pub fn set_oracle(&mut self, state: State, oracle: impl FnMut(&mut Tape, isize) + Send + 'static) {
+        self.oracle = Some((state, Box::new(oracle)));
+    }
+
+    /// Number of times the oracle was consulted. These are not counted in `steps`.
+    pub fn oracle_calls(&self) -> usize {
+        self.oracle_calls
+    }
+
+    /// Enables or disables recording a snapshot of the configuration at every step of `run`.
+    pub fn set_tracing(&mut self, tracing: bool) {
+        self.tracing = tracing;
+    }
+
+    /// Keeps the last `capacity` configurations in a ring buffer, see `last_snapshots`, for
+    /// a look at how a long run got where it failed without the memory full tracing takes.
+    /// Starts over from the current configuration; 0, the default, keeps none.
+    pub fn set_snapshot_capacity(&mut self, capacity: usize) {
+        self.snapshot_capacity = capacity;
+        self.last_snapshots.clear();
+        if capacity > 0 {
+            self.last_snapshots.push_back(self.snapshot());
+        }
+    }
+
+    /// Enables or disables recording the answer region's value in snapshots, see `partial_value`.
+    pub fn set_partial_values(&mut self, partial_values: bool) {
+        self.partial_values = partial_values;
+    }
+
+    /// Makes the bounded runs check the tape every `interval` steps, failing with
+    /// `RunError::Corrupted` if it holds a symbol that no rule reads or writes. A safety
+    /// net for experimental rule tables and oracles. `None`, the default, disables it.
+    pub fn set_sanity_interval(&mut self, interval: Option<usize>) {
+        self.sanity_interval = interval.filter(|&interval| interval > 0);
+    }
+
+    /// Makes the bounded runs remember every configuration by its `config_key` and fail with
+    /// `RunError::Cycle` when one comes back, as the machine would then loop forever. On by
+    /// default in `TapeMode::Strict` and `Fixed`, where a machine that doesn't halt must
+    /// loop, and off in `Grow`, where it can also run away along the tape and the keys
+    /// would take memory for nothing.
+    pub fn set_cycle_detection(&mut self, enabled: bool) {
+        self.cycle_detection = Some(enabled);
+    }
+
+    /// The value of the answer region right of the '+' cells, if it currently
+    /// holds a clean number: not while a digit is being written or a carry is rippling, and
+    /// only when it fits in a `u64`.
+    pub fn partial_value(&self) -> Option<u64> {
+        if matches!(self.state, State::AddDigitZero | State::AddDigitOne | State::Carry) {
+            return None;
+        }
+        let separator = self.tape.iter().rposition(|&c| c == '+')?;
+        let digits = decode_result(self.tape.range(separator..));
+        if digits.is_empty() || digits.len() > 64 || digits.chars().any(|c| c != '0' && c != '1') {
+            return None;
+        }
+        u64::from_str_radix(&digits, 2).ok()
+    }
+
+    /// The column of the addition the adder is working on, counted from the least
+    /// significant bit. While it takes a digit of the first operand, carries it right and
+    /// adds it in, that is the number of columns already added (the `I`/`O` markers);
+    /// while a carry ripples, it is the column the carry has reached. `None` on the walks
+    /// to the `+` and back to the start between columns, once halted, and in states that
+    /// aren't the adder's.
+    pub fn current_bit_index(&self) -> Option<usize> {
+        if self.halt_reason.is_some() {
+            return None;
+        }
+        let is_marker = |c: &char| *c == 'I' || *c == 'O';
+        let added = self.tape.iter().filter(|c| is_marker(c)).count();
+        match self.state {
+            // No digit left to take, so it is about to halt.
+            State::GetLast if !matches!(self.symbol(), '0' | '1') => None,
+            State::GetLast | State::AddZero | State::AddOne | State::AddDigitZero | State::AddDigitOne => Some(added),
+            State::Carry => {
+                // The carry starts on the cell left of the first marker, column `added`.
+                let first_marker = self.tape.iter().position(is_marker)?;
+                Some(added + first_marker.checked_sub(self.head + 1)?)
+            }
+            _ => None,
+        }
+    }
+
+    /// The snapshots recorded while tracing, ending with the halted configuration.
+    pub fn trace(&self) -> &[Snapshot] {
+        &self.trace
+    }
+
+    /// The last configurations the machine was in, oldest first and ending with the current
+    /// one, as many as `set_snapshot_capacity` asked for. Every step, however it was taken,
+    /// adds one and drops the oldest once the buffer is full.
+    pub fn last_snapshots(&self) -> &VecDeque<Snapshot> {
+        &self.last_snapshots
+    }
+
+    /// The tape drawn as boxed cells, the head's in heavy lines, see `trace::render_boxed`.
+    pub fn render_boxed(&self) -> String {
+        trace::render_boxed(self.tape.iter(), self.head, &self.display_map)
+    }
+
+    /// A snapshot of the current configuration.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            tape: self.tape.iter().copied().collect(),
+            head: self.head,
+            state: self.state.clone(),
+            partial_value: if self.partial_values { self.partial_value() } else { None },
+        }
+    }
+
+    /// The transition rules.
+    pub fn rules(&self) -> &RuleTable {
+        &self.rules
+    }
+
+    /// The transition rules as shared, for `with_shared_rules`.
+    pub fn shared_rules(&self) -> Arc<RuleTable> {
+        Arc::clone(&self.rules)
+    }
+
+    /// The current contents of the tape.
+    pub fn tape(&self) -> &Tape {
+        &self.tape
+    }
+
+    /// Consumes the machine, returning its tape.
+    pub fn into_tape(self) -> Vec<char> {
+        self.tape.into()
+    }
+
+    /// The current position of the head on the tape.
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// The current state of the machine.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Number of rules applied so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Whether a rule has written over a `+`, as the adder does when the sum outgrows the second operand.
+    pub fn separator_consumed(&self) -> bool {
+        self.separator_consumed
+    }
+
+    /// Total distance the head has moved so far: the steps taken, less those that stayed
+    /// put. Moves onto newly grown cells count too. For the adder, which never stays, it
+    /// equals the step count.
+    pub fn head_travel(&self) -> usize {
+        self.head_travel
+    }
+
+    /// How many cells the tape has grown on the left: the first cell of the tape the
+    /// machine was created with is now at this index.
+    pub fn grown_left(&self) -> usize {
+        self.grown_left
+    }
+
+    /// The head's logical cell: 0 is the first cell of the tape the machine was created
+    /// with, and cells the tape grew on the left are negative, as in `TapeLayout`. Unlike
+    /// `head`, it doesn't shift when the tape grows left.
+    pub fn logical_head(&self) -> isize {
+        self.head as isize - self.grown_left as isize
+    }
+
+    /// Roughly how many more steps the machine takes to halt, for a progress bar, judged
+    /// by its state, its head and its tape as if it were the adder: see
+    /// `progress::adder_remaining_steps`. `None` if the configuration isn't the adder's,
+    /// e.g. in a `Named` state.
+    pub fn estimated_remaining_steps(&self) -> Option<usize> {
+        machine::progress::adder_remaining_steps(&self.tape, self.head, &self.state)
+    }
+
+    /// Reads the answer off the tape with the machine's result strategy. For the adder, that is
+    /// the digits right of the consumed first operand, with the `I`/`O` markers read back as
+    /// `1`/`0`. The width follows the operands, see `input::LeadingZeros`.
+    /// With a tape layout, the adder's answer is read from its `operand_b` region instead.
+    pub fn result(&self) -> String {
+        self.layout_result()
+            .unwrap_or_else(|| self.result_strategy.extract(&self.tape, self.halted_in.as_ref()))
+    }
+
+    /// The answer as bits, most significant first, or `None` if it isn't binary.
+    pub fn result_bits(&self) -> Option<Vec<bool>> {
+        let bit = |c| match c {
+            '0' => Some(false),
+            '1' => Some(true),
+            _ => None,
+        };
+        self.result().chars().map(bit).collect()
+    }
+
+    /// The answer as big-endian bytes, padded with zeros on the left to whole bytes, or
+    /// `None` if it isn't binary. An empty answer gives no bytes.
+    pub fn result_bytes(&self) -> Option<Vec<u8>> {
+        let bits = self.result_bits()?;
+        let padding = (8 - bits.len() % 8) % 8;
+        let padded: Vec<bool> = core::iter::repeat_n(false, padding).chain(bits).collect();
+        Some(padded.chunks(8).map(|byte| byte.iter().fold(0, |value, &bit| value << 1 | bit as u8)).collect())
+    }
+
+    /// Number of significant bits in the answer, 0 for a zero result, without decoding it.
+    /// Leading zeros and markers read as zeros don't count, see `ResultStrategy::bit_length`.
+    pub fn result_bit_length(&self) -> usize {
+        self.result_strategy.bit_length(&self.tape)
+    }
+
+    /// Records the adder's operands from the tape as it is now, so they can still be shown
+    /// once the run has consumed the first one. Call it before running.
+    pub fn preserve_operands(&mut self) {
+        let cells: String = self.tape.iter().collect();
+        let (a, b) = cells.trim_matches('_').split_once('+').unwrap_or((&cells, ""));
+        let b = b.split('_').next().unwrap_or_default();
+        self.operands = Some((String::from(a), String::from(b)));
+    }
+
+    /// The operands recorded by `preserve_operands`.
+    pub fn operands(&self) -> Option<(&str, &str)> {
+        self.operands.as_ref().map(|(a, b)| (a.as_str(), b.as_str()))
+    }
+
+    /// The whole addition, e.g. `1011 + 11 = 1110`, if the operands were preserved.
+    /// An empty operand shows as `0`.
+    pub fn equation(&self) -> Option<String> {
+        let or_zero = |operand: &str| if operand.is_empty() { String::from("0") } else { String::from(operand) };
+        let (a, b) = self.operands()?;
+        Some(format!("{} + {} = {}", or_zero(a), or_zero(b), self.result()))
+    }
+
+    /// Sets what happens when the head is off the tape, see `TapeMode`.
+    pub fn set_tape_mode(&mut self, tape_mode: TapeMode) {
+        self.tape_mode = tape_mode;
+    }
+
+    /// Sets the markers that bound the tape, or `None` (the default) for a tape bounded
+    /// only by the tape mode. The markers are ordinary symbols for reading and writing;
+    /// only moving onto one is special, see `Sentinels`.
+    pub fn set_sentinels(&mut self, sentinels: Option<Sentinels>) {
+        self.sentinels = sentinels;
+    }
+
+    /// Limits the tape to `max_tape_len` cells, or lifts the limit with `None` (the default).
+    /// A move that would grow the tape past the limit halts with `HaltReason::TapeLimitExceeded`.
+    /// Fails, leaving the machine as it was, if the tape is already longer: the tape never
+    /// shrinks, so such a machine could never finish.
+    pub fn set_max_tape_len(&mut self, max_tape_len: Option<usize>) -> Result<(), BuildError> {
+        if let Some(allowed) = max_tape_len.filter(|&allowed| self.tape.len() > allowed) {
+            return Err(BuildError::TapeTooLong {
+                required: self.tape.len(),
+                allowed,
+            });
+        }
+        self.max_tape_len = max_tape_len;
+        Ok(())
+    }
+
+    /// Sets the named regions of the tape, as made by `input::build_tape_with_layout`. The
+    /// machine keeps them up to date as it runs, see `TapeLayout`.
+    pub fn set_layout(&mut self, layout: TapeLayout) {
+        self.layout = Some(layout);
+    }
+
+    /// The tape's named regions, if it has a layout.
+    pub fn layout(&self) -> Option<&TapeLayout> {
+        self.layout.as_ref()
+    }
+
+    /// Sets how `result` reads the answer, for machines other than the adder.
+    pub fn set_result_strategy(&mut self, strategy: ResultStrategy) {
+        self.result_strategy = strategy;
+    }
+
+    /// The state the machine was in when it found no rule to apply and halted.
+    pub fn halted_in(&self) -> Option<&State> {
+        self.halted_in.as_ref()
+    }
+
+    /// Sets the states in which halting means the machine finished its work, rather than
+    /// got stuck. The adder's, `GetLast`, by default.
+    pub fn set_final_states(&mut self, final_states: Vec<State>) {
+        self.final_states = final_states;
+    }
+
+    /// The states in which halting means the machine finished its work, see `set_final_states`.
+    pub fn final_states(&self) -> &[State] {
+        &self.final_states
+    }
+
+    /// Why the machine halted, or `None` while it hasn't.
+    pub fn halt_reason(&self) -> Option<&HaltReason> {
+        self.halt_reason.as_ref()
+    }
+
+    /// Whether the machine halted having finished, e.g. the adder completed the addition,
+    /// as opposed to getting stuck on a missing rule or not having halted at all.
+    pub fn finished_successfully(&self) -> bool {
+        matches!(self.halt_reason, Some(HaltReason::Finished(_)))
+    }
+
+    /// Whether the machine halted because the tape would have had to grow, in `TapeMode::Fixed`.
+    pub fn overflowed(&self) -> bool {
+        matches!(self.halt_reason, Some(HaltReason::Overflow(_)))
+    }
+
+    /// Creates a procedural machine, whose rules `transition_fn` computes when they are
+    /// needed instead of looking them up in a table, e.g. from a state's number. It steps,
+    /// traces, counts and stops at step limits as any machine does, but `rules` is empty:
+    /// see `finite_rules` and `enumerate_rules` for exporters that need every rule, and
+    /// don't `compile` it. Sanity checks are skipped, as the symbols it knows are unknown.
+    pub fn with_transition_fn(
+        tape: Vec<char>,
+        transition_fn: impl Fn(&State, char) -> Option<(char, Direction, State)> + Send + 'static,
+    ) -> Self {
+        let mut machine = TuringMachine::new(tape, RuleTable::new());
+        machine.procedural = Some(Box::new(transition_fn));
+        machine
+    }
+
+    /// The rule table, or `NotEnumerable` for a procedural machine, whose rules can't be
+    /// listed; for exporters such as `rules::to_dot` or `Fingerprint` that need them all.
+    pub fn finite_rules(&self) -> Result<&RuleTable, NotEnumerable> {
+        match self.procedural {
+            Some(_) => Err(NotEnumerable),
+            None => Ok(&self.rules),
+        }
+    }
+
+    /// The rules for every state in `states` reading every symbol in `alphabet`, as a
+    /// table, whether they are looked up or computed. Lists a procedural machine's rules
+    /// given its states and alphabet.
+    pub fn enumerate_rules(&self, states: &[State], alphabet: &[char]) -> RuleTable {
+        let mut rules = RuleTable::new();
+        for state in states {
+            for &read in alphabet {
+                if let Some(rule) = self.rule(state, read) {
+                    rules.insert((state.clone(), read), rule.into_owned());
+                }
+            }
+        }
+        rules
+    }
+
+    /// Executes one step of the Turing machine.
+    /// A head off the tape is handled first, see `TapeMode`.
+    pub fn step(&mut self) {
+        self.apply_step();
+        if self.snapshot_capacity > 0 {
+            if self.last_snapshots.len() == self.snapshot_capacity {
+                self.last_snapshots.pop_front();
+            }
+            self.last_snapshots.push_back(self.snapshot());
+        }
+    }
+
+    // Applies the rule for the symbol under the head, or halts, see `step`.
+    fn apply_step(&mut self) {
+        if self.settle_head().is_err() {
+            self.state = State::Halt;
+            self.halt_reason = Some(HaltReason::HeadOutOfBounds);
+            return;
+        }
+
+        // Get the current symbol under the head.
+        let current_symbol = self.tape[self.head];
+
+        // Look up the transition rule for the current state and symbol.
+        if let Some(rule) = lookup(&self.rules, &self.procedural, &self.state, current_symbol) {
+            let (write, direction, next_state) = (rule.0, &rule.1, &rule.2);
+            log::trace!(
+                "step {}: state {} reads '{}' at cell {}, writes '{}', moves {}, goes to {}",
+                self.steps + 1,
+                self.state,
+                current_symbol,
+                self.head,
+                write,
+                direction,
+                next_state
+            );
+
+            // Update the symbol under the head.
+            self.separator_consumed |= current_symbol == '+' && write != '+';
+            self.tape[self.head] = write;
+            if let Some(layout) = &mut self.layout {
+                layout.record_write(self.head, write);
+            }
+
+            // A bounded tape ends at its markers, so a move past either violates the bound.
+            if let TapeMode::Bounded { left_marker, right_marker } = self.tape_mode {
+                let past = match direction {
+                    Direction::Left if current_symbol == left_marker => Some(left_marker),
+                    Direction::Right if current_symbol == right_marker => Some(right_marker),
+                    _ => None,
+                };
+                if let Some(marker) = past {
+                    log::debug!("moving {} from cell {} would pass the end marker '{}'", direction, self.head, marker);
+                    self.steps += 1;
+                    self.halt_reason = Some(HaltReason::PastMarker(marker));
+                    self.state = State::Halt;
+                    return;
+                }
+            }
+
+            // A fixed-width tape can't grow, so a move off either end halts the machine.
+            let fixed = matches!(self.tape_mode, TapeMode::Fixed | TapeMode::Bounded { .. });
+            if self.grows_tape(direction) && fixed {
+                log::debug!("overflow: moving {} from cell {} would grow the tape", direction, self.head);
+                self.steps += 1;
+                self.halt_reason = Some(HaltReason::Overflow(direction.clone()));
+                self.state = State::Halt;
+                return;
+            }
+
+            // A move onto a sentinel halts, or is turned into staying put.
+            let mut direction = direction.clone();
+            if let Some(sentinel) = self.sentinel_ahead(&direction) {
+                log::debug!("the head ran into '{}' moving {} from cell {}", sentinel, direction, self.head);
+                if self.sentinels.is_some_and(|sentinels| sentinels.on_contact == OnSentinel::Halt) {
+                    self.steps += 1;
+                    self.halt_reason = Some(HaltReason::Sentinel(sentinel));
+                    self.state = State::Halt;
+                    return;
+                }
+                direction = Direction::Stay;
+            }
+
+            // A tape at its limit can't grow, so a move off either end halts the machine.
+            let at_limit = |limit: usize| self.tape.len() >= limit && self.grows_tape(&direction);
+            if let Some(limit) = self.max_tape_len.filter(|&limit| at_limit(limit)) {
+                log::debug!("moving {} from cell {} would grow the tape past {} cells", direction, self.head, limit);
+                self.steps += 1;
+                self.halt_reason = Some(HaltReason::TapeLimitExceeded(limit));
+                self.state = State::Halt;
+                return;
+            }
+
+            // Move the head in the specified direction.
+            match direction {
+                Direction::Left => {
+                    if self.head > 0 {
+                        self.head -= 1;
+                    } else {
+                        // If at the start, expand the tape to the left.
+                        self.tape.push_front('_');
+                        self.grown_left += 1;
+                        if let Some(layout) = &mut self.layout {
+                            layout.grew_left();
+                        }
+                        log::debug!("the tape grew left to {} cells", self.tape.len());
+                    }
+                }
+                Direction::Right => {
+                    self.head += 1;
+                    if self.head >= self.tape.len() {
+                        // If at the end, expand the tape to the right.
+                        self.tape.push_back('_');
+                        log::debug!("the tape grew right to {} cells", self.tape.len());
+                    }
+                }
+                Direction::Stay => {}
+            }
+            if direction != Direction::Stay {
+                self.head_travel += 1;
+            }
+
+            // Transition to the next state.
+            self.state = next_state.clone();
+            self.steps += 1;
+            if self.state == State::Halt {
+                self.halt_reason = Some(HaltReason::Finished(State::Halt));
+            }
+
+            // Let the oracle answer if we just entered its state.
+            if let Some((oracle_state, oracle)) = &mut self.oracle {
+                if *oracle_state == self.state {
+                    oracle(&mut self.tape, self.head as isize);
+                    self.oracle_calls += 1;
+                    log::debug!("the oracle answered in state {} at cell {}", self.state, self.head);
+                    // The oracle may have shortened the tape; grow it back under the head.
+                    while self.tape.len() <= self.head {
+                        self.tape.push_back('_');
+                    }
+                }
+            }
+        } else {
+            // If no rule applies, halt the machine.
+            let state = core::mem::replace(&mut self.state, State::Halt);
+            let on_marker = match self.tape_mode {
+                TapeMode::Bounded { left_marker, right_marker } => {
+                    current_symbol == left_marker || current_symbol == right_marker
+                }
+                _ => false,
+            };
+            self.halt_reason = Some(if self.final_states.contains(&state) {
+                log::debug!("halted after {} steps in final state {}", self.steps, state);
+                HaltReason::Finished(state.clone())
+            } else if on_marker {
+                log::warn!(
+                    "stuck after {} steps in state {} on the end marker '{}'",
+                    self.steps,
+                    state,
+                    current_symbol
+                );
+                HaltReason::StuckOnMarker {
+                    state: state.clone(),
+                    marker: current_symbol,
+                }
+            } else {
+                log::warn!("stuck after {} steps in state {} reading '{}'", self.steps, state, current_symbol);
+                HaltReason::Stuck {
+                    state: state.clone(),
+                    symbol: current_symbol,
+                }
+            });
+            self.halted_in = Some(state);
+        }
+    }
+
+    /// Runs the Turing machine until it reaches the Halt state.
+    pub fn run(&mut self) {
+        while self.state != State::Halt {
+            self.trace_step();
+            self.step();
+        }
+        self.trace_final();
+    }
+
+    /// Runs the Turing machine until it halts, handing the starting configuration and the
+    /// configuration after every step to `observer`. Stops at the first error it returns.
+    pub fn run_observed<E>(&mut self, mut observer: impl FnMut(&Configuration) -> Result<(), E>) -> Result<(), E> {
+        self.run_hooked(|machine| observer(&machine.configuration()))
+    }
+
+    // `run_observed` for hooks that need the machine itself, e.g. to corrupt its tape.
+    pub(crate) fn run_hooked<E>(&mut self, mut hook: impl FnMut(&mut Self) -> Result<(), E>) -> Result<(), E> {
+        hook(self)?;
+        while self.state != State::Halt {
+            self.trace_step();
+            self.step();
+            hook(self)?;
+        }
+        self.trace_final();
+        Ok(())
+    }
+
+    /// Runs the Turing machine until it halts, calling `f` with
+    /// `(from state, read, write, move, to state)` for every rule applied.
+    /// The final step that finds no rule and halts is not reported.
+    pub fn run_observing(&mut self, mut f: impl FnMut(State, char, char, &Direction, &State)) {
+        while self.state != State::Halt {
+            let from = self.state.clone();
+            let read = self.symbol();
+            let fired = self.rule(&from, read).map(Cow::into_owned);
+            self.trace_step();
+            self.step();
+            if let Some((write, direction, to)) = fired {
+                f(from, read, write, &direction, &to);
+            }
+        }
+        self.trace_final();
+    }
+
+    /// Runs the Turing machine until it halts, returning the final tape and a snapshot of
+    /// the starting configuration and of the one after every step, as `trace` holds with
+    /// tracing on. Needs no flag and leaves `trace` alone.
+    pub fn run_collecting_trace(&mut self) -> (Vec<char>, Vec<Snapshot>) {
+        let mut trace = vec![self.snapshot()];
+        while self.state != State::Halt {
+            self.trace_step();
+            self.step();
+            trace.push(self.snapshot());
+        }
+        self.trace_final();
+        (self.tape.iter().copied().collect(), trace)
+    }
+
+    /// Whether both machines have the same tape, head and state, whatever their rules,
+    /// e.g. to check that two rule tables took a tape to the same place. See `PartialEq`.
+    pub fn same_configuration(&self, other: &Self) -> bool {
+        self.state == other.state && self.head == other.head && self.tape == other.tape
+    }
+
+    /// A view of the current configuration.
+    pub fn configuration(&self) -> Configuration<'_> {
+        Configuration {
+            tape: &self.tape,
+            head: self.head,
+            logical_head: self.logical_head(),
+            state: &self.state,
+            steps: self.steps,
+        }
+    }
+
+    /// Runs the Turing machine until it halts or `max_steps` rules have been applied.
+    /// Returns the number of steps taken.
+    pub fn run_bounded(&mut self, max_steps: usize) -> Result<usize, RunError> {
+        self.run_bounded_with_progress(max_steps, usize::MAX, |_| {})
+    }
+
+    /// Runs as `run_bounded` does, handing the machine to `progress` every `every` steps,
+    /// e.g. to show how a long run is going. The run checks for cycles across the calls.
+    pub fn run_bounded_with_progress(
+        &mut self,
+        max_steps: usize,
+        every: usize,
+        mut progress: impl FnMut(&Self),
+    ) -> Result<usize, RunError> {
+        let mut seen = HashMap::new();
+        let mut next_report = self.steps.saturating_add(every.max(1));
+        while self.state != State::Halt {
+            if self.steps >= max_steps {
+                return Err(RunError::StepLimitExceeded(max_steps));
+            }
+            if self.steps >= next_report {
+                progress(self);
+                next_report = self.steps.saturating_add(every.max(1));
+            }
+            self.settle_head()?;
+            self.check_cycle(&mut seen)?;
+            self.trace_step();
+            self.step();
+            self.check_sanity()?;
+            self.check_bounds()?;
+        }
+        self.trace_final();
+        Ok(self.steps)
+    }
+
+    /// Runs the Turing machine until the tape satisfies `pred`, the machine halts, or
+    /// `max_steps` rules have been applied. `pred` is checked before every step.
+    /// Returns whether `pred` held, as opposed to the machine halting first.
+    pub fn run_until(&mut self, pred: impl Fn(&[char]) -> bool, max_steps: usize) -> Result<bool, RunError> {
+        let mut seen = HashMap::new();
+        loop {
+            if pred(self.tape.make_contiguous()) {
+                return Ok(true);
+            }
+            if self.state == State::Halt {
+                self.trace_final();
+                return Ok(false);
+            }
+            if self.steps >= max_steps {
+                return Err(RunError::StepLimitExceeded(max_steps));
+            }
+            self.settle_head()?;
+            self.check_cycle(&mut seen)?;
+            self.trace_step();
+            self.step();
+            self.check_sanity()?;
+            self.check_bounds()?;
+        }
+    }
+
+    // In `TapeMode::Bounded`, turns a halt on an end marker into the error it is.
+    fn check_bounds(&self) -> Result<(), RunError> {
+        match &self.halt_reason {
+            Some(HaltReason::PastMarker(marker)) => Err(RunError::PastMarker {
+                step: self.steps,
+                marker: *marker,
+            }),
+            Some(HaltReason::StuckOnMarker { state, marker }) => Err(RunError::StuckOnMarker {
+                state: state.clone(),
+                marker: *marker,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    // When cycle detection is on, records the configuration's key with the step it was
+    // reached at, failing if it was reached before.
+    fn check_cycle(&self, seen: &mut HashMap<u64, usize>) -> Result<(), RunError> {
+        if !self.cycle_detection.unwrap_or(self.tape_mode != TapeMode::Grow) {
+            return Ok(());
+        }
+        match seen.insert(self.config_key(), self.steps) {
+            Some(earlier) => Err(RunError::Cycle {
+                step: self.steps,
+                period: self.steps - earlier,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    // Makes sure the head is on the tape, growing the tape or failing as the tape mode says.
+    fn settle_head(&mut self) -> Result<(), RunError> {
+        if self.head < self.tape.len() {
+            return Ok(());
+        }
+        match self.tape_mode {
+            TapeMode::Grow => {
+                log::debug!("the head is off the tape at cell {}, growing it from {} cells", self.head, self.tape.len());
+                self.tape.resize(self.head + 1, '_');
+                Ok(())
+            }
+            TapeMode::Strict | TapeMode::Fixed | TapeMode::Bounded { .. } => Err(RunError::HeadOutOfBounds {
+                head: self.head,
+                len: self.tape.len(),
+            }),
+        }
+    }
+
+    // When a sanity check is due, checks that every symbol on the tape is a blank or one
+    // the rules read or write.
+    fn check_sanity(&self) -> Result<(), RunError> {
+        match self.sanity_interval {
+            Some(interval) if self.steps.is_multiple_of(interval) && self.procedural.is_none() => {}
+            _ => return Ok(()),
+        }
+        let sentinel = |symbol: char| self.sentinels.is_some_and(|s| symbol == s.left || symbol == s.right);
+        let known = |symbol: char| {
+            symbol == '_'
+                || sentinel(symbol)
+                || self
+                    .rules
+                    .iter()
+                    .any(|((_, read), (write, _, _))| *read == symbol || *write == symbol)
+        };
+        match self.tape.iter().position(|&symbol| !known(symbol)) {
+            Some(cell) => Err(RunError::Corrupted {
+                step: self.steps,
+                cell,
+                symbol: self.tape[cell],
+            }),
+            None => Ok(()),
+        }
+    }
+
+    // Whether a move this way takes the head off an end, growing the tape.
+    fn grows_tape(&self, direction: &Direction) -> bool {
+        match direction {
+            Direction::Left => self.head == 0,
+            Direction::Right => self.head + 1 == self.tape.len(),
+            Direction::Stay => false,
+        }
+    }
+
+    // The sentinel a move this way would put the head on, if any.
+    fn sentinel_ahead(&self, direction: &Direction) -> Option<char> {
+        let sentinels = self.sentinels?;
+        let cell = match direction {
+            Direction::Left => self.head.checked_sub(1)?,
+            Direction::Right => self.head + 1,
+            Direction::Stay => return None,
+        };
+        self.tape.get(cell).copied().filter(|&symbol| symbol == sentinels.left || symbol == sentinels.right)
+    }
+
+    // The rule for `state` reading `read`, see `lookup`.
+    pub(crate) fn rule(&self, state: &State, read: char) -> Option<Cow<'_, (char, Direction, State)>> {
+        lookup(&self.rules, &self.procedural, state, read)
+    }
+
+    // The symbol under the head, a blank if the head is off the tape.
+    fn symbol(&self) -> char {
+        self.tape.get(self.head).copied().unwrap_or('_')
+    }
+
+    // Debugging output: Shows the tape, head position, and current state at each step,
+    // as a record for `trace::STEP_TARGET`.
+    fn trace_step(&mut self) {
+        if self.tracing {
+            self.trace.push(self.snapshot());
+        }
+        #[cfg(feature = "std")]
+        match self.verbosity {
+            Verbosity::Quiet => {}
+            Verbosity::Steps => log::info!(
+                target: trace::STEP_TARGET,
+                "Tape: {}, Head: {}, State: {:?}",
+                self.render_tape(),
+                self.head,
+                self.state
+            ),
+            Verbosity::Rules => log::info!(
+                target: trace::STEP_TARGET,
+                "Tape: {}, Head: {}, State: {:?}  {}",
+                self.render_tape(),
+                self.head,
+                self.state,
+                self.render_rule()
+            ),
+            Verbosity::Explain => log::info!(target: trace::STEP_TARGET, "{}", self.explain_rule()),
+        }
+    }
+
+    // Final state and tape output after halting.
+    fn trace_final(&mut self) {
+        if self.tracing {
+            self.trace.push(self.snapshot());
+        }
+        #[cfg(feature = "std")]
+        if self.verbosity == Verbosity::Explain {
+            let tape = self.render_tape();
+            let halted = format!("The machine halted after {} steps, leaving the tape {}.", self.steps, tape);
+            log::info!(target: trace::STEP_TARGET, "{}", halted);
+        } else if self.verbosity != Verbosity::Quiet {
+            let (tape, head, state) = (self.render_tape(), self.head, &self.state);
+            log::info!(target: trace::STEP_TARGET, "Final Tape: {}, Head: {}, State: {:?}", tape, head, state);
+        }
+    }
+
+    // The rule about to fire, as echoed in the trace, e.g. `[AddOne,1 -> 1,R,AddOne]`.
+    #[cfg(feature = "std")]
+    fn render_rule(&self) -> String {
+        let read = self.symbol();
+        match self.rule(&self.state, read).as_deref() {
+            Some((write, direction, next_state)) => format!(
+                "[{},{} -> {},{},{}]",
+                self.state,
+                self.show(read),
+                self.show(*write),
+                direction,
+                next_state
+            ),
+            None => format!("[{},{} -> no rule, halt]", self.state, self.show(read)),
+        }
+    }
+
+    // The rule about to fire as a sentence, e.g. `In state GetLast, reading '1' at cell 10:
+    // erase it and remember we must add one (write '+', move right, state AddOne).`
+    #[cfg(feature = "std")]
+    fn explain_rule(&self) -> String {
+        let read = self.symbol();
+        let key = (self.state.clone(), read);
+        let situation = format!("In state {}, reading '{}' at cell {}", self.state, self.show(read), self.head);
+        let Some((write, direction, next_state)) = self.rule(&self.state, read).map(Cow::into_owned) else {
+            return format!("{}: no rule applies, so the machine halts.", situation);
+        };
+        let movement = match direction {
+            Direction::Left => "move left",
+            Direction::Right => "move right",
+            Direction::Stay => "stay put",
+        };
+        match self.descriptions.get(&key) {
+            Some(description) => format!(
+                "{}: {} (write '{}', {}, state {}).",
+                situation,
+                description,
+                self.show(write),
+                movement,
+                next_state
+            ),
+            None => format!(
+                "{}: write '{}', {} and go to state {}.",
+                situation,
+                self.show(write),
+                movement,
+                next_state
+            ),
+        }
+    }
+
+    // A symbol as printed in the trace, see `set_display_map`.
+    #[cfg(feature = "std")]
+    fn show(&self, symbol: char) -> String {
+        self.display_map.show(symbol)
+    }
+
+    // The tape as printed in the trace, windowed around the head when it is too long.
+    #[cfg(feature = "std")]
+    fn render_tape(&self) -> String {
+        match self.window {
+            Some(width) if self.tape.len() > width => {
+                trace::render_window(self.tape.iter(), self.head, width, &self.display_map)
+            }
+            _ if self.display_map.is_empty() => format!("{:?}", self.tape),
+            _ => {
+                let cells: Vec<String> = self.tape.iter().map(|&symbol| format!("'{}'", self.show(symbol))).collect();
+                format!("[{}]", cells.join(", "))
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+
+use crate::{RuleDescriptions, RuleTable, Snapshot, State, TuringMachine};
+
+pub use crate::error::BuildError;
+
+/// Configures a machine before it starts: where the head starts and in which state.
+pub struct TuringMachineBuilder {
+    tape: Vec<char>,                // The initial tape.
+    rules: RuleTable,               // Transition rules.
+    head: usize,                    // Where the head starts, 0 by default.
+    state: State,                   // The start state, `FindPlus` by default.
+    descriptions: RuleDescriptions, // Explanations of the rules, none by default.
+    max_tape_len: Option<usize>,    // The most cells the tape may grow to, unlimited by default.
+}
+
+impl TuringMachine {
+    /// Starts building a machine with the given tape and rules.
+    pub fn builder(tape: Vec<char>, rules: RuleTable) -> TuringMachineBuilder {
+        TuringMachineBuilder {
+            tape,
+            rules,
+            head: 0, // The adder relies on starting on the leading blank.
+            state: State::FindPlus,
+            descriptions: RuleDescriptions::new(),
+            max_tape_len: None,
+        }
+    }
+
+    /// Recreates a machine in the configuration `snapshot` recorded, e.g. one read back
+    /// from a file. Fails rather than trusting a head position that is off the tape.
+    pub fn from_snapshot(snapshot: &Snapshot, rules: RuleTable) -> Result<TuringMachine, BuildError> {
+        TuringMachine::builder(snapshot.tape.clone(), rules)
+            .with_head(snapshot.head)
+            .with_state(snapshot.state.clone())
+            .build()
+    }
+}
+
+impl TuringMachineBuilder {
+    /// Starts the head at `head` instead of the first cell.
+    pub fn with_head(mut self, head: usize) -> Self {
+        self.head = head;
+        self
+    }
+
+    /// Starts the machine in `state` instead of `FindPlus`.
+    pub fn with_state(mut self, state: State) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Attaches explanations of the rules, see `TuringMachine::set_descriptions`.
+    pub fn with_descriptions(mut self, descriptions: RuleDescriptions) -> Self {
+        self.descriptions = descriptions;
+        self
+    }
+
+    /// Limits the tape to `max_tape_len` cells, see `TuringMachine::set_max_tape_len`.
+    pub fn with_max_tape_len(mut self, max_tape_len: usize) -> Self {
+        self.max_tape_len = Some(max_tape_len);
+        self
+    }
+
+    /// Builds the machine, checking that the head starts on the tape and that the tape
+    /// fits within the limit, if one is set.
+    pub fn build(self) -> Result<TuringMachine, BuildError> {
+        if self.head >= self.tape.len() {
+            return Err(BuildError::HeadOutOfRange {
+                head: self.head,
+                len: self.tape.len(),
+            });
+        }
+        let mut machine = TuringMachine::new(self.tape, self.rules);
+        machine.head = self.head;
+        machine.set_state(self.state);
+        machine.descriptions = self.descriptions;
+        machine.set_max_tape_len(self.max_tape_len)?;
+        Ok(machine)
+    }
+}
@@ -0,0 +1,377 @@
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::error::CompileError;
+use crate::{Direction, ResultStrategy, RuleTable, RunError, Snapshot, State, TapeMode, TuringMachine};
+
+/// A rule table laid out densely, indexed by `(state index, symbol index)`, as a
+/// `CompiledMachine` steps through it. Built once, it can be shared between machines
+/// through an `Arc`, see `CompiledMachine::with_shared_rules`.
+#[derive(Debug)]
+pub struct CompiledRules {
+    start: usize,                                 // Index of the state machines start in.
+    halt: usize,                                  // Index of `State::Halt` in `states`.
+    states: Vec<State>,                           // Every state named by the rules, by index.
+    symbols: Vec<char>,                           // Every symbol read by the rules, by index.
+    ascii_index: [Option<usize>; 128],            // Symbol index of each ASCII symbol.
+    table: Vec<Option<(char, Direction, usize)>>, // The rules, at `state * symbols.len() + symbol`.
+    scans: Vec<Option<Direction>>,                // For self-loop rules in `table`, the way they move.
+}
+
+impl CompiledRules {
+    /// Lays out `rules` for machines starting in `start`.
+    pub fn new(rules: &RuleTable, start: &State) -> Self {
+        let mut states = vec![State::Halt];
+        if *start != State::Halt {
+            states.push(start.clone());
+        }
+        let mut symbols = Vec::new();
+        for ((state, read), (_, _, next_state)) in rules.iter() {
+            for state in [state, next_state] {
+                if !states.contains(state) {
+                    states.push(state.clone());
+                }
+            }
+            if !symbols.contains(read) {
+                symbols.push(*read);
+            }
+        }
+
+        let mut ascii_index = [None; 128];
+        for (index, &symbol) in symbols.iter().enumerate() {
+            if symbol.is_ascii() {
+                ascii_index[symbol as usize] = Some(index);
+            }
+        }
+
+        let state_index = |state: &State| states.iter().position(|s| s == state).unwrap();
+        let mut table = vec![None; states.len() * symbols.len()];
+        for ((state, read), (write, direction, next_state)) in rules.iter() {
+            let symbol = symbols.iter().position(|s| s == read).unwrap();
+            table[state_index(state) * symbols.len() + symbol] = Some((*write, direction.clone(), state_index(next_state)));
+        }
+
+        // A rule that rewrites what it reads and stays in its state only marches the head along.
+        let scans = table
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| match rule {
+                Some((write, direction, next_state))
+                    if *write == symbols[index % symbols.len()]
+                        && *next_state == index / symbols.len()
+                        && *direction != Direction::Stay =>
+                {
+                    Some(direction.clone())
+                }
+                _ => None,
+            })
+            .collect();
+
+        CompiledRules {
+            start: state_index(start),
+            halt: 0,
+            states,
+            symbols,
+            ascii_index,
+            table,
+            scans,
+        }
+    }
+
+    // Index of a symbol in the table, if any rule reads it.
+    fn symbol_index(&self, symbol: char) -> Option<usize> {
+        if symbol.is_ascii() {
+            self.ascii_index[symbol as usize]
+        } else {
+            self.symbols.iter().position(|&s| s == symbol)
+        }
+    }
+
+    // The rule for the state at index `state` reading `symbol`.
+    fn rule(&self, state: usize, symbol: char) -> Option<&(char, Direction, usize)> {
+        let symbol = self.symbol_index(symbol)?;
+        self.table[state * self.symbols.len() + symbol].as_ref()
+    }
+
+    // The way the self-loop rule for `state` reading `symbol` moves, if it is one.
+    fn scan(&self, state: usize, symbol: char) -> Option<&Direction> {
+        let symbol = self.symbol_index(symbol)?;
+        self.scans[state * self.symbols.len() + symbol].as_ref()
+    }
+}
+
+/// A Turing machine whose rules are laid out in a dense table indexed by
+/// `(state index, symbol index)`, so stepping never hashes.
+pub struct CompiledMachine {
+    tape: VecDeque<char>,            // The tape, as in `TuringMachine`.
+    head: usize,                     // The current position of the head on the tape.
+    state: usize,                    // Index of the current state in the rules' states.
+    rules: Arc<CompiledRules>,       // The dense table, shared with other machines if built once for all.
+    scanning: bool,                  // Whether runs take runs of self-loops in one go.
+    steps: usize,                    // Number of rules applied so far.
+    halted_in: Option<usize>,        // Index of the state in which no rule applied, once halted that way.
+    final_states: Vec<State>,        // States that halting in counts as finishing, as in `TuringMachine`.
+    result_strategy: ResultStrategy, // How `result` reads the answer.
+    tracing: bool,                   // Whether runs record a snapshot at every step.
+    trace: Vec<Snapshot>,            // The recorded snapshots, oldest first.
+}
+
+impl TuringMachine {
+    /// Builds the dense transition table and returns a machine that continues from
+    /// the current configuration. The steps taken, result strategy, final states and
+    /// tracing setting carry over; the trace recorded so far, logging and the checks only
+    /// the bounded runs make (sanity interval, cycle detection) don't.
+    ///
+    /// Fails for a procedural machine, and for settings that change how the machine
+    /// steps: a tape mode other than `TapeMode::Grow`, a tape limit, sentinels or an oracle.
+    pub fn compile(self) -> Result<CompiledMachine, CompileError> {
+        if self.tape_mode != TapeMode::Grow {
+            return Err(CompileError::TapeMode(self.tape_mode));
+        }
+        if let Some(cells) = self.max_tape_len {
+            return Err(CompileError::TapeLimit(cells));
+        }
+        if self.sentinels.is_some() {
+            return Err(CompileError::Sentinels);
+        }
+        if let Some((state, _)) = &self.oracle {
+            return Err(CompileError::Oracle(state.clone()));
+        }
+        let rules = CompiledRules::new(self.finite_rules()?, &self.state);
+        let halted_in = self.halted_in.and_then(|state| rules.states.iter().position(|s| *s == state));
+        // The dense stepper assumes the head is on the tape; grow it as `TapeMode::Grow` would.
+        let mut tape = self.tape;
+        if self.head >= tape.len() {
+            tape.resize(self.head + 1, '_');
+        }
+
+        Ok(CompiledMachine {
+            state: rules.start,
+            tape,
+            head: self.head,
+            rules: Arc::new(rules),
+            scanning: true,
+            steps: self.steps,
+            halted_in,
+            final_states: self.final_states,
+            result_strategy: self.result_strategy,
+            tracing: self.tracing,
+            trace: Vec::new(),
+        })
+    }
+}
+
+impl CompiledMachine {
+    /// Creates a machine on `tape`, head on the first cell, in the rules' start state,
+    /// running on rules other machines may share.
+    pub fn with_shared_rules(tape: Vec<char>, rules: Arc<CompiledRules>) -> Self {
+        let mut machine = CompiledMachine {
+            tape: VecDeque::new(),
+            head: 0,
+            state: rules.start,
+            rules,
+            scanning: true,
+            steps: 0,
+            halted_in: None,
+            final_states: vec![State::GetLast],
+            result_strategy: ResultStrategy::adder(),
+            tracing: false,
+            trace: Vec::new(),
+        };
+        machine.reset_with_input(tape);
+        machine
+    }
+
+    /// The dense table, to share with other machines.
+    pub fn shared_rules(&self) -> Arc<CompiledRules> {
+        Arc::clone(&self.rules)
+    }
+
+    /// Makes the machine ready to run again on `tape`: the head on the first cell, the
+    /// rules' start state, no steps taken and no trace. The rules, scanning and tracing
+    /// settings stay.
+    pub fn reset_with_input(&mut self, tape: Vec<char>) {
+        self.tape = tape.into();
+        if self.tape.is_empty() {
+            self.tape.push_back('_');
+        }
+        self.head = 0;
+        self.state = self.rules.start;
+        self.steps = 0;
+        self.halted_in = None;
+        self.trace.clear();
+    }
+
+    /// The current contents of the tape.
+    pub fn tape(&self) -> &VecDeque<char> {
+        &self.tape
+    }
+
+    /// The current position of the head on the tape.
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// The current state of the machine.
+    pub fn state(&self) -> &State {
+        &self.rules.states[self.state]
+    }
+
+    /// Number of rules applied so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Enables or disables recording a snapshot before every step `run` and `run_bounded`
+    /// take and of the halted configuration, as `TuringMachine::set_tracing` does. A scan
+    /// records one snapshot for each rule it applies, so the trace is the same with
+    /// scanning on or off. Off by default.
+    pub fn set_tracing(&mut self, tracing: bool) {
+        self.tracing = tracing;
+    }
+
+    /// The snapshots recorded while tracing, ending with the halted configuration.
+    pub fn trace(&self) -> &[Snapshot] {
+        &self.trace
+    }
+
+    /// A snapshot of the current configuration.
+    pub fn snapshot(&self) -> Snapshot {
+        snapshot(&self.tape, self.head, self.state())
+    }
+
+    /// Reads the answer from the tape, by default the adder's sum, see `TuringMachine::result`.
+    pub fn result(&self) -> String {
+        self.result_strategy.extract(&self.tape, self.halted_in())
+    }
+
+    /// Sets how `result` reads the answer, see `TuringMachine::set_result_strategy`.
+    pub fn set_result_strategy(&mut self, strategy: ResultStrategy) {
+        self.result_strategy = strategy;
+    }
+
+    /// The state the machine was in when it found no rule to apply and halted.
+    pub fn halted_in(&self) -> Option<&State> {
+        self.halted_in.map(|state| &self.rules.states[state])
+    }
+
+    /// Whether the machine halted in one of its final states, see
+    /// `TuringMachine::set_final_states`; `GetLast`, the adder's, by default.
+    pub fn finished_successfully(&self) -> bool {
+        self.halted_in().is_some_and(|state| self.final_states.contains(state))
+    }
+
+    /// Executes one step of the Turing machine.
+    pub fn step(&mut self) {
+        let current_symbol = self.tape[self.head];
+
+        if let Some(&(write, ref direction, next_state)) = self.rules.rule(self.state, current_symbol) {
+            self.tape[self.head] = write;
+
+            match direction {
+                Direction::Left => {
+                    if self.head > 0 {
+                        self.head -= 1;
+                    } else {
+                        self.tape.push_front('_');
+                    }
+                }
+                Direction::Right => {
+                    self.head += 1;
+                    if self.head >= self.tape.len() {
+                        self.tape.push_back('_');
+                    }
+                }
+                Direction::Stay => {}
+            }
+
+            self.state = next_state;
+            self.steps += 1;
+        } else {
+            // If no rule applies, halt the machine.
+            if self.state != self.rules.halt {
+                self.halted_in = Some(self.state);
+            }
+            self.state = self.rules.halt;
+        }
+    }
+
+    /// Enables or disables scanning: applying a run of self-loop rules (which rewrite the
+    /// symbol they read, keep the state and move one way) as a single head movement, still
+    /// counting every rule in `steps`. On by default; the result is the same either way.
+    pub fn set_scanning(&mut self, scanning: bool) {
+        self.scanning = scanning;
+    }
+
+    // Applies up to `budget` consecutive self-loop rules in one go, stopping before the
+    // head would leave the tape so `step` does the growing. Returns how many it applied.
+    // The rules leave the tape as it was, so tracing only needs the head of each one.
+    fn scan(&mut self, budget: usize) -> usize {
+        let rules = &self.rules;
+        let Some(direction) = rules.scan(self.state, self.tape[self.head]) else {
+            return 0;
+        };
+        let mut applied = 0;
+        while applied < budget && rules.scan(self.state, self.tape[self.head]) == Some(direction) {
+            let next = match direction {
+                Direction::Left if self.head > 0 => self.head - 1,
+                Direction::Right if self.head + 1 < self.tape.len() => self.head + 1,
+                _ => break,
+            };
+            if self.tracing {
+                self.trace.push(snapshot(&self.tape, self.head, &rules.states[self.state]));
+            }
+            self.head = next;
+            applied += 1;
+        }
+        self.steps += applied;
+        applied
+    }
+
+    /// Runs the machine until it reaches the Halt state.
+    pub fn run(&mut self) {
+        while self.state != self.rules.halt {
+            if !self.scanning || self.scan(usize::MAX) == 0 {
+                self.trace_step();
+                self.step();
+            }
+        }
+        self.trace_step();
+    }
+
+    /// Runs the machine until it halts or `max_steps` rules have been applied.
+    /// Returns the number of steps taken.
+    pub fn run_bounded(&mut self, max_steps: usize) -> Result<usize, RunError> {
+        while self.state != self.rules.halt {
+            if self.steps >= max_steps {
+                return Err(RunError::StepLimitExceeded(max_steps));
+            }
+            if !self.scanning || self.scan(max_steps - self.steps) == 0 {
+                self.trace_step();
+                self.step();
+            }
+        }
+        self.trace_step();
+        Ok(self.steps)
+    }
+
+    // Records the current configuration, if tracing.
+    fn trace_step(&mut self) {
+        if self.tracing {
+            self.trace.push(self.snapshot());
+        }
+    }
+}
+
+// A snapshot of a compiled machine's configuration, without a partial value.
+fn snapshot(tape: &VecDeque<char>, head: usize, state: &State) -> Snapshot {
+    Snapshot {
+        tape: tape.iter().copied().collect(),
+        head,
+        state: state.clone(),
+        partial_value: None,
+    }
+}
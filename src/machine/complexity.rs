@@ -0,0 +1,69 @@
+use std::fmt::Write;
+
+use crate::tape::input::{build_tape, LeadingZeros};
+use crate::{rules, TuringMachine};
+
+/// Steps the adder took for one operand size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    pub bits: usize,  // Width of both operands.
+    pub steps: usize, // Rules applied until the machine halted.
+}
+
+/// Runs the adder silently on all-ones operands (the worst case for carries) of
+/// `samples` sizes spread evenly up to `max_bits`. With the current rules 8-bit operands take 250 steps.
+pub fn measure_adder(max_bits: usize, samples: usize) -> Vec<Sample> {
+    let samples = samples.clamp(1, max_bits.max(1));
+    let mut sizes: Vec<usize> = (1..=samples).map(|i| (max_bits * i / samples).max(1)).collect();
+    sizes.dedup();
+
+    sizes
+        .into_iter()
+        .map(|bits| {
+            let ones = "1".repeat(bits);
+            let tape = build_tape(&ones, &ones, LeadingZeros::Strip).expect("all-ones operands are binary");
+            let mut machine = TuringMachine::new(tape, rules::adder());
+            machine.run();
+            Sample { bits, steps: machine.steps() }
+        })
+        .collect()
+}
+
+/// An upper bound on the steps the adder takes from `FindPlus` on the first cell of
+/// `tape`, without running it: each digit of the first operand costs at most a walk to
+/// the `+` and one to the sum and back, each at most the tape's length, and a last walk
+/// to the `+` finds no digit left. The tape never grows, as the sum is written over the
+/// operands. `None` if the tape has no `+`, so the adder would never halt.
+pub fn adder_step_bound(tape: &[char]) -> Option<usize> {
+    let plus = tape.iter().position(|&symbol| symbol == '+')?;
+    let digits = tape[..plus].iter().filter(|&&symbol| symbol == '0' || symbol == '1').count();
+    Some((3 * digits + 1) * tape.len())
+}
+
+/// Fits `steps ≈ c · bits^k` by least squares on the log-log data and returns `k`.
+/// Needs at least two distinct sizes.
+pub fn fitted_exponent(samples: &[Sample]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|s| s.steps > 0)
+        .map(|s| ((s.bits as f64).ln(), (s.steps as f64).ln()))
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    if points.len() < 2 || variance == 0.0 {
+        return None;
+    }
+    Some(covariance / variance)
+}
+
+/// Renders the samples as CSV with a `bits,steps` header.
+pub fn to_csv(samples: &[Sample]) -> String {
+    let mut csv = String::from("bits,steps\n");
+    for sample in samples {
+        writeln!(csv, "{},{}", sample.bits, sample.steps).unwrap();
+    }
+    csv
+}
@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::rng::SplitMix64;
+use crate::{HaltReason, RunError, State, Tape, TuringMachine};
+
+/// A symbol flipped on the tape by a `FaultInjector`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fault {
+    pub step: usize,  // The step whose write was corrupted.
+    pub cell: usize,  // Position of the cell on the tape, as it was after that step.
+    pub before: char, // What the step wrote.
+    pub after: char,  // What the cell holds instead.
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "step {}: cell {} flipped from '{}' to '{}'", self.step, self.cell, self.before, self.after)
+    }
+}
+
+/// An unreliable tape: after every step, flips the cell just written with a given
+/// probability, `0` <-> `1` and `O` <-> `I`. Other symbols are never flipped.
+/// Seeded, so equal seeds inject the same faults into the same run.
+#[derive(Debug, Clone)]
+pub struct FaultInjector {
+    threshold: u64,  // A draw below this flips the cell; the rate scaled to `u64::MAX`.
+    rng: SplitMix64, // Decides which writes are corrupted.
+    log: Vec<Fault>, // Every fault injected so far, oldest first.
+}
+
+impl FaultInjector {
+    /// Creates an injector flipping each write with probability `rate`, clamped to `0.0..=1.0`.
+    pub fn new(rate: f64, seed: u64) -> Self {
+        FaultInjector {
+            threshold: (rate.clamp(0.0, 1.0) * u64::MAX as f64) as u64,
+            rng: SplitMix64::new(seed),
+            log: Vec::new(),
+        }
+    }
+
+    /// The faults injected so far, oldest first.
+    pub fn log(&self) -> &[Fault] {
+        &self.log
+    }
+
+    // Maybe flips `cell`, which `step` just wrote.
+    fn inject(&mut self, tape: &mut Tape, cell: usize, step: usize) {
+        if self.rng.next_u64() >= self.threshold {
+            return;
+        }
+        let Some(&before) = tape.get(cell) else {
+            return; // An oracle may have shortened the tape since.
+        };
+        let after = match before {
+            '0' => '1',
+            '1' => '0',
+            'O' => 'I',
+            'I' => 'O',
+            _ => return,
+        };
+        tape[cell] = after;
+        self.log.push(Fault { step, cell, before, after });
+    }
+}
+
+impl TuringMachine {
+    /// Runs the Turing machine as `run_observed` does until it halts or `max_steps` rules
+    /// have been applied, letting `injector` corrupt the cell written by every step.
+    /// Returns the number of steps taken. A corrupted tape may well never halt, hence the bound.
+    pub fn run_with_faults(&mut self, injector: &mut FaultInjector, max_steps: usize) -> Result<usize, RunError> {
+        let mut before = None; // The logical head and steps before the latest step.
+        self.run_hooked(|machine| {
+            // A step that applied a rule wrote where the head was, even if it then halted;
+            // the logical cell stays put however the tape grew on the left.
+            if let Some((logical_head, _)) = before.filter(|&(_, steps)| machine.steps > steps) {
+                let cell = (logical_head + machine.grown_left as isize) as usize;
+                injector.inject(&mut machine.tape, cell, machine.steps);
+            }
+            before = Some((machine.logical_head(), machine.steps));
+            if machine.halt_reason == Some(HaltReason::HeadOutOfBounds) {
+                return Err(RunError::HeadOutOfBounds {
+                    head: machine.head,
+                    len: machine.tape.len(),
+                });
+            }
+            if machine.state != State::Halt && machine.steps >= max_steps {
+                return Err(RunError::StepLimitExceeded(max_steps));
+            }
+            Ok(())
+        })?;
+        Ok(self.steps)
+    }
+}
@@ -0,0 +1,83 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{Configuration, Snapshot, TuringMachine};
+
+pub use crate::error::InvariantViolation;
+
+/// A property of the configuration that must hold at every step of a run.
+pub trait Invariant {
+    /// Checks the configuration, explaining what is wrong when it doesn't hold.
+    fn check(&self, config: &Configuration) -> Result<(), String>;
+}
+
+/// There is exactly one separator on the tape.
+///
+/// The adder erases each digit of the first operand it has consumed by writing `+` over it,
+/// so the separator is the run of adjacent `+` cells rather than a single cell.
+pub struct ExactlyOnePlus;
+
+impl Invariant for ExactlyOnePlus {
+    fn check(&self, config: &Configuration) -> Result<(), String> {
+        let mut runs = 0;
+        let mut previous = None;
+        for &symbol in config.tape {
+            if symbol == '+' && previous != Some('+') {
+                runs += 1;
+            }
+            previous = Some(symbol);
+        }
+        match runs {
+            1 => Ok(()),
+            0 => Err("the tape has no '+'".to_string()),
+            runs => Err(format!("the tape has {} separate runs of '+'", runs)),
+        }
+    }
+}
+
+/// The adder's `I`/`O` markers form one contiguous run at the right end of the tape's
+/// symbols: nothing but blanks follows the first marker, apart from more markers.
+///
+/// The adder writes the sum's digits right to left, each as a marker just left of the
+/// ones already written, and a carry only ever turns unmarked digits or `+` cells
+/// further left. So scanning right for the first marker or blank always finds the
+/// next digit to add to just left of it.
+pub struct MarkersAreSuffix;
+
+impl Invariant for MarkersAreSuffix {
+    fn check(&self, config: &Configuration) -> Result<(), String> {
+        let first = config.tape.iter().position(|&symbol| matches!(symbol, 'I' | 'O'));
+        let Some(first) = first else {
+            return Ok(());
+        };
+        let end = config.tape.iter().rposition(|&symbol| symbol != '_').unwrap_or(first);
+        let stray = config.tape.iter().enumerate().take(end + 1).skip(first).find(|(_, symbol)| !matches!(symbol, 'I' | 'O'));
+        match stray {
+            Some((cell, symbol)) => Err(format!("cell {} holds '{}' after the marker in cell {}", cell, symbol, first)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl TuringMachine {
+    /// Runs the Turing machine until it halts, checking every invariant on the starting
+    /// configuration and after every step. Stops at the first violation.
+    pub fn run_with_invariants(&mut self, invariants: &[Box<dyn Invariant>]) -> Result<(), InvariantViolation> {
+        self.run_observed(|config| {
+            for invariant in invariants {
+                invariant.check(config).map_err(|message| InvariantViolation {
+                    step: config.steps,
+                    message,
+                    snapshot: Snapshot {
+                        tape: config.tape.iter().copied().collect(),
+                        head: config.head,
+                        state: config.state.clone(),
+                        partial_value: None,
+                    },
+                })?;
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,111 @@
+use alloc::string::String;
+
+use crate::{State, TuringMachine};
+
+/// Runs two machines together, e.g. two adders on the same input, so their progress
+/// can be compared. Every tick steps each machine that hasn't halted by its pace, one
+/// step by default, so both go in lockstep; a machine that halts first waits for the other.
+pub struct Lockstep {
+    machines: [TuringMachine; 2], // The left and the right machine.
+    pace: [usize; 2],             // Steps each takes per tick.
+    ticks: usize,                 // Ticks so far.
+    diverged_at: Option<usize>,   // The first tick after which the decoded answers differed, if any.
+}
+
+/// Where both machines are after a tick, see `Lockstep::tick`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tick {
+    pub tick: usize,      // Ticks so far, 0 before the first.
+    pub sides: [Side; 2], // The left and the right machine.
+    pub diverged: bool,   // Whether their decoded answers differ.
+}
+
+/// One machine's part of a `Tick`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Side {
+    pub steps: usize,   // Steps it has taken.
+    pub stepped: usize, // Steps it took in this tick.
+    pub state: State,   // The state it is in.
+    pub result: String, // Its answer as decoded now, see `TuringMachine::result`.
+    pub halted: bool,   // Whether it has halted.
+}
+
+impl Lockstep {
+    /// Pairs two machines, each ready to run, stepping one step each per tick.
+    pub fn new(left: TuringMachine, right: TuringMachine) -> Self {
+        Lockstep {
+            machines: [left, right],
+            pace: [1, 1],
+            ticks: 0,
+            diverged_at: None,
+        }
+    }
+
+    /// Lets each machine go at its own pace: `left` and `right` steps per tick. A pace
+    /// of zero counts as one.
+    pub fn with_pace(mut self, left: usize, right: usize) -> Self {
+        self.pace = [left.max(1), right.max(1)];
+        self
+    }
+
+    /// The left and the right machine.
+    pub fn machines(&self) -> &[TuringMachine; 2] {
+        &self.machines
+    }
+
+    /// Whether both machines have halted.
+    pub fn halted(&self) -> bool {
+        self.machines.iter().all(|machine| machine.state() == &State::Halt)
+    }
+
+    /// The first tick after which the decoded answers differed, if they have.
+    pub fn first_divergence(&self) -> Option<usize> {
+        self.diverged_at
+    }
+
+    /// Where both machines are now, without stepping them.
+    pub fn current(&self) -> Tick {
+        self.view([0, 0])
+    }
+
+    /// Steps each machine that hasn't halted by its pace and reports where both are, or
+    /// returns `None` once both have halted.
+    pub fn tick(&mut self) -> Option<Tick> {
+        if self.halted() {
+            return None;
+        }
+        let mut stepped = [0, 0];
+        for (side, machine) in self.machines.iter_mut().enumerate() {
+            while stepped[side] < self.pace[side] && machine.state() != &State::Halt {
+                machine.step();
+                stepped[side] += 1;
+            }
+        }
+        self.ticks += 1;
+        let tick = self.view(stepped);
+        if tick.diverged && self.diverged_at.is_none() {
+            self.diverged_at = Some(self.ticks);
+        }
+        Some(tick)
+    }
+
+    // Describes both machines, which took `stepped` steps in the last tick.
+    fn view(&self, stepped: [usize; 2]) -> Tick {
+        let side = |index: usize| {
+            let machine = &self.machines[index];
+            Side {
+                steps: machine.steps(),
+                stepped: stepped[index],
+                state: machine.state().clone(),
+                result: machine.result(),
+                halted: machine.state() == &State::Halt,
+            }
+        };
+        let sides = [side(0), side(1)];
+        Tick {
+            tick: self.ticks,
+            diverged: sides[0].result != sides[1].result,
+            sides,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// Running machines: building them, compiling them to a dense table, running them in
+// lockstep, streamed, through a pipeline, under invariants or faults, and checking what
+// a run can reach. The machine itself, `TuringMachine`, is at the crate root.
+
+pub mod builder;
+pub mod compiled;
+#[cfg(feature = "std")]
+pub mod complexity;
+pub mod fault;
+pub mod invariant;
+pub mod lockstep;
+pub mod pipeline;
+pub mod progress;
+pub mod safety;
+pub mod simulate;
+#[cfg(feature = "std")]
+pub mod stream;
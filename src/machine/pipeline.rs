@@ -0,0 +1,80 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::{RuleTable, Snapshot, TuringMachine};
+
+pub use crate::error::PipelineError;
+
+/// Rewrites a tape into the input format the next machine expects.
+pub type TapeAdapter = Box<dyn Fn(&[char]) -> Vec<char>>;
+
+/// What one stage of a pipeline did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageStats {
+    pub steps: usize,         // Rules applied by this stage's machine.
+    pub tape_len: usize,      // Length of the tape when the machine halted.
+    pub trace: Vec<Snapshot>, // The stage's trace, empty unless the pipeline is tracing.
+}
+
+/// The outcome of running a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineOutput {
+    pub tape: Vec<char>,        // The last machine's final tape.
+    pub stages: Vec<StageStats>, // Per-stage statistics, in stage order.
+}
+
+/// Runs machines one after another, each on the previous one's final tape.
+///
+/// Every stage pairs a rule table with the adapter that turns the incoming tape into
+/// that machine's input. The first stage's adapter receives the pipeline's input.
+pub struct Pipeline {
+    stages: Vec<(RuleTable, TapeAdapter)>, // The machines and their input adapters, in order.
+    max_steps: usize,                      // Step limit for each stage.
+    tracing: bool,                         // Whether stages record their traces.
+}
+
+impl Pipeline {
+    /// Creates an empty pipeline where each stage may apply at most `max_steps` rules.
+    pub fn new(max_steps: usize) -> Self {
+        Pipeline {
+            stages: Vec::new(),
+            max_steps,
+            tracing: false,
+        }
+    }
+
+    /// Appends a stage.
+    pub fn stage(mut self, rules: RuleTable, adapter: impl Fn(&[char]) -> Vec<char> + 'static) -> Self {
+        self.stages.push((rules, Box::new(adapter)));
+        self
+    }
+
+    /// Enables or disables recording each stage's trace.
+    pub fn tracing(mut self, tracing: bool) -> Self {
+        self.tracing = tracing;
+        self
+    }
+
+    /// Runs every stage in order on `input`.
+    pub fn run(&self, input: &[char]) -> Result<PipelineOutput, PipelineError> {
+        let mut tape = input.to_vec();
+        let mut stages = Vec::with_capacity(self.stages.len());
+
+        for (stage, (rules, adapter)) in self.stages.iter().enumerate() {
+            let mut machine = TuringMachine::new(adapter(&tape), rules.clone());
+            machine.set_tracing(self.tracing);
+            machine
+                .run_bounded(self.max_steps)
+                .map_err(|error| PipelineError { stage, error })?;
+
+            stages.push(StageStats {
+                steps: machine.steps(),
+                tape_len: machine.tape().len(),
+                trace: machine.trace().to_vec(),
+            });
+            tape = machine.into_tape();
+        }
+
+        Ok(PipelineOutput { tape, stages })
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{State, Tape};
+
+/// Roughly how many more steps the adder takes to halt from a configuration: `None` if
+/// the tape has no `+` with a blank somewhere before it, `state` isn't one of the
+/// adder's, or the head is where the adder never is in that state, e.g. left of the blank
+/// before its operands, so the configuration isn't the adder's.
+///
+/// Every digit of the first operand costs one round trip: from the digit right to the
+/// first marker (or the blank after the second operand), where the sum's next digit goes,
+/// then back to the blank before the tape, then right again to the `+` run. The markers
+/// move left by a cell per digit, so the trips shrink as the run goes on. A carry walks
+/// left as the return trip does, so it costs nothing extra. With the rest of the current
+/// trip worked out from the state and the head, the estimate comes out exact for the
+/// adder's own runs; tests/progress.rs checks it at every step of thousands.
+pub fn adder_remaining_steps(tape: &Tape, head: usize, state: &State) -> Option<usize> {
+    // The first `+`, the blank before the digits left of it, and the first marker or blank
+    // after the operands: where the next digit of the sum goes, one cell to its left.
+    let plus = tape.iter().position(|&c| c == '+')?;
+    let start = tape.range(..plus).rposition(|&c| c != '0' && c != '1')?;
+    let boundary = plus + tape.range(plus..).take_while(|&&c| matches!(c, '+' | '0' | '1')).count();
+    let digits = plus - start - 1;
+    // The round trips for `digits` more digits, the first from `GetLast` with the next
+    // digit of the sum going just left of `boundary`. `None`, as the other estimates that
+    // would come out negative, for a head the adder can't have reached in this state.
+    let trips = |boundary: usize| {
+        (digits * (2 * boundary.checked_sub(start)? + 1)).checked_sub(digits * digits.saturating_sub(1))
+    };
+    // The rest of a trip from `BackToStart` at `at`: left to the blank, then right to the `+` run.
+    let back = |at: usize| Some(at.checked_sub(start)? + 1 + (plus - start));
+    let remaining = match state {
+        State::Halt => 0,
+        State::GetLast if !matches!(tape.get(head), Some('0' | '1')) => 0,
+        State::GetLast => trips(boundary)?,
+        State::FindPlus => plus.checked_sub(head)? + 1 + trips(boundary)?,
+        State::AddZero | State::AddOne => {
+            boundary.checked_sub(head)? + 1 + 1 + back(boundary - 2)? + trips(boundary - 1)?
+        }
+        State::AddDigitZero | State::AddDigitOne => 1 + back(head.checked_sub(1)?)? + trips(head)?,
+        State::Carry | State::BackToStart => back(head)? + trips(boundary)?,
+        State::Named(_) => return None,
+    };
+    Some(remaining)
+}
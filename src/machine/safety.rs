@@ -0,0 +1,181 @@
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(not(feature = "std"))]
+use hashbrown::HashSet;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+use crate::{Direction, RuleTable, Snapshot, State};
+
+pub use crate::error::PropertyParseError;
+
+/// A safety property: something a machine must never do, see `check_safety`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Property {
+    NoWrite(char),      // The symbol is never written into a cell that didn't hold it.
+    NoWriteLeftOfStart, // Nothing but a blank is written left of the initial tape's first cell.
+}
+
+impl fmt::Display for Property {
+    // The spelling `FromStr` reads.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Property::NoWrite(symbol) => write!(f, "no-write:{}", symbol),
+            Property::NoWriteLeftOfStart => write!(f, "no-write-left"),
+        }
+    }
+}
+
+impl FromStr for Property {
+    type Err = PropertyParseError;
+
+    /// Reads `no-write:X` or `no-write-left`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text == "no-write-left" {
+            return Ok(Property::NoWriteLeftOfStart);
+        }
+        let mut symbol = text.strip_prefix("no-write:").unwrap_or_default().chars();
+        match (symbol.next(), symbol.next()) {
+            (Some(symbol), None) => Ok(Property::NoWrite(symbol)),
+            _ => Err(PropertyParseError(String::from(text))),
+        }
+    }
+}
+
+impl Property {
+    // Whether writing `write` over `read` at `cell`, counted from the initial tape's first cell, breaks it.
+    fn violated_by(&self, cell: isize, read: char, write: char) -> bool {
+        match self {
+            Property::NoWrite(symbol) => write == *symbol && read != *symbol,
+            Property::NoWriteLeftOfStart => cell < 0 && write != '_',
+        }
+    }
+}
+
+/// What `check_safety` found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SafetyOutcome {
+    /// No configuration within the bound breaks the property. `exhausted` says every run
+    /// halted or came back to an earlier configuration first, so it holds at any depth.
+    Holds { depth: usize, configurations: usize, exhausted: bool },
+    /// A run breaks the property.
+    Violated(Counterexample),
+}
+
+/// A run that breaks a property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample {
+    pub step: usize,          // The step that breaks it, from 1.
+    pub trace: Vec<Snapshot>, // The configurations from the initial tape up to that step, which the last one takes.
+}
+
+// A configuration, with the tape trimmed of blanks so equal configurations compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Configuration {
+    state: State,     // The current state.
+    head: isize,      // The head's cell, counted from the initial tape's first cell.
+    origin: isize,    // The cell `cells[0]` is, 0 when `cells` is empty.
+    cells: Vec<char>, // The tape from its first to its last non-blank cell.
+}
+
+impl Configuration {
+    fn new(state: State, head: isize, origin: isize, cells: Vec<char>) -> Self {
+        let start = cells.iter().position(|&c| c != '_').unwrap_or(cells.len());
+        let end = cells.iter().rposition(|&c| c != '_').map_or(start, |last| last + 1);
+        let origin = if start == end { 0 } else { origin + start as isize };
+        Configuration {
+            state,
+            head,
+            origin,
+            cells: cells[start..end].to_vec(),
+        }
+    }
+
+    fn symbol(&self, cell: isize) -> char {
+        usize::try_from(cell - self.origin).ok().and_then(|index| self.cells.get(index)).copied().unwrap_or('_')
+    }
+
+    // The configuration as a snapshot, on a tape from the initial tape's first cell (or
+    // further left, if the head or the tape is) to past its last cell and the head.
+    fn snapshot(&self) -> Snapshot {
+        let start = self.origin.min(self.head).min(0);
+        let end = (self.origin + self.cells.len() as isize).max(self.head + 1);
+        Snapshot {
+            tape: (start..end).map(|cell| self.symbol(cell)).collect(),
+            head: (self.head - start) as usize,
+            state: self.state.clone(),
+            partial_value: None,
+        }
+    }
+}
+
+/// Explores every configuration `rules` reach from `tapes`, starting in `start` on each
+/// tape's first cell, up to `depth` steps, breadth first, and checks that no step breaks
+/// `property`. Configurations seen before aren't explored again, which also ends runs
+/// that loop. Returns the shortest counterexample if there is one.
+pub fn check_safety(
+    rules: &RuleTable,
+    start: &State,
+    tapes: &[Vec<char>],
+    property: &Property,
+    depth: usize,
+) -> SafetyOutcome {
+    // Every configuration found, with the one it was reached from.
+    let mut found: Vec<(Configuration, Option<usize>)> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    for tape in tapes {
+        let initial = Configuration::new(start.clone(), 0, 0, tape.clone());
+        if seen.insert(initial.clone()) {
+            queue.push_back((found.len(), 0));
+            found.push((initial, None));
+        }
+    }
+
+    let mut exhausted = true;
+    while let Some((index, steps)) = queue.pop_front() {
+        let configuration = &found[index].0;
+        let read = configuration.symbol(configuration.head);
+        let Some((write, direction, next_state)) = rules.get(&(configuration.state.clone(), read)) else {
+            continue;
+        };
+        if steps == depth {
+            exhausted = false;
+            continue;
+        }
+        if property.violated_by(configuration.head, read, *write) {
+            let mut trace = Vec::new();
+            let mut at = Some(index);
+            while let Some(index) = at {
+                trace.push(found[index].0.snapshot());
+                at = found[index].1;
+            }
+            trace.reverse();
+            return SafetyOutcome::Violated(Counterexample { step: steps + 1, trace });
+        }
+
+        // Lay the tape out from the head's cell if it is off the trimmed cells, then write.
+        let start = configuration.origin.min(configuration.head);
+        let end = (configuration.origin + configuration.cells.len() as isize).max(configuration.head + 1);
+        let mut cells: Vec<char> = (start..end).map(|cell| configuration.symbol(cell)).collect();
+        cells[(configuration.head - start) as usize] = *write;
+        let head = match direction {
+            Direction::Left => configuration.head - 1,
+            Direction::Right => configuration.head + 1,
+            Direction::Stay => configuration.head,
+        };
+        let next = Configuration::new(next_state.clone(), head, start, cells);
+        if seen.insert(next.clone()) {
+            queue.push_back((found.len(), steps + 1));
+            found.push((next, Some(index)));
+        }
+    }
+    SafetyOutcome::Holds {
+        depth,
+        configurations: found.len(),
+        exhausted,
+    }
+}
@@ -0,0 +1,41 @@
+use crate::tape::input::{parse_input, LeadingZeros, ParseError};
+use crate::{rules, Error, HaltReason, TuringMachine};
+
+/// Everything a run of the adder measured, see `simulate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simulation {
+    pub operands: (u64, u64), // The numbers added.
+    pub result: u64,          // Their sum, as the machine computed it.
+    pub steps: usize,         // Rules applied until the machine halted.
+    pub max_tape: usize,      // The most cells the tape held at once.
+    pub halted: HaltReason,   // Why the machine halted.
+}
+
+/// Adds the operands of an `a+b` input on the adder and sums the run up. Leading zeros
+/// are stripped. Fails if the input isn't two binary operands around a `+`, or if an
+/// operand or the sum doesn't fit in a `u64`.
+pub fn simulate(input: &str) -> Result<Simulation, Error> {
+    let tape = parse_input(input, LeadingZeros::Strip)?;
+    let (a, b) = input.split_once('+').ok_or(ParseError::MissingOperator)?;
+    let operands = (value(a)?, value(b)?);
+
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    machine.run();
+    Ok(Simulation {
+        operands,
+        result: value(&machine.result())?,
+        steps: machine.steps(),
+        // The tape only ever grows.
+        max_tape: machine.tape().len(),
+        halted: machine.halt_reason().cloned().expect("a machine that stopped running has halted"),
+    })
+}
+
+// The value of binary digits, 0 for none.
+fn value(digits: &str) -> Result<u64, ParseError> {
+    let digits = digits.trim_start_matches('0');
+    if digits.len() > 64 {
+        return Err(ParseError::TooLarge(digits.len()));
+    }
+    Ok(u64::from_str_radix(digits, 2).unwrap_or(0))
+}
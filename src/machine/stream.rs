@@ -0,0 +1,175 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::mpsc::{SyncSender, TrySendError};
+
+use crate::{Configuration, Direction, RunError, Snapshot, State, TuringMachine};
+
+// A rule that fired: the state and symbol it read, what it wrote, the move and the next state.
+type Fired = (State, char, char, Direction, State);
+
+/// One step of a run, as `run_streaming` sends it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepEvent {
+    pub step: usize,          // The step's number, from 1.
+    pub state: State,         // The state the machine was in.
+    pub read: char,           // The symbol read.
+    pub write: char,          // The symbol written over it.
+    pub direction: Direction, // Where the head moved.
+    pub next: State,          // The state the machine went to.
+    pub snapshot: Snapshot,   // The configuration after the step.
+}
+
+/// What `run_streaming` does when the receiver falls behind and the channel is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backpressure {
+    #[default]
+    Block,             // Wait for room, so the receiver sees every step and the machine runs at its pace.
+    DropOldest(usize), // Never wait: hold up to N steps while the channel is full, dropping the oldest to make room.
+    Sample(usize),     // Send every Nth step only, and the last one, waiting for room.
+}
+
+impl TuringMachine {
+    /// Runs the Turing machine until it halts, sending every step to `events` as
+    /// `backpressure` allows, e.g. to a thread that draws the run. The last step is always
+    /// sent, and the channel closes when the run ends. Dropping the receiver cancels the
+    /// run: it stops with `RunError::Cancelled` at the next step it would send. Returns the
+    /// number of steps taken.
+    ///
+    /// The channel must be bounded, see `std::sync::mpsc::sync_channel`, for a slow
+    /// receiver to hold the run back or make it drop steps. With `DropOldest`, steps the
+    /// channel has room for are never dropped; the steps held back are sent, oldest first,
+    /// as soon as it has room again, and at the end of the run, waiting for room then.
+    pub fn run_streaming(
+        &mut self,
+        events: SyncSender<StepEvent>,
+        backpressure: Backpressure,
+    ) -> Result<usize, RunError> {
+        // The steps not sent yet: events waiting for room, oldest first, or a skipped rule.
+        let mut pending: VecDeque<StepEvent> = VecDeque::new();
+        let mut skipped = None;
+        while self.state != State::Halt {
+            let state = self.state.clone();
+            let read = self.symbol();
+            let rule = self.rule(&state, read).map(Cow::into_owned);
+            self.trace_step();
+            self.step();
+            let Some((write, direction, next)) = rule else {
+                break;
+            };
+            let rule = (state, read, write, direction, next);
+            match backpressure {
+                Backpressure::Block => self.send(&events, rule)?,
+                Backpressure::Sample(every) if !self.steps.is_multiple_of(every.max(1)) => skipped = Some(rule),
+                Backpressure::Sample(_) => {
+                    skipped = None;
+                    self.send(&events, rule)?;
+                }
+                Backpressure::DropOldest(capacity) => {
+                    if pending.len() == capacity.max(1) {
+                        pending.pop_front();
+                    }
+                    pending.push_back(self.event(rule));
+                    while let Some(event) = pending.pop_front() {
+                        match events.try_send(event) {
+                            Ok(()) => {}
+                            Err(TrySendError::Full(event)) => {
+                                pending.push_front(event);
+                                break;
+                            }
+                            Err(TrySendError::Disconnected(_)) => return Err(RunError::Cancelled(self.steps)),
+                        }
+                    }
+                }
+            }
+        }
+        self.trace_final();
+        if let Some(rule) = skipped {
+            self.send(&events, rule)?;
+        }
+        for event in pending {
+            events.send(event).map_err(|_| RunError::Cancelled(self.steps))?;
+        }
+        Ok(self.steps)
+    }
+
+    // The event for the step that just applied `rule`.
+    fn event(&self, (state, read, write, direction, next): Fired) -> StepEvent {
+        StepEvent {
+            step: self.steps,
+            state,
+            read,
+            write,
+            direction,
+            next,
+            snapshot: self.snapshot(),
+        }
+    }
+
+    // Sends the event for `rule`, waiting for room.
+    fn send(&self, events: &SyncSender<StepEvent>, rule: Fired) -> Result<(), RunError> {
+        events.send(self.event(rule)).map_err(|_| RunError::Cancelled(self.steps))
+    }
+}
+
+/// Writes the head's trajectory as CSV, `step,logical_head,state` rows under a header,
+/// from the configurations a run hands its observer, see `TuringMachine::run_observed`:
+///
+/// ```text
+/// let mut trace = HeadTrace::new(BufWriter::new(File::create("head.csv")?), 10)?;
+/// machine.run_observed(|configuration| trace.observe(configuration))?;
+/// trace.finish()?;
+/// ```
+///
+/// Rows are written as the run goes, so a long run doesn't pile up in memory. The head is
+/// given in logical cells, which stay put when the tape grows left.
+pub struct HeadTrace<W: Write> {
+    out: W,                                 // Where the rows go.
+    every: usize,                           // Write every Nth step's row only, and the last one.
+    observed: Option<usize>,                // The latest step observed, if any.
+    skipped: Option<(usize, isize, State)>, // The latest row not written, if any.
+}
+
+impl<W: Write> HeadTrace<W> {
+    /// Writes the header, keeping every `every`th step's row (every row for 0 or 1).
+    pub fn new(mut out: W, every: usize) -> io::Result<Self> {
+        writeln!(out, "step,logical_head,state")?;
+        Ok(HeadTrace {
+            out,
+            every: every.max(1),
+            observed: None,
+            skipped: None,
+        })
+    }
+
+    /// Writes the configuration's row, unless downsampling skips it. A run that finds no
+    /// rule hands over the same step again, in `Halt`, which is left out, so each step has
+    /// one row and the last names the state the machine halted in.
+    pub fn observe(&mut self, configuration: &Configuration) -> io::Result<()> {
+        if self.observed.replace(configuration.steps) == Some(configuration.steps) {
+            return Ok(());
+        }
+        let row = (configuration.steps, configuration.logical_head, configuration.state.clone());
+        if configuration.steps.is_multiple_of(self.every) {
+            self.skipped = None;
+            self.write(row)
+        } else {
+            self.skipped = Some(row);
+            Ok(())
+        }
+    }
+
+    /// Writes the last configuration's row if downsampling skipped it, so the trace always
+    /// ends with the final step, then flushes and hands back the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(row) = self.skipped.take() {
+            self.write(row)?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn write(&mut self, (step, head, state): (usize, isize, State)) -> io::Result<()> {
+        writeln!(self.out, "{},{},{}", step, head, state)
+    }
+}
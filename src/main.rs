@@ -1,164 +1,1532 @@
 use std::collections::HashMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, IsTerminal};
+use std::process;
+use std::time::{Duration, Instant};
 
-/// Represents the possible states of the Turing machine can be in.
-#[derive(Hash, Eq, PartialEq, Debug, Clone)]
-enum State {
-    FindPlus,//moves to the + symbol
-    GetLast,//gets the last digit to the left of the +
-    AddOne,//remembers that a 1 has to be added to the end of the second number
-    AddZero,//remembers that a 0 has to be added to the end of the second number
-    AddDigitZero,//adds zero to the last non-altered digit of the second number(turns 0 to O and 1 to I)
-    AddDigitOne,//adds one to the last non-altered digit of the second number
-    Carry,//carries an additional one to the digits further left of the last addDigitOne if a 1 was encountered
-    BackToStart,//moves back to start of the tape
-    Halt,//We did it! :D
-}
-
-/// Represents the direction the head can move on the tape.
-#[derive(Debug, Clone)]
-enum Direction {
-    Left,  // Move one step to the left.
-    Right, // Move one step to the right.
-}
-
-/// Represents the Turing machine.
-struct TuringMachine {
-    tape: Vec<char>, // The tape holds symbols (e.g., '0', '1', '+', '_').
-    head: usize,     // The current position of the head on the tape.
-    state: State,    // The current state of the machine.
-    rules: HashMap<(State, char), (char, Direction, State)>, // Transition rules.
-}
-
-impl TuringMachine {
-    /// Creates a new Turing machine with the given tape and rules.
-    fn new(tape: Vec<char>, rules: HashMap<(State, char), (char, Direction, State)>) -> Self {
-        TuringMachine {
-            tape,
-            head: 0, // Start at the first position on the tape.
-            state: State::FindPlus, // Initial state to locate the '+'.
-            rules,
-        }
-    }
-
-    /// Executes one step of the Turing machine.
-    fn step(&mut self) {
-        // Get the current symbol under the head.
-        let current_symbol = self.tape[self.head];
-
-        // Get the current state.
-        let current_state = self.state.clone();
-
-        // Look up the transition rule for the current state and symbol.
-        if let Some(&(write, ref direction, ref next_state)) = self.rules.get(&(current_state, current_symbol)) {
-            // Update the symbol under the head.
-            self.tape[self.head] = write;
-
-            // Move the head in the specified direction.
-            match direction {
-                Direction::Left => {
-                    if self.head > 0 {
-                        self.head -= 1;
-                    } else {
-                        // If at the start, expand the tape to the left.
-                        self.tape.insert(0, '_');
-                    }
+use turing::machine::complexity;
+use turing::machine::fault::FaultInjector;
+use turing::machine::lockstep::{Lockstep, Side, Tick};
+use turing::machine::safety::{self, Property, SafetyOutcome};
+use turing::machine::stream::HeadTrace;
+use turing::registry::bundle::{self, Bundle};
+use turing::registry::vectors;
+use turing::rules::{lint, mutate, turingmachine_io, Fingerprint, MachineDefinition};
+use turing::tape::bounded;
+use turing::tape::decimal::{binary_to_decimal, decimal_to_binary};
+use turing::tape::display::{self, DisplayMap};
+use turing::tape::input::{self, build_tape, parse_input, LeadingZeros, ParseError};
+use turing::trace::formats::{self, Format};
+use turing::trace::replay::Trace;
+use turing::trace::stats::{AggregateStats, Distribution};
+use turing::{
+    registry, rules, tape, trace, Direction, HaltReason, OnSentinel, RuleTable, RunError, Sentinels, State, TapeMode,
+    TuringMachine, Verbosity,
+};
+
+use cache::{CachedResult, ResultCache};
+
+mod cache;
+
+const USAGE: &str = "\
+Usage: tm <command> [options]
+
+Commands:
+  add A B            add two binary numbers (A+B is accepted too)
+  trace A+B          add two binary numbers, printing every step
+  verify-trace FILE  check that a recorded trace holds together
+  check-input A+B    explain everything wrong with an adder input
+  run                run a rule file on a tape
+  rules [FORMAT]     print or check a rule table (text, dot, yaml, smtlib or lint)
+  list               list the built-in machines
+  export NAME        print a built-in machine as a bundle
+  batch FILE         add every `a,b` pair in a CSV file
+  complexity         measure adder steps over growing operands
+  mutate             check that the test vectors catch random rule bugs
+  check              check a safety property on every run up to a depth
+  gen-vectors        generate a test vector file for the adder
+  verify-vectors F   check a rule table against a test vector file
+  compare-live       step two machines in lockstep on the same tape
+  demo [long-run]    run the built-in example, or a deliberately slow machine
+  completions SHELL  print a tab completion script for bash, zsh or fish
+
+Without a command, runs a few example additions, printing each sum (`tm demo`).
+Run `tm <command> --help` for the options of a command.
+
+Environment:
+  BATM_MAX_STEPS     give up on a run after this many steps, unless --max-steps is given";
+
+// Prints the lines a machine's verbosity asks for on stdout, as the trace output, and
+// hands every other record to env_logger.
+struct Logger(env_logger::Logger);
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target() == trace::STEP_TARGET || self.0.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.target() == trace::STEP_TARGET {
+            println!("{}", record.args());
+        } else {
+            self.0.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.0.flush();
+    }
+}
+
+fn main() {
+    // Diagnostics are silent unless RUST_LOG asks, e.g. `RUST_LOG=trace` for every step on stderr.
+    let logger = env_logger::Builder::from_default_env().build();
+    log::set_max_level(logger.filter().max(log::LevelFilter::Info));
+    log::set_boxed_logger(Box::new(Logger(logger))).expect("no logger is set yet");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let rest = args.get(1..).unwrap_or_default();
+
+    match args.first().map(String::as_str) {
+        None => run_demo(false, DisplayMap::adder()),
+        Some("add") => run_add(rest),
+        Some("trace") => run_trace(rest),
+        Some("verify-trace") => verify_trace(rest),
+        Some("check-input") => check_input(rest),
+        Some("run") => run_rules(rest),
+        Some("rules") => print_rules(rest),
+        Some("list") => list_machines(rest),
+        Some("export") => export_machine(rest),
+        Some("batch") => run_batch(rest),
+        Some("complexity") => run_complexity(rest),
+        Some("mutate") => run_mutate(rest),
+        Some("check") => run_check(rest),
+        Some("gen-vectors") => generate_vectors(rest),
+        Some("verify-vectors") => verify_vectors(rest),
+        Some("compare-live") => compare_live(rest),
+        Some("demo") => run_demos(rest),
+        Some("completions") => print_completions(rest),
+        Some("help" | "--help" | "-h") => println!("{}", USAGE),
+        // Compatibility with the old positional style, `tm 1011+11`.
+        Some(input) if input.contains('+') => {
+            eprintln!("note: `tm {}` is deprecated, use `tm add {}`", input, input);
+            run_add(&args);
+        }
+        Some(command) => fail(&format!("unknown command '{}'\n\n{}", command, USAGE)),
+    }
+}
+
+// Runs the adder on each of the example inputs, printing the sums, or with `verbose`
+// every step as well.
+fn run_demo(verbose: bool, display: DisplayMap) {
+    // Room for the longest `a + b = sum`, whose sum has at most as many digits as `a+b` has characters.
+    let width = input::DEMO_INPUTS.iter().map(|(input, _)| 2 * input.len() + 5).max().unwrap_or(0);
+    for (index, (input, description)) in input::DEMO_INPUTS.iter().enumerate() {
+        // Initialize the tape with two binary numbers and a '+' in between.
+        let tape = parse_input(input, LeadingZeros::Strip).expect("the demo inputs are valid");
+
+        // Create the Turing machine with the tape and rules.
+        let mut machine = TuringMachine::new(tape, rules::adder());
+        machine.preserve_operands();
+        machine.set_verbose(verbose);
+        machine.set_display_map(display.clone());
+        if verbose {
+            println!("{}== {}: {}", if index == 0 { "" } else { "\n" }, input, description);
+        }
+
+        // Run the Turing machine, within BATM_MAX_STEPS if it is set.
+        run_machine(&mut machine, None);
+        let equation = machine.equation().unwrap();
+        println!("{:<width$}  {}", equation, description, width = width);
+    }
+}
+
+const DEMO_USAGE: &str = "\
+Usage: tm demo [long-run] [options]
+
+Without an argument, runs the adder on a few example additions (carries rippling
+through, operands of unequal lengths, zeros), printing each sum. With --verbose,
+prints every step as well, with the I/O markers shown as the underlined 1̲/0̲ they
+stand for unless --raw-symbols is given. `long-run` instead computes 2^N in unary with the `double` machine, which takes
+about 2.7 * 4^N steps and a tape of 2^N cells: a stress test for long runs, the
+step limit and the progress line. --n 13 takes seconds, --n 16 minutes.
+
+Options:
+  --verbose      print every step of the examples
+  --raw-symbols  show the I/O markers as they are in those steps
+  --n N          the exponent (default 12)
+  --max-steps N  give up after N steps";
+
+// `demo [long-run]`: runs one of the demonstrations.
+fn run_demos(args: &[String]) {
+    let args = Args::parse(args, &["--n", "--max-steps"], &["--verbose", "--raw-symbols"], DEMO_USAGE);
+    match args.positional.first().map(String::as_str) {
+        None => run_demo(args.flag("--verbose"), display_map(&args, &display::ADDER_MARKERS)),
+        Some("long-run") => run_long_demo(args.number("--n").unwrap_or(12), args.number("--max-steps")),
+        Some(name) => fail(&format!("unknown demo '{}'\n\n{}", name, DEMO_USAGE)),
+    }
+}
+
+// Computes 2^n with the slow doubling machine, reporting the steps, tape and time taken.
+fn run_long_demo(n: usize, max_steps: Option<usize>) {
+    if n >= usize::BITS as usize {
+        fail(&format!("--n must be below {}", usize::BITS));
+    }
+    let definition = builtin_machine("double");
+    let mut machine = TuringMachine::new(input::doubling_tape(n), definition.rules);
+    machine.set_state(definition.start);
+    machine.set_result_strategy(definition.result);
+    machine.set_final_states(definition.final_states);
+    let started = Instant::now();
+    run_machine(&mut machine, max_steps);
+
+    let ones = machine.result().len();
+    if ones != 1 << n {
+        fail(&format!("2^{} came out as {} ones", n, ones));
+    }
+    println!("2^{} = {} ones", n, ones);
+    println!("{} steps, {} tape cells, {:.2?}", machine.steps(), machine.tape().len(), started.elapsed());
+}
+
+const ADD_USAGE: &str = "\
+Usage: tm add A B [options]
+
+Options:
+  --decimal                 read the operands and print the sum in decimal, at any size
+  --show-operands           print the whole addition, `a + b = sum`, instead of just the sum
+  --diff                    print the tape before and after the run, lined up, with the
+                            cells that changed marked (and highlighted on a terminal,
+                            unless NO_COLOR is set), then the whole addition
+  --preserve-leading-zeros  keep leading zeros, so the sum is as wide as the widest operand
+  --max-steps N             give up after N steps
+  --fault-rate P            flip each written 0/1 (or O/I) with probability P, logging every
+                            fault and checking the result against a fault-free run
+  --fault-seed N            seed for choosing the faults (default 0)
+
+The step count grows with the square of the operand width: a 4096-bit
+addition takes tens of millions of steps.";
+
+// `add A B`: prints the sum.
+fn run_add(args: &[String]) {
+    let args = Args::parse(
+        args,
+        &["--max-steps", "--fault-rate", "--fault-seed"],
+        &["--decimal", "--show-operands", "--diff", "--preserve-leading-zeros"],
+        ADD_USAGE,
+    );
+    let leading_zeros = if args.flag("--preserve-leading-zeros") {
+        LeadingZeros::Preserve
+    } else {
+        LeadingZeros::Strip
+    };
+    let decimal = args.flag("--decimal");
+    let tape = match &args.positional[..] {
+        [input] if decimal => match input.split_once('+') {
+            Some((a, b)) => decimal_tape(a, b, leading_zeros),
+            None => Err(ParseError::MissingOperator),
+        },
+        [a, b] if decimal => decimal_tape(a, b, leading_zeros),
+        [input] => Ok(adder_tape(input, leading_zeros)),
+        // Checked as `A+B`, so both forms report the same problems.
+        [a, b] => Ok(adder_tape(&format!("{}+{}", a, b), leading_zeros)),
+        _ => fail(ADD_USAGE),
+    };
+    let tape = tape.unwrap_or_else(|error| fail(&error.to_string()));
+    let before = tape.clone();
+
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    machine.preserve_operands();
+    if let Some(rate) = args.value("--fault-rate") {
+        let rate = rate
+            .parse()
+            .ok()
+            .filter(|rate| (0.0..=1.0).contains(rate))
+            .unwrap_or_else(|| fail(&format!("--fault-rate expects a probability, got '{}'", rate)));
+        run_with_faults(&mut machine, rate, args.number("--fault-seed").unwrap_or(0) as u64, args.number("--max-steps"));
+    } else {
+        run_machine(&mut machine, args.number("--max-steps"));
+    }
+    if args.flag("--diff") {
+        let highlight = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+        let after: Vec<char> = machine.tape().iter().copied().collect();
+        println!("{}", trace::render_tape_diff(&before, &after, machine.grown_left(), highlight));
+    }
+    let to_decimal = |bits: &str| binary_to_decimal(bits).unwrap_or_else(|error| fail(&error.to_string()));
+    let (a, b) = machine.operands().unwrap();
+    match (args.flag("--show-operands") || args.flag("--diff"), decimal) {
+        (false, false) => println!("{}", machine.result()),
+        (false, true) => println!("{}", to_decimal(&machine.result())),
+        (true, false) => println!("{}", machine.equation().unwrap()),
+        (true, true) => println!("{} + {} = {}", to_decimal(a), to_decimal(b), to_decimal(&machine.result())),
+    }
+}
+
+// Step budget for a run with faults when --max-steps isn't given.
+const FAULTY_MAX_STEPS: usize = 10_000_000;
+
+// Runs the adder on an unreliable tape, logging every fault to stderr and warning if
+// the answer differs from a fault-free run's.
+fn run_with_faults(machine: &mut TuringMachine, rate: f64, seed: u64, max_steps: Option<usize>) {
+    let mut clean = TuringMachine::new(machine.tape().iter().copied().collect(), rules::adder());
+    let mut injector = FaultInjector::new(rate, seed);
+    // Faults can send the adder into a loop, so always bound the run.
+    let max_steps = max_steps.or_else(env_max_steps).unwrap_or(FAULTY_MAX_STEPS);
+    let outcome = machine.run_with_faults(&mut injector, max_steps);
+    for fault in injector.log() {
+        eprintln!("fault at {}", fault);
+    }
+    if let Err(error) = outcome {
+        fail(&error.to_string());
+    }
+    run_machine(&mut clean, Some(max_steps));
+    if machine.result() != clean.result() {
+        eprintln!(
+            "warning: {} faults changed the sum from {} to {}",
+            injector.log().len(),
+            clean.result(),
+            machine.result()
+        );
+    }
+}
+
+// Builds the adder's tape from two decimal operands.
+fn decimal_tape(a: &str, b: &str, leading_zeros: LeadingZeros) -> Result<Vec<char>, ParseError> {
+    if let Some(second) = [a, b].iter().position(|operand| operand.is_empty()) {
+        return Err(ParseError::MissingOperand { second: second == 1 });
+    }
+    build_tape(&decimal_to_binary(a)?, &decimal_to_binary(b)?, leading_zeros)
+}
+
+const TRACE_USAGE: &str = "\
+Usage: tm trace A+B [options]
+
+Options:
+  --full-tape    print the whole tape on every line
+  --show-rules   follow every line with the rule that fires
+  --explain      describe every step in words instead
+  --narrate      describe the addition bit by bit instead
+  --boxed        draw the tape on every step as a row of boxed cells instead
+  --raw-symbols  show the I/O markers as they are, not as the underlined 1̲/0̲
+                 they stand for
+  --window N     cells shown around the head on long tapes (default 40)
+  --svg FILE     also write the run to FILE as an animated SVG
+  --json FILE    also write the run to FILE as a trace, for `tm verify-trace`
+  --max-steps N  give up after N steps";
+
+// How a command shows the symbols in what it prints: `markers` as the symbols they stand
+// for, unless --raw-symbols asks for them as they are.
+fn display_map(args: &Args, markers: &[(char, char)]) -> DisplayMap {
+    if args.flag("--raw-symbols") {
+        DisplayMap::new()
+    } else {
+        DisplayMap::markers(markers)
+    }
+}
+
+// `trace A+B`: adds, printing every step.
+fn run_trace(args: &[String]) {
+    let args = Args::parse(
+        args,
+        &["--window", "--svg", "--json", "--max-steps"],
+        &["--full-tape", "--show-rules", "--explain", "--narrate", "--boxed", "--raw-symbols"],
+        TRACE_USAGE,
+    );
+    let [input] = &args.positional[..] else {
+        fail(TRACE_USAGE);
+    };
+    let tape = adder_tape(input, LeadingZeros::Strip);
+
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    machine.preserve_operands();
+    machine.set_descriptions(rules::adder_descriptions());
+    machine.set_display_map(display_map(&args, &display::ADDER_MARKERS));
+    machine.set_verbosity(if args.flag("--narrate") || args.flag("--boxed") {
+        Verbosity::Quiet
+    } else if args.flag("--explain") {
+        Verbosity::Explain
+    } else if args.flag("--show-rules") {
+        Verbosity::Rules
+    } else {
+        Verbosity::Steps
+    });
+    if args.flag("--full-tape") {
+        machine.set_trace_window(None);
+    } else if let Some(window) = args.number("--window") {
+        machine.set_trace_window(Some(window));
+    }
+    machine.set_tracing(args.value("--svg").is_some());
+    if let Some(path) = args.value("--json") {
+        // Recording runs the machine, so do it on a copy and trace the original as usual.
+        let mut copy = TuringMachine::new(machine.tape().iter().copied().collect(), rules::adder());
+        let recorded = Trace::record(&mut copy, args.number("--max-steps").unwrap_or(usize::MAX))
+            .unwrap_or_else(|error| fail(&error.to_string()));
+        fs::write(path, format!("{}\n", recorded.to_json()))
+            .unwrap_or_else(|error| fail(&format!("could not write {}: {}", path, error)));
+    }
+    if args.flag("--narrate") {
+        // The narration ends with the whole addition, so it needs no `Result:` line.
+        for sentence in machine.narrate() {
+            println!("{}", sentence);
+        }
+    } else if args.flag("--boxed") {
+        let max_steps = args.number("--max-steps").or_else(env_max_steps).unwrap_or(usize::MAX);
+        let display = machine.display_map().clone();
+        let boxed = machine.run_observed(|configuration| {
+            if configuration.steps > max_steps {
+                return Err(RunError::StepLimitExceeded(max_steps));
+            }
+            let tape = trace::render_boxed(configuration.tape.iter(), configuration.head, &display);
+            println!("step {}, state {}\n{}", configuration.steps, configuration.state, tape);
+            Ok(())
+        });
+        boxed.unwrap_or_else(|error| fail(&error.to_string()));
+        println!("Result: {}", machine.equation().unwrap());
+    } else {
+        run_machine(&mut machine, args.number("--max-steps"));
+        println!("Result: {}", machine.equation().unwrap());
+    }
+    if let Some(path) = args.value("--svg") {
+        fs::write(path, trace::trace_to_svg(machine.trace()))
+            .unwrap_or_else(|error| fail(&format!("could not write {}: {}", path, error)));
+    }
+}
+
+const VERIFY_TRACE_USAGE: &str = "\
+Usage: tm verify-trace FILE --input A+B
+
+Replays a trace written by `tm trace --json` on the adder's tape for A+B (or on
+a tape given cell by cell, starting with `_`), without the rules that made it:
+every step must start in the state the previous one went to, with the head where
+the previous moves left it, reading what the tape holds there. The final tape and
+answer the trace claims must be the ones its steps leave. Exits with an error
+naming the first step that doesn't fit, counted from 0.";
+
+const CHECK_INPUT_USAGE: &str = "\
+Usage: tm check-input A+B
+
+Checks an input for the adder the way `tm add` and `tm trace` do, but lists every
+problem rather than the first, each with its column and a caret under it: symbols
+other than 0 and 1, a missing or extra '+', a missing operand or no input at all.
+Exits with 1 if there are any.";
+
+// `check-input A+B`: lists every problem with an adder input.
+fn check_input(args: &[String]) {
+    let args = Args::parse(args, &[], &[], CHECK_INPUT_USAGE);
+    let [input] = &args.positional[..] else {
+        fail(CHECK_INPUT_USAGE);
+    };
+    let diagnostics = input::diagnose(input);
+    if diagnostics.is_empty() {
+        println!("ok: {}", input);
+        return;
+    }
+    println!("{}", input::render_diagnostics(input, &diagnostics));
+    process::exit(1);
+}
+
+// Builds the adder's tape for `a+b`, or fails showing every problem with the input, as
+// `check-input` does.
+fn adder_tape(input: &str, leading_zeros: LeadingZeros) -> Vec<char> {
+    parse_input(input, leading_zeros).unwrap_or_else(|_| {
+        fail(&format!("invalid input\n{}", input::render_diagnostics(input, &input::diagnose(input))))
+    })
+}
+
+// `verify-trace FILE --input A+B`: checks a recorded trace.
+fn verify_trace(args: &[String]) {
+    let args = Args::parse(args, &["--input"], &[], VERIFY_TRACE_USAGE);
+    let ([path], Some(input)) = (&args.positional[..], args.value("--input")) else {
+        fail(VERIFY_TRACE_USAGE);
+    };
+    let tape = if input.starts_with('_') {
+        input::parse_tape(input).unwrap_or_else(|error| fail(&error.to_string()))
+    } else {
+        adder_tape(input, LeadingZeros::Strip)
+    };
+    let text = fs::read_to_string(path).unwrap_or_else(|error| fail(&format!("could not read {}: {}", path, error)));
+    let recorded = Trace::from_json(&text).unwrap_or_else(|error| fail(&format!("{}: {}", path, error)));
+    if let Err(error) = recorded.verify_consistency(&tape) {
+        fail(&format!("{}: {}", path, error));
+    }
+    println!("{} steps, consistent", recorded.events.len());
+}
+
+const RUN_USAGE: &str = "\
+Usage: tm run (--rules FILE | --machine NAME | --bundle FILE)
+              (--input TAPE | --input-file FILE | --resume-config CONFIG) [options]
+
+The rule file has one `state read write move next` rule per line, e.g.
+`FindPlus 1 1 R FindPlus`, optionally followed by `# what it does`, and may set
+the start state with `start STATE`, the states it is meant to halt in with
+`final STATE ...` and how to read the answer with `result STRATEGY`. See
+machines/ for examples, and `tm list` for the machines --machine can name.
+Files ending in .yaml are read in the turingmachine.io format, and files ending in
+.json in the JSON rule format `tm rules json` writes.
+The tape is given cell by cell, e.g. `_1011+11_`. With --input-file it is read
+from a file instead (`-` for stdin), which suits tapes too long for the shell.
+A bundle, as written by `tm export`, holds a machine together with its alphabet
+and example inputs; with --verify-bundle the examples are run first, and the
+tape may then be left out.
+With --resume-config the run picks up from a configuration copied from another
+simulator: the state, then the tape with the head's cell bracketed, as in
+`GetLast _1010[1]+11_`, or with a `^` under it on a second line.
+
+Options:
+  --start STATE    the state to start in (default: the file's, or FindPlus)
+  --verbose        print every step
+  --show-rules     print every step followed by the rule that fires
+  --explain        describe every step in words, using the rule descriptions
+  --raw-symbols    print markers as they are, not as the symbols they stand for
+                   (the adder's I/O as underlined 1̲/0̲, or a bundle's `markers`)
+  --fixed-width    keep the tape at its initial length, halting with an overflow
+                   where it would have to grow
+  --sentinels LR   treat the symbols L and R (e.g. `<>`) as the ends of the input:
+                   the head never moves onto either, and halts instead
+  --reflect        with --sentinels, stay put instead of halting
+  --bounded LR     run as a linear bounded automaton on a tape such as `<abba>`,
+                   between the end markers L and R (e.g. `<>`): rules may read them
+                   but not overwrite them, and moving past one is an error
+  --max-tape-len N let the tape grow to at most N cells, halting where it would
+                   have to grow further; fails at once if the input is longer
+  --verify-bundle  run the bundle's examples, failing if any gives a wrong answer
+  --sanity-interval N
+                   every N steps, check the tape for symbols no rule knows
+  --max-steps N    give up after N steps
+  --last N         keep the last N configurations and print them if the run fails
+                   or halts other than in a final state
+  --head-trace FILE
+                   write the head's logical cell after every step to FILE, as
+                   `step,logical_head,state` CSV rows, for plotting
+  --head-trace-every N
+                   write only every Nth step's row, and the last one
+  --dry-run        load and check the machine and the tape, and estimate the run,
+                   without running it; exits with 1 if a check fails";
+
+// `run --rules FILE --input TAPE`: runs any rule table, printing the final tape and answer.
+fn run_rules(args: &[String]) {
+    let args = Args::parse(
+        args,
+        &[
+            "--rules",
+            "--machine",
+            "--bundle",
+            "--input",
+            "--input-file",
+            "--resume-config",
+            "--start",
+            "--sanity-interval",
+            "--max-steps",
+            "--head-trace",
+            "--head-trace-every",
+            "--sentinels",
+            "--max-tape-len",
+            "--bounded",
+            "--last",
+        ],
+        &[
+            "--verbose",
+            "--show-rules",
+            "--explain",
+            "--verify-bundle",
+            "--fixed-width",
+            "--reflect",
+            "--dry-run",
+            "--raw-symbols",
+        ],
+        RUN_USAGE,
+    );
+    // The markers come from the bundle, or from the built-in machine the rules are.
+    let (definition, markers) = match (args.value("--rules"), args.value("--machine"), args.value("--bundle")) {
+        (Some(path), None, None) => {
+            let definition = load_machine(path);
+            let markers = registry::identify(&definition.rules).map_or(&[][..], |entry| entry.markers);
+            (definition, markers.to_vec())
+        }
+        (None, Some(name), None) => (builtin_machine(name), builtin_entry(name).markers.to_vec()),
+        (None, None, Some(path)) => {
+            let bundle = load_bundle(path);
+            if args.flag("--verify-bundle") && !args.flag("--dry-run") {
+                verify_bundle(&bundle, args.number("--max-steps").unwrap_or(BUNDLE_MAX_STEPS));
+                if ["--input", "--input-file", "--resume-config"].iter().all(|option| args.value(option).is_none()) {
+                    return;
                 }
-                Direction::Right => {
-                    self.head += 1;
-                    if self.head >= self.tape.len() {
-                        // If at the end, expand the tape to the right.
-                        self.tape.push('_');
+            }
+            (bundle.definition, bundle.markers)
+        }
+        _ => fail(RUN_USAGE),
+    };
+    let mut machine = match (args.value("--input"), args.value("--input-file"), args.value("--resume-config")) {
+        (Some(input), None, None) => start_machine(input.to_string(), definition.rules),
+        (None, Some(path), None) => start_machine(read_tape(path), definition.rules),
+        (None, None, Some(config)) => {
+            if args.value("--start").is_some() {
+                fail("--start can't be combined with --resume-config, which names the state");
+            }
+            let snapshot = tape::parse_configuration(config, &definition.rules)
+                .unwrap_or_else(|error| fail(&format!("--resume-config: {}", error)));
+            TuringMachine::from_snapshot(&snapshot, definition.rules).unwrap_or_else(|error| fail(&error.to_string()))
+        }
+        _ => fail(RUN_USAGE),
+    };
+    if args.value("--resume-config").is_none() {
+        machine.set_state(args.value("--start").map_or(definition.start, State::from_name));
+    }
+    machine.set_result_strategy(definition.result);
+    machine.set_descriptions(definition.descriptions);
+    machine.set_final_states(definition.final_states);
+    machine.set_display_map(display_map(&args, &markers));
+    if args.flag("--fixed-width") {
+        machine.set_tape_mode(TapeMode::Fixed);
+    }
+    if let Some(symbols) = args.value("--sentinels") {
+        let [left, right] = symbols.chars().collect::<Vec<_>>()[..] else {
+            fail(&format!("--sentinels expects two symbols, the left and the right end, got '{}'", symbols));
+        };
+        let on_contact = if args.flag("--reflect") { OnSentinel::Reflect } else { OnSentinel::Halt };
+        machine.set_sentinels(Some(Sentinels { left, right, on_contact }));
+    }
+    if let Some(symbols) = args.value("--bounded") {
+        let [left_marker, right_marker] = symbols.chars().collect::<Vec<_>>()[..] else {
+            fail(&format!("--bounded expects two end markers, the left and the right, got '{}'", symbols));
+        };
+        if args.flag("--fixed-width") {
+            fail("--bounded can't be combined with --fixed-width, a bounded tape keeps its width anyway");
+        }
+        let tape: Vec<char> = machine.tape().iter().copied().collect();
+        bounded::check_rules(machine.rules(), left_marker, right_marker)
+            .and_then(|()| bounded::check_tape(&tape, left_marker, right_marker))
+            .unwrap_or_else(|error| fail(&error.to_string()));
+        machine.set_tape_mode(TapeMode::Bounded { left_marker, right_marker });
+    }
+    if let Err(error) = machine.set_max_tape_len(args.number("--max-tape-len")) {
+        fail(&error.to_string());
+    }
+    machine.set_sanity_interval(args.number("--sanity-interval"));
+    machine.set_snapshot_capacity(args.number("--last").unwrap_or(0));
+    if args.flag("--explain") {
+        machine.set_verbosity(Verbosity::Explain);
+    } else if args.flag("--show-rules") {
+        machine.set_verbosity(Verbosity::Rules);
+    } else {
+        machine.set_verbose(args.flag("--verbose"));
+    }
+    if args.flag("--dry-run") {
+        let source = match (args.value("--rules"), args.value("--machine"), args.value("--bundle")) {
+            (Some(path), _, _) => format!("rule file {}", path),
+            (_, Some(name), _) => format!("built-in machine {}", name),
+            (_, _, Some(path)) => format!("bundle {}", path),
+            _ => unreachable!("the machine was loaded from one of them"),
+        };
+        process::exit(dry_run(&machine, &source, args.number("--max-steps")));
+    }
+    match args.value("--head-trace") {
+        Some(path) => {
+            let every = args.number("--head-trace-every").unwrap_or(1);
+            run_head_trace(&mut machine, path, every, args.number("--max-steps"))
+        }
+        None => run_machine(&mut machine, args.number("--max-steps")),
+    }
+
+    let tape: String = machine.tape().iter().collect();
+    println!("{}", tape);
+    println!("Result: {}", machine.result());
+    println!("{} steps, head traveled {} cells", machine.steps(), machine.head_travel());
+    if machine.separator_consumed() {
+        println!("the answer grew over the separator's cell, it starts after the last '+'");
+    }
+    match machine.halt_reason() {
+        Some(HaltReason::Stuck { state, symbol }) => {
+            eprintln!("warning: the machine got stuck in state {} reading '{}', which has no rule", state, symbol)
+        }
+        Some(HaltReason::Overflow(direction)) => {
+            let end = if *direction == Direction::Left { "left" } else { "right" };
+            eprintln!("warning: the tape overflowed, the head would have moved off its {} end", end)
+        }
+        Some(HaltReason::Sentinel(sentinel)) => {
+            eprintln!("warning: the head ran into the sentinel '{}' at the end of the input", sentinel)
+        }
+        Some(HaltReason::TapeLimitExceeded(limit)) => {
+            eprintln!("warning: the tape reached its limit of {} cells and would have had to grow", limit)
+        }
+        _ => {}
+    }
+    if !machine.finished_successfully() {
+        print_last_snapshots(&machine);
+    }
+}
+
+// Prints the configurations `--last` kept, if any, to show how a run got where it failed.
+fn print_last_snapshots(machine: &TuringMachine) {
+    let snapshots = machine.last_snapshots();
+    if snapshots.is_empty() {
+        return;
+    }
+    eprintln!("the last {} configurations, oldest first:", snapshots.len());
+    for snapshot in snapshots {
+        eprintln!("  {}", snapshot.to_compact_string());
+    }
+}
+
+// `run --dry-run`: describes a loaded machine and its tape, checks them and estimates
+// the run, without applying a rule. Returns the exit code, 1 if a check failed.
+fn dry_run(machine: &TuringMachine, source: &str, max_steps: Option<usize>) -> i32 {
+    let rules = machine.rules();
+    let known = registry::identify(rules).filter(|_| !source.starts_with("built-in"));
+    let known = known.map_or(String::new(), |entry| format!(" (the built-in {})", entry.name));
+    println!("machine:  {}{}", source, known);
+    let mut states: Vec<&State> = rules.iter().flat_map(|((from, _), (_, _, to))| [from, to]).collect();
+    states.sort_by_key(|state| state.to_string());
+    states.dedup();
+    println!("rules:    {} rules over {} states, starting in {}", rules.len(), states.len(), machine.state());
+    let tape: Vec<char> = machine.tape().iter().copied().collect();
+    let mut symbols = tape.clone();
+    symbols.push('_');
+    symbols.sort();
+    symbols.dedup();
+    let head = machine.head();
+    println!("tape:     {} cells, head on cell {}, symbols {}", tape.len(), head, String::from_iter(&symbols));
+
+    // Only the adder has a model, and only for a run from the start of its tape.
+    let fresh_adder = rules == &rules::adder() && *machine.state() == State::FindPlus && machine.head() == 0;
+    match complexity::adder_step_bound(&tape).filter(|_| fresh_adder) {
+        Some(bound) => {
+            println!("estimate: at most {} steps, on a tape that stays {} cells", bound, tape.len());
+            if max_steps.is_some_and(|max_steps| max_steps < bound) {
+                println!("          --max-steps may stop the run before it halts");
+            }
+        }
+        None => println!("estimate: none for this machine, it runs until it halts or reaches --max-steps"),
+    }
+
+    let mut problems: Vec<String> = lint::lint(rules, &symbols).iter().map(ToString::to_string).collect();
+    let known = |symbol: char| {
+        symbol == '_' || rules.iter().any(|((_, read), (write, _, _))| *read == symbol || *write == symbol)
+    };
+    if let Some(cell) = tape.iter().position(|&symbol| !known(symbol)) {
+        problems.push(format!("cell {} holds '{}', which no rule reads or writes", cell, tape[cell]));
+    }
+    let read = machine.tape().get(machine.head()).copied().unwrap_or('_');
+    if !rules.contains_key(&(machine.state().clone(), read)) && !machine.final_states().contains(machine.state()) {
+        problems.push(format!("no rule reads '{}' in state {}, so the machine halts at once", read, machine.state()));
+    }
+    if problems.is_empty() {
+        println!("checks:   ok");
+        return 0;
+    }
+    println!("checks:   {} failed", problems.len());
+    for problem in &problems {
+        println!("          {}", problem);
+    }
+    1
+}
+
+const RULES_USAGE: &str = "\
+Usage: tm rules [text|dot|yaml|json|smtlib|lint|total] [options]
+
+Prints the adder's rules, or those of a rule file, as text, as a Graphviz graph, in
+the turingmachine.io format or in the JSON rule format, with each rule's description
+as a trailing comment, an edge tooltip or a `comment` field, and the machine's
+description, if its file has one, as a leading comment or the graph's label. Or
+prints them as an SMT-LIB transition function for proving properties.
+`lint` instead reports rules that can never fire and states that can never halt.
+`total` reports every state (but the final ones) and symbol of the alphabet with no
+rule, for formalisms that require a total transition function. The adder, which
+halts by finding no rule, is not total.
+
+Options:
+  --rules FILE        print this rule file (or builtin:NAME) instead of the adder
+  --alphabet SYMBOLS  the symbols the input may contain, for lint and total (default _01+IO)";
+
+// `rules [text|dot|lint]`: prints or checks a rule table.
+fn print_rules(args: &[String]) {
+    let args = Args::parse(args, &["--rules", "--alphabet"], &[], RULES_USAGE);
+    let definition = load_machine(args.value("--rules").unwrap_or("builtin:add"));
+    let MachineDefinition { rules, descriptions, .. } = &definition;
+    let alphabet = || -> Vec<char> {
+        match args.value("--alphabet") {
+            Some(symbols) => symbols.chars().collect(),
+            None => rules::ADDER_ALPHABET.to_vec(),
+        }
+    };
+    match args.positional.first().map(String::as_str) {
+        None | Some("text") => {
+            for line in definition.description.iter().flat_map(|description| description.lines()) {
+                println!("# {}", line);
+            }
+            print!("{}", rules::to_text_described(rules, descriptions))
+        }
+        Some("dot") => print!("{}", rules::to_machine_dot(&definition)),
+        Some("yaml") => print!("{}", turingmachine_io::export(&definition, &[])),
+        Some("json") => println!("{:#}", rules::to_json(&definition)),
+        Some("smtlib") => print!("{}", rules::to_smtlib(rules)),
+        Some("lint") => {
+            let warnings = lint::lint(rules, &alphabet());
+            for warning in &warnings {
+                println!("{}", warning);
+            }
+            if !warnings.is_empty() {
+                process::exit(1);
+            }
+        }
+        Some("total") => {
+            let mut states: Vec<State> =
+                rules.iter().flat_map(|((state, _), (_, _, next))| [state, next]).cloned().collect();
+            states.sort_by_cached_key(|state| state.to_string());
+            states.dedup();
+            states.retain(|state| !definition.final_states.contains(state));
+            let missing = rules::missing_rules(rules, &alphabet(), &states);
+            for (state, symbol) in &missing {
+                println!("no rule for state {} reading '{}'", state, symbol);
+            }
+            if !missing.is_empty() {
+                process::exit(1);
+            }
+            println!("total over {} states and {} symbols", states.len(), alphabet().len());
+        }
+        Some(format) => {
+            fail(&format!("unknown rule format '{}', use text, dot, yaml, json, smtlib, lint or total", format))
+        }
+    }
+}
+
+const LIST_USAGE: &str = "Usage: tm list";
+
+// `list`: prints the built-in machines.
+fn list_machines(args: &[String]) {
+    if !Args::parse(args, &[], &[], LIST_USAGE).positional.is_empty() {
+        fail(LIST_USAGE);
+    }
+    for entry in registry::MACHINES {
+        let definition = (entry.build)();
+        println!("{:<12} {}", entry.name, entry.description);
+        println!("{:<12} tape: {}, result: {}", "", entry.tape_format, definition.result);
+    }
+}
+
+const EXPORT_USAGE: &str = "Usage: tm export NAME";
+
+// `export NAME`: prints a built-in machine as a bundle.
+fn export_machine(args: &[String]) {
+    let args = Args::parse(args, &[], &[], EXPORT_USAGE);
+    let [name] = &args.positional[..] else {
+        fail(EXPORT_USAGE);
+    };
+    print!("{}", bundle::export(&Bundle::from_entry(builtin_entry(name))));
+}
+
+const BATCH_USAGE: &str = "\
+Usage: tm batch FILE [options]
+
+Adds every `a,b` line of FILE and prints `a,b,sum,steps,cached` for each.
+Blank lines and lines starting with `#` are skipped. Ends with a summary of
+the steps and tape cells used, and of the failures, on stderr.
+
+Options:
+  --max-steps N         give up on a pair after N steps
+  --cache DIR           reuse results stored in DIR by earlier runs, and store new ones
+  --summary-json FILE   also write the summary to FILE as JSON, in a tm-stats envelope";
+
+// `batch FILE`: adds every pair in a CSV file.
+fn run_batch(args: &[String]) {
+    let args = Args::parse(args, &["--max-steps", "--cache", "--summary-json"], &[], BATCH_USAGE);
+    let [path] = &args.positional[..] else {
+        fail(BATCH_USAGE);
+    };
+    let text = fs::read_to_string(path).unwrap_or_else(|error| fail(&format!("could not read {}: {}", path, error)));
+    let max_steps = args.number("--max-steps").or_else(env_max_steps);
+    let cache = args.value("--cache").map(|dir| {
+        ResultCache::open(dir).unwrap_or_else(|error| fail(&format!("could not open cache {}: {}", dir, error)))
+    });
+
+    // One machine for every pair, reset for each, so the rules are built once.
+    let mut machine = TuringMachine::new(input::DEMO_TAPE.chars().collect(), rules::adder());
+    let rules_fingerprint = machine.rules().fingerprint();
+    let (mut pairs, mut hits, mut steps_executed) = (0usize, 0usize, 0);
+    let mut stats = AggregateStats::new();
+
+    println!("a,b,sum,steps,cached");
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((a, b)) = line.split_once(',') else {
+            fail(&format!("{}:{}: expected `a,b`", path, index + 1));
+        };
+        let (a, b) = (a.trim(), b.trim());
+        pairs += 1;
+        let tape = match build_tape(a, b, LeadingZeros::Strip) {
+            Ok(tape) => tape,
+            Err(error) => {
+                println!("{},{},error: {},,", a, b, error);
+                stats.record_failure("invalid input");
+                continue;
+            }
+        };
+
+        let tape_fingerprint = tape.fingerprint();
+        if let Some(entry) = cache.as_ref().and_then(|cache| cache.get(rules_fingerprint, tape_fingerprint)) {
+            hits += 1;
+            println!("{},{},{},{},yes", a, b, entry.result, entry.steps);
+            stats.record_success(entry.steps, None);
+            continue;
+        }
+
+        machine.reset_with_input(tape);
+        let outcome = machine.run_bounded(max_steps.unwrap_or(usize::MAX));
+        steps_executed += machine.steps();
+        match outcome {
+            Ok(steps) => {
+                let entry = CachedResult { result: machine.result(), steps };
+                println!("{},{},{},{},no", a, b, entry.result, entry.steps);
+                stats.record_success(steps, Some(machine.tape().len()));
+                if let Some(cache) = &cache {
+                    if let Err(error) = cache.put(rules_fingerprint, tape_fingerprint, &entry) {
+                        eprintln!("warning: could not store a cache entry: {}", error);
                     }
                 }
             }
+            Err(error) => {
+                println!("{},{},error: {},{},no", a, b, error, machine.steps());
+                stats.record_failure("step limit");
+            }
+        }
+    }
+    eprintln!("{} pairs, {} cache hits, {} machine steps executed", pairs, hits, steps_executed);
+    print_distribution("steps", stats.steps());
+    print_distribution("tape cells", stats.tape_cells());
+    for (category, count) in stats.failures() {
+        eprintln!("failed ({}): {}", category, count);
+    }
 
-            // Transition to the next state.
-            self.state = next_state.clone();
-        } else {
-            // If no rule applies, halt the machine.
-            self.state = State::Halt;
+    if let Some(path) = args.value("--summary-json") {
+        let summary = stats.to_json().field("pairs", pairs).field("cache_hits", hits);
+        let summary = formats::envelope(Format::Stats, summary);
+        if let Err(error) = fs::write(path, format!("{}\n", summary)) {
+            fail(&format!("could not write {}: {}", path, error));
         }
     }
+}
+
+// Prints one line of the batch summary.
+fn print_distribution(name: &str, distribution: Option<Distribution>) {
+    match distribution {
+        Some(d) => eprintln!("{}: min {}, median {}, p95 {}, max {}", name, d.min, d.median, d.p95, d.max),
+        None => eprintln!("{}: n/a", name),
+    }
+}
+
+const COMPLEXITY_USAGE: &str = "\
+Usage: tm complexity [options]
+
+Runs the adder on all-ones operands of growing width and fits steps ≈ c·bits^k.
+
+Options:
+  --op add        the operation to measure (only add for now)
+  --max-bits N    widest operands to try (default 64)
+  --samples N     number of widths to try (default 20)
+  --csv FILE      also write the measurements as CSV";
+
+// `complexity --op add --max-bits 64 --samples 20 [--csv file]`: step counts over growing operands.
+fn run_complexity(args: &[String]) {
+    let args = Args::parse(args, &["--op", "--max-bits", "--samples", "--csv"], &[], COMPLEXITY_USAGE);
+    let op = args.value("--op").unwrap_or("add");
+    if op != "add" {
+        fail(&format!("unsupported operation '{}', only 'add' is available", op));
+    }
+    let max_bits = args.number("--max-bits").unwrap_or(64);
+    let samples = args.number("--samples").unwrap_or(20);
 
-    /// Runs the Turing machine until it reaches the Halt state.
-    fn run(&mut self) {
-        while self.state != State::Halt {
-            // Debugging output: Shows the tape, head position, and current state at each step.
-            println!("Tape: {:?}, Head: {}, State: {:?}", self.tape, self.head, self.state);
-            self.step();
+    let measured = complexity::measure_adder(max_bits, samples);
+    println!("{:>6} {:>12}", "bits", "steps");
+    for sample in &measured {
+        println!("{:>6} {:>12}", sample.bits, sample.steps);
+    }
+    match complexity::fitted_exponent(&measured) {
+        Some(exponent) => println!("fitted exponent: {:.3}", exponent),
+        None => println!("fitted exponent: n/a (need at least two sizes)"),
+    }
+
+    if let Some(path) = args.value("--csv") {
+        if let Err(error) = fs::write(path, complexity::to_csv(&measured)) {
+            fail(&format!("could not write {}: {}", path, error));
         }
-        // Final state and tape output after halting.
-        println!("Final Tape: {:?}, Head: {}, State: {:?}", self.tape, self.head, self.state);
     }
 }
 
-fn main() {
-    // Define the transition rules for the Turing machine.
-    let mut rules = HashMap::new();
-    
-
-    /*
-     * The rules below are defined as follows:
-     * *IF* I am currently in a state X, looking at a cell of valy Y, then write Z in said cell,
-     * Move in direction A, setting my state to state B. B could be X or any other state.
-     * */
-
-    //skips over the first empty cell
-    rules.insert((State::FindPlus, '_'), ('_', Direction::Right, State::FindPlus));
-    //keeps moving until we find a plus
-    rules.insert((State::FindPlus, '1'), ('1', Direction::Right, State::FindPlus));
-    rules.insert((State::FindPlus, '0'), ('0', Direction::Right, State::FindPlus));
-    //turns back to get the last number
-    rules.insert((State::FindPlus, '+'), ('+', Direction::Left, State::GetLast));
-    //if previous number is 0, add zer0 to the second number, be sure to delete the number
-    rules.insert((State::GetLast, '0'), ('+', Direction::Right, State::AddZero));
-    //move all the way to the right, ignoring 1, 0 and + symbols
-    rules.insert((State::AddZero, '1'), ('1', Direction::Right, State::AddZero));
-    rules.insert((State::AddZero, '0'), ('0', Direction::Right, State::AddZero));
-    rules.insert((State::AddZero, '+'), ('+', Direction::Right, State::AddZero));
-    //stop at a _, I or O, and turn to the number on our left
-    rules.insert((State::AddZero, 'I'), ('I', Direction::Left, State::AddDigitZero));
-    rules.insert((State::AddZero, 'O'), ('O', Direction::Left, State::AddDigitZero));
-    rules.insert((State::AddZero, '_'), ('_', Direction::Left, State::AddDigitZero));
-    //adds a zero to the current digit, and moves all the way back to the start of the first number
-    rules.insert((State::AddDigitZero, '1'), ('I', Direction::Left, State::BackToStart));
-    rules.insert((State::AddDigitZero, '0'), ('O', Direction::Left, State::BackToStart));
-    rules.insert((State::AddDigitZero, '+'), ('O', Direction::Left, State::BackToStart));
-
-    //carries a 1 to the right and deletes the current digit
-    rules.insert((State::GetLast, '1'), ('+', Direction::Right, State::AddOne));
-    rules.insert((State::AddOne, '1'), ('1', Direction::Right, State::AddOne));
-    rules.insert((State::AddOne, '0'), ('0', Direction::Right, State::AddOne));
-    rules.insert((State::AddOne, '+'), ('+', Direction::Right, State::AddOne));
-    rules.insert((State::AddOne, '_'), ('_', Direction::Left, State::AddDigitOne));
-    rules.insert((State::AddOne, 'I'), ('I', Direction::Left, State::AddDigitOne));
-    rules.insert((State::AddOne, 'O'), ('O', Direction::Left, State::AddDigitOne));
-   
-    //adds a zero to the last digit of the second number and moves back to plus
-    rules.insert((State::AddDigitOne, '1'), ('O', Direction::Left, State::Carry));
-    rules.insert((State::AddDigitOne, '0'), ('I', Direction::Left, State::BackToStart));
-    rules.insert((State::AddDigitOne, '+'), ('I', Direction::Left, State::BackToStart));
-    
-    rules.insert((State::Carry, '0'), ('1', Direction::Left, State::BackToStart));
-    rules.insert((State::Carry, '1'), ('0', Direction::Left, State::Carry));
-    rules.insert((State::Carry, '+'), ('1', Direction::Left, State::BackToStart));
-
-    //moves all the way back to the start of the tape
-    rules.insert((State::BackToStart, '0'), ('0', Direction::Left, State::BackToStart));
-    rules.insert((State::BackToStart, '1'), ('1', Direction::Left, State::BackToStart));
-    rules.insert((State::BackToStart, '+'), ('+', Direction::Left, State::BackToStart));
-
-    //once we reach the start, we pretend to start again
-    rules.insert((State::BackToStart, '_'), ('_', Direction::Right, State::FindPlus));
-
-
-
-    // Initialize the tape with two binary numbers and a '+' in between.
-    let tape = vec!['_','1','0','1','0','0','1','1','0','1','1','+', '1', '0', '1','1', '_'];
-
-    // Create the Turing machine with the tape and rules.
-    let mut machine = TuringMachine::new(tape, rules);
-
-    // Run the Turing machine.
-    machine.run();
+const CHECK_USAGE: &str = "\
+Usage: tm check --property PROPERTY [options] [TAPE ...]
+
+Explores every configuration the machine reaches from the given tapes (by default
+a built-in machine's examples) up to a number of steps, checking that none of them
+breaks the property, and prints the shortest run that does if one does. Exits with
+an error then. Properties:
+  no-write:X     the symbol X is never written into a cell that didn't hold it
+  no-write-left  nothing but a blank is written left of the initial tape
+
+Options:
+  --rules FILE   the machine to check, a rule file or builtin:NAME (default builtin:add)
+  --depth N      how many steps to explore (default 10000)";
+
+// `check --property P`: bounded model checking of a safety property.
+fn run_check(args: &[String]) {
+    let args = Args::parse(args, &["--property", "--rules", "--depth"], &[], CHECK_USAGE);
+    let property: Property = args
+        .value("--property")
+        .unwrap_or_else(|| fail(CHECK_USAGE))
+        .parse()
+        .unwrap_or_else(|error: turing::machine::safety::PropertyParseError| fail(&error.to_string()));
+    let path = args.value("--rules").unwrap_or("builtin:add");
+    let definition = load_machine(path);
+    let tapes: Vec<Vec<char>> = if args.positional.is_empty() {
+        let Some(name) = path.strip_prefix("builtin:") else {
+            fail("give the tapes to start from, a rule file has no examples");
+        };
+        builtin_entry(name).examples.iter().map(|(input, _)| input.chars().collect()).collect()
+    } else {
+        (args.positional.iter())
+            .map(|tape| input::parse_tape(tape).unwrap_or_else(|error| fail(&error.to_string())))
+            .collect()
+    };
+    let depth = args.number("--depth").unwrap_or(10_000);
+
+    match safety::check_safety(&definition.rules, &definition.start, &tapes, &property, depth) {
+        SafetyOutcome::Holds { depth, configurations, exhausted } => {
+            println!("{} holds up to depth {} ({} configurations explored)", property, depth, configurations);
+            if exhausted {
+                println!("every run halts or repeats itself before that, so it holds at any depth");
+            }
+        }
+        SafetyOutcome::Violated(counterexample) => {
+            eprintln!("{} is broken at step {}:", property, counterexample.step);
+            for (step, snapshot) in counterexample.trace.iter().enumerate() {
+                eprintln!("{:>6}  {}", step, snapshot.to_compact_string());
+            }
+            process::exit(1);
+        }
+    }
+}
+
+// Runs to completion, or up to `max_steps` when given (BATM_MAX_STEPS otherwise), exiting
+// on failure. A quiet run that takes more than a second shows its progress on stderr, if
+// that is a terminal.
+fn run_machine(machine: &mut TuringMachine, max_steps: Option<usize>) {
+    let max_steps = max_steps.or_else(env_max_steps).unwrap_or(usize::MAX);
+    let show_progress = machine.verbosity() == Verbosity::Quiet && io::stderr().is_terminal();
+    let adder = machine.rules() == &rules::adder();
+    let started = Instant::now();
+    let mut shown = false;
+    let every = if show_progress { PROGRESS_STEPS } else { usize::MAX };
+    // Always a bounded run, as only those report failed sanity checks.
+    let outcome = machine.run_bounded_with_progress(max_steps, every, |machine| {
+        let elapsed = started.elapsed();
+        if elapsed < PROGRESS_DELAY {
+            return;
+        }
+        let rate = machine.steps() as f64 / elapsed.as_secs_f64();
+        let mut line = format!(
+            "{} steps, {:.0} steps/s, {} cells, state {}",
+            machine.steps(),
+            rate,
+            machine.tape().len(),
+            machine.state()
+        );
+        if let Some(remaining) = adder.then(|| machine.estimated_remaining_steps()).flatten() {
+            let done = machine.steps() as f64 / (machine.steps() + remaining) as f64;
+            line.push_str(&format!(", {:.0}% done, about {:.0}s to go", done * 100.0, remaining as f64 / rate));
+        }
+        eprint!("\r\x1b[K{}", line);
+        shown = true;
+    });
+    if shown {
+        eprint!("\r\x1b[K");
+    }
+    if let Err(error) = outcome {
+        let bound = matches!(error, RunError::PastMarker { .. } | RunError::StuckOnMarker { .. });
+        if bound || matches!(error, RunError::StepLimitExceeded(_)) {
+            eprintln!("tape so far: {}", machine.tape().iter().collect::<String>());
+        }
+        print_last_snapshots(machine);
+        fail(&error.to_string());
+    }
+}
+
+// Runs like `run_machine`, writing the head's trajectory to `path` as it goes, every
+// `every`th step and the last. A run that fails still leaves the rows up to then.
+fn run_head_trace(machine: &mut TuringMachine, path: &str, every: usize, max_steps: Option<usize>) {
+    let max_steps = max_steps.or_else(env_max_steps).unwrap_or(usize::MAX);
+    let not_written = |error: io::Error| format!("could not write {}: {}", path, error);
+    let file = File::create(path).unwrap_or_else(|error| fail(&not_written(error)));
+    let mut trace = HeadTrace::new(BufWriter::new(file), every).unwrap_or_else(|error| fail(&not_written(error)));
+    let outcome = machine.run_observed(|configuration| {
+        trace.observe(configuration).map_err(not_written)?;
+        if configuration.steps >= max_steps && *configuration.state != State::Halt {
+            return Err(RunError::StepLimitExceeded(max_steps).to_string());
+        }
+        Ok(())
+    });
+    trace.finish().unwrap_or_else(|error| fail(&not_written(error)));
+    if let Err(message) = outcome {
+        fail(&message);
+    }
+}
+
+// The step limit BATM_MAX_STEPS sets for runs without --max-steps, so that a broken rule
+// table can't hang the terminal. Exits if it is set to something other than a number.
+fn env_max_steps() -> Option<usize> {
+    let value = env::var("BATM_MAX_STEPS").ok()?;
+    Some(value.parse().unwrap_or_else(|_| fail(&format!("BATM_MAX_STEPS expects a number, got '{}'", value))))
+}
+
+// Steps between progress updates, a few per second at typical speeds.
+const PROGRESS_STEPS: usize = 5_000_000;
+
+// How long a run goes before it shows its progress.
+const PROGRESS_DELAY: Duration = Duration::from_secs(1);
+
+const MUTATE_USAGE: &str = "\
+Usage: tm mutate [options]
+
+Applies one random mutation at a time to a rule table (change a written symbol,
+a move or a next state, or delete a rule) and runs the adder's test vectors on
+the mutant. Prints the share of mutants the vectors caught and lists the rest.
+
+Options:
+  --rules FILE      the rule file (or builtin:NAME) to mutate (default builtin:add)
+  --iterations N    number of mutants to try (default 100)
+  --seed N          seed for choosing mutations (default 0)";
+
+// `mutate --rules FILE --iterations N --seed N`: mutation testing of the test vectors.
+fn run_mutate(args: &[String]) {
+    let args = Args::parse(args, &["--rules", "--iterations", "--seed"], &[], MUTATE_USAGE);
+    let path = args.value("--rules").unwrap_or("builtin:add");
+    let iterations = args.number("--iterations").unwrap_or(100);
+    let seed = args.number("--seed").unwrap_or(0);
+
+    let rules = load_machine(path).rules;
+    let Some(outcomes) = mutate::mutation_test(&rules, iterations, seed as u64) else {
+        fail(&format!("{} does not pass the test vectors unmutated", path));
+    };
+    let survivors: Vec<_> = outcomes.iter().filter(|outcome| !outcome.killed).collect();
+    let killed = outcomes.len() - survivors.len();
+    let rate = if outcomes.is_empty() { 0.0 } else { 100.0 * killed as f64 / outcomes.len() as f64 };
+    println!("killed {} of {} mutants ({:.1}%)", killed, outcomes.len(), rate);
+    if !survivors.is_empty() {
+        println!("surviving mutants:");
+        for outcome in survivors {
+            println!("  {}", outcome.mutation);
+        }
+    }
+}
+
+const GEN_VECTORS_USAGE: &str = "\
+Usage: tm gen-vectors [options]
+
+Writes a test vector file: edge cases, then random pairs of operands, each with its
+sum and the steps the built-in adder takes. The same count and seed always give the
+same file; tests/vectors.json is `--count 500 --seed 1`.
+
+Options:
+  --count N    number of vectors (default 500)
+  --seed N     seed for the random pairs (default 1)
+  --out FILE   write to FILE instead of stdout";
+
+// `gen-vectors --count N --seed N --out FILE`: writes a test vector file.
+fn generate_vectors(args: &[String]) {
+    let args = Args::parse(args, &["--count", "--seed", "--out"], &[], GEN_VECTORS_USAGE);
+    let seed = args.number("--seed").unwrap_or(1) as u64;
+    let text = vectors::export(&vectors::generate(args.number("--count").unwrap_or(500), seed), seed);
+    match args.value("--out") {
+        Some(path) => {
+            fs::write(path, text).unwrap_or_else(|error| fail(&format!("could not write {}: {}", path, error)))
+        }
+        None => print!("{}", text),
+    }
+}
+
+const VERIFY_VECTORS_USAGE: &str = "\
+Usage: tm verify-vectors FILE [options]
+
+Runs a rule table on every vector in a test vector file and lists the ones whose
+sum or step count differs. Exits with an error if any does.
+
+Options:
+  --rules FILE     the machine to check, a rule file or builtin:NAME (default builtin:add)
+  --ignore-steps   only check the sums, for machines that take other step counts";
+
+// `verify-vectors FILE --rules FILE --ignore-steps`: checks a machine against a vector file.
+fn verify_vectors(args: &[String]) {
+    let args = Args::parse(args, &["--rules"], &["--ignore-steps"], VERIFY_VECTORS_USAGE);
+    let [path] = &args.positional[..] else {
+        fail(VERIFY_VECTORS_USAGE);
+    };
+    let text = fs::read_to_string(path).unwrap_or_else(|error| fail(&format!("could not read {}: {}", path, error)));
+    let vectors = vectors::parse(&text).unwrap_or_else(|error| fail(&format!("{}: {}", path, error)));
+    let rules = load_machine(args.value("--rules").unwrap_or("builtin:add")).rules;
+
+    let failures = vectors::verify(&rules, &vectors, args.flag("--ignore-steps"));
+    for failure in &failures {
+        eprintln!("{}", failure);
+    }
+    println!("{} of {} vectors passed", vectors.len() - failures.len(), vectors.len());
+    if !failures.is_empty() {
+        process::exit(1);
+    }
+}
+
+const COMPARE_LIVE_USAGE: &str = "\
+Usage: tm compare-live --machines A,B --input TAPE [options]
+
+Steps two machines on the same tape in lockstep and prints a line per tick: each
+machine's steps, state and answer as decoded so far, marked where the answers differ.
+There is no interactive view; the lines are printed as the machines run.
+A machine is a built-in one (see `tm list`), a rule file or builtin:NAME. The tape is
+given cell by cell, e.g. `_1011+11_`, or as the adder's `1011+11`.
+
+Options:
+  --pace N,M       steps each machine takes per tick (default 1,1, lockstep); a
+                   machine that halts first waits for the other
+  --max-steps N    give up once either machine has taken N steps";
+
+// `compare-live --machines A,B --input TAPE --pace N,M`: prints paired lines as two machines run in lockstep.
+fn compare_live(args: &[String]) {
+    let args = Args::parse(args, &["--machines", "--input", "--pace", "--max-steps"], &[], COMPARE_LIVE_USAGE);
+    let (Some(machines), Some(input)) = (args.value("--machines"), args.value("--input")) else {
+        fail(COMPARE_LIVE_USAGE);
+    };
+    let Some((left, right)) = machines.split_once(',') else {
+        fail(&format!("--machines expects two machines, e.g. add,increment, got '{}'", machines));
+    };
+    let tape = if input::validate_input(input).is_ok() {
+        parse_input(input, LeadingZeros::Strip)
+    } else {
+        input::parse_tape(input)
+    };
+    let tape = tape.unwrap_or_else(|error| fail(&error.to_string()));
+    let machine = |name: &str| {
+        let definition = if name.contains(['.', '/', ':']) { load_machine(name) } else { builtin_machine(name) };
+        let mut machine = TuringMachine::new(tape.clone(), definition.rules);
+        machine.set_state(definition.start);
+        machine.set_result_strategy(definition.result);
+        machine.set_final_states(definition.final_states);
+        machine
+    };
+    let (left_pace, right_pace) = match args.value("--pace") {
+        None => (1, 1),
+        Some(pace) => match pace.split_once(',').map(|(l, r)| (l.parse(), r.parse())) {
+            Some((Ok(left), Ok(right))) => (left, right),
+            _ => fail(&format!("--pace expects two numbers of steps, e.g. 1,3, got '{}'", pace)),
+        },
+    };
+    let max_steps = args.number("--max-steps").or_else(env_max_steps).unwrap_or(usize::MAX);
+
+    let mut lockstep = Lockstep::new(machine(left), machine(right)).with_pace(left_pace, right_pace);
+    let row = |tick: &Tick| {
+        let side = |side: &Side| format!("{:>6} {:<14} {:<12}", side.steps, side.state.to_string(), side.result);
+        let marker = if tick.diverged { "  <- answers differ" } else { "" };
+        let line = format!("{:>6}  {} | {}{}", tick.tick, side(&tick.sides[0]), side(&tick.sides[1]), marker);
+        println!("{}", line.trim_end());
+    };
+    println!("{:>6}  {:>6} {:<27} | {:>6} {}", "tick", "steps", left, "steps", right);
+    row(&lockstep.current());
+    while let Some(tick) = lockstep.tick() {
+        row(&tick);
+        if tick.sides.iter().any(|side| !side.halted && side.steps >= max_steps) {
+            fail(&format!("gave up after {} ticks, a machine did not halt within {} steps", tick.tick, max_steps));
+        }
+    }
+    match lockstep.first_divergence() {
+        Some(tick) => println!("the answers first differed after tick {}", tick),
+        None => println!("the answers never differed"),
+    }
+}
+
+// Reads and parses a rule file, a turingmachine.io file (`.yaml`), or builds a built-in
+// machine given as `builtin:NAME`, exiting on failure.
+fn load_machine(path: &str) -> MachineDefinition {
+    if let Some(name) = path.strip_prefix("builtin:") {
+        return builtin_machine(name);
+    }
+    let text = fs::read_to_string(path).unwrap_or_else(|error| fail(&format!("could not read {}: {}", path, error)));
+    if path.ends_with(".yaml") || path.ends_with(".yml") {
+        return turingmachine_io::parse(&text)
+            .unwrap_or_else(|error| fail(&format!("{}: {}", path, error)))
+            .definition;
+    }
+    if path.ends_with(".json") {
+        return rules::from_json(&text).unwrap_or_else(|error| fail(&format!("{}: {}", path, error)));
+    }
+    rules::parse_machine(&text).unwrap_or_else(|error| fail(&format!("{}: {}", path, error)))
+}
+
+// A machine at the start of `tape`, which must not be empty.
+fn start_machine(tape: String, rules: RuleTable) -> TuringMachine {
+    if tape.is_empty() {
+        fail("the input tape is empty");
+    }
+    let tape = input::parse_tape(&tape).unwrap_or_else(|error| fail(&error.to_string()));
+    TuringMachine::new(tape, rules)
+}
+
+// Reads a tape from a file, or from stdin for `-`, dropping the trailing newlines
+// an editor or `echo` adds. Exits if the tape is empty or spans several lines.
+fn read_tape(path: &str) -> String {
+    let text = if path == "-" {
+        io::read_to_string(io::stdin()).unwrap_or_else(|error| fail(&format!("could not read stdin: {}", error)))
+    } else {
+        fs::read_to_string(path).unwrap_or_else(|error| fail(&format!("could not read {}: {}", path, error)))
+    };
+    let tape = text.trim_end_matches(['\n', '\r']);
+    if tape.is_empty() {
+        fail(&format!("{} holds no tape", if path == "-" { "stdin" } else { path }));
+    }
+    if let Some(position) = tape.find(['\n', '\r']) {
+        fail(&format!("the tape has a line break after cell {}, it must be on one line", position));
+    }
+    tape.to_string()
+}
+
+// Reads and parses a bundle, exiting on failure.
+fn load_bundle(path: &str) -> Bundle {
+    let text = fs::read_to_string(path).unwrap_or_else(|error| fail(&format!("could not read {}: {}", path, error)));
+    bundle::parse(&text).unwrap_or_else(|error| fail(&format!("{}: {}", path, error)))
+}
+
+// Step budget for each of a bundle's examples when --max-steps isn't given.
+const BUNDLE_MAX_STEPS: usize = 10_000_000;
+
+// Runs a bundle's examples, exiting after listing the failures if there are any.
+fn verify_bundle(bundle: &Bundle, max_steps: usize) {
+    let failures = bundle.check_examples(max_steps);
+    for failure in &failures {
+        eprintln!("{}", failure);
+    }
+    if !failures.is_empty() {
+        fail(&format!("{} of {} examples failed", failures.len(), bundle.examples.len()));
+    }
+    eprintln!("all {} examples passed", bundle.examples.len());
+}
+
+// Builds a built-in machine by name, exiting with suggestions if there is none.
+fn builtin_machine(name: &str) -> MachineDefinition {
+    (builtin_entry(name).build)()
+}
+
+const COMPLETIONS_USAGE: &str = "\
+Usage: tm completions bash|zsh|fish
+
+Prints a script that completes tm's commands, their options and the names of the
+built-in machines. For bash, add `source <(tm completions bash)` to ~/.bashrc; for
+zsh, save it as _tm somewhere on $fpath; for fish, save it as
+~/.config/fish/completions/tm.fish.";
+
+// Every command, with its usage, which names all of its options, and the words it
+// takes as its first argument. `(machines)` stands for the built-in machines' names.
+const COMMANDS: &[(&str, &str, &[&str])] = &[
+    ("add", ADD_USAGE, &[]),
+    ("trace", TRACE_USAGE, &[]),
+    ("verify-trace", VERIFY_TRACE_USAGE, &[]),
+    ("check-input", CHECK_INPUT_USAGE, &[]),
+    ("run", RUN_USAGE, &[]),
+    ("rules", RULES_USAGE, &["text", "dot", "yaml", "json", "smtlib", "lint", "total"]),
+    ("list", LIST_USAGE, &[]),
+    ("export", EXPORT_USAGE, &["(machines)"]),
+    ("batch", BATCH_USAGE, &[]),
+    ("complexity", COMPLEXITY_USAGE, &[]),
+    ("mutate", MUTATE_USAGE, &[]),
+    ("check", CHECK_USAGE, &[]),
+    ("gen-vectors", GEN_VECTORS_USAGE, &[]),
+    ("verify-vectors", VERIFY_VECTORS_USAGE, &[]),
+    ("compare-live", COMPARE_LIVE_USAGE, &[]),
+    ("demo", DEMO_USAGE, &["long-run"]),
+    ("completions", COMPLETIONS_USAGE, &["bash", "zsh", "fish"]),
+];
+
+// `completions SHELL`: prints a completion script. Nothing else goes to stdout, so
+// the output can be sourced as it is.
+fn print_completions(args: &[String]) {
+    let args = Args::parse(args, &[], &[], COMPLETIONS_USAGE);
+    let machines: Vec<&str> = registry::names().collect();
+    let machines = machines.join(" ");
+    let names: Vec<&str> = COMMANDS.iter().map(|(name, _, _)| *name).collect();
+    // The words to offer after a command: its first argument's and its options.
+    let candidates = |first: &[&str], usage: &str| {
+        let first = first.join(" ").replace("(machines)", &machines);
+        first.split_whitespace().chain(options(usage)).collect::<Vec<_>>().join(" ")
+    };
+
+    let mut script = String::new();
+    match args.positional.first().map(String::as_str) {
+        Some("bash") => {
+            let mut cases = String::new();
+            for (name, usage, first) in COMMANDS {
+                let words = candidates(first, usage);
+                cases.push_str(&format!("        {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n", name, words));
+            }
+            script = format!(
+                r#"_tm() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    if [ "$COMP_CWORD" -eq 1 ]; then
+        COMPREPLY=($(compgen -W "{}" -- "$cur"))
+        return
+    fi
+    if [ "$prev" = --machine ]; then
+        COMPREPLY=($(compgen -W "{}" -- "$cur"))
+        return
+    fi
+    case "${{COMP_WORDS[1]}}" in
+{}    esac
+}}
+complete -o default -F _tm tm
+"#,
+                names.join(" "),
+                machines,
+                cases
+            );
+        }
+        Some("zsh") => {
+            let mut commands = String::new();
+            let mut cases = String::new();
+            for (name, usage, first) in COMMANDS {
+                commands.push_str(&format!("            '{}:{}'\n", name, describe(name).replace('\'', "'\\''")));
+                cases.push_str(&format!("        {}) compadd -- {} ;;\n", name, candidates(first, usage)));
+            }
+            script = format!(
+                r#"#compdef tm
+
+_tm() {{
+    if (( CURRENT == 2 )); then
+        local -a commands
+        commands=(
+{}        )
+        _describe command commands
+        return
+    fi
+    if [[ ${{words[CURRENT-1]}} == --machine ]]; then
+        compadd -- {}
+        return
+    fi
+    case ${{words[2]}} in
+{}    esac
+    _files
+}}
+
+_tm "$@"
+"#,
+                commands, machines, cases
+            );
+        }
+        Some("fish") => {
+            script.push_str("complete -c tm -f\n");
+            for name in &names {
+                let description = describe(name).replace('\'', "\\'");
+                script.push_str(&format!("complete -c tm -n __fish_use_subcommand -a {} -d '{}'\n", name, description));
+            }
+            for (name, usage, first) in COMMANDS {
+                let when = format!("complete -c tm -n '__fish_seen_subcommand_from {}'", name);
+                if !first.is_empty() {
+                    let first = first.join(" ").replace("(machines)", &machines);
+                    script.push_str(&format!("{} -a '{}'\n", when, first));
+                }
+                for option in options(usage) {
+                    script.push_str(&format!("{} -l {}\n", when, &option[2..]));
+                }
+            }
+            script.push_str(&format!("complete -c tm -l machine -x -a '{}'\n", machines));
+            script.push_str("complete -c tm -n 'not __fish_use_subcommand' -F\n");
+        }
+        _ => fail(COMPLETIONS_USAGE),
+    }
+    print!("{}", script);
+}
+
+// A command's one-line description, from the top-level usage.
+fn describe(name: &str) -> &'static str {
+    let line = USAGE.lines().find(|line| {
+        let rest = line.strip_prefix("  ").and_then(|line| line.strip_prefix(name));
+        rest.is_some_and(|rest| rest.starts_with(' '))
+    });
+    line.and_then(|line| line[2..].split_once("  ")).map_or("", |(_, description)| description.trim())
+}
+
+// The options a usage names, in order, e.g. `--max-steps` for `  --max-steps N  ...`.
+// Quoted commands, as in "written by `tm trace --json`", are another command's.
+fn options(usage: &str) -> Vec<&str> {
+    let mut options = Vec::new();
+    let unquoted = usage.split('`').step_by(2);
+    let words = unquoted.flat_map(|text| text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-')));
+    for option in words.filter(|word| word.len() > 2 && word.starts_with("--")) {
+        if !options.contains(&option) {
+            options.push(option);
+        }
+    }
+    options
+}
+
+// Looks up a built-in machine by name, exiting with suggestions if there is none.
+fn builtin_entry(name: &str) -> &'static registry::Entry {
+    registry::entry(name).unwrap_or_else(|| match registry::suggest(name).first() {
+        Some(suggestion) => fail(&format!("unknown machine '{}', did you mean '{}'?", name, suggestion)),
+        None => fail(&format!("unknown machine '{}', see `tm list`", name)),
+    })
+}
+
+// A subcommand's arguments, split into positionals, options with values and flags.
+struct Args {
+    positional: Vec<String>,                  // Arguments that are not options, in order.
+    options: HashMap<String, Option<String>>, // Options seen, with their value if they take one.
+}
+
+impl Args {
+    // Splits `args`, exiting with `usage` on `--help` or an unknown option.
+    fn parse(args: &[String], valued: &[&str], flags: &[&str], usage: &str) -> Args {
+        let mut parsed = Args {
+            positional: Vec::new(),
+            options: HashMap::new(),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--help" || arg == "-h" {
+                println!("{}", usage);
+                process::exit(0);
+            } else if valued.contains(&arg.as_str()) {
+                let Some(value) = args.next() else {
+                    fail(&format!("{} needs a value", arg));
+                };
+                parsed.options.insert(arg.clone(), Some(value.clone()));
+            } else if flags.contains(&arg.as_str()) {
+                parsed.options.insert(arg.clone(), None);
+            } else if arg.starts_with("--") {
+                fail(&format!("unknown option '{}'\n\n{}", arg, usage));
+            } else {
+                parsed.positional.push(arg.clone());
+            }
+        }
+        parsed
+    }
+
+    // Whether a flag was given.
+    fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    // The value of an option, if given.
+    fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name)?.as_deref()
+    }
+
+    // The value of an option, parsed as a number.
+    fn number(&self, name: &str) -> Option<usize> {
+        self.value(name).map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| fail(&format!("{} expects a number, got '{}'", name, value)))
+        })
+    }
+}
+
+// Prints an error and exits.
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(2);
 }
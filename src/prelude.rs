@@ -0,0 +1,13 @@
+// The items most programs need, for `use turing::prelude::*;`: the machine and its builder,
+// what its rules and tape are made of, the errors a run ends with, and the adder's tape
+// builders. Everything else stays behind its module path, e.g. `turing::registry::vectors`.
+// Items are only added here, never removed or renamed outside a major version; the listing
+// in tests/public_api.txt catches any change to the public surface.
+
+pub use crate::error::{Error, RunError};
+pub use crate::machine::builder::TuringMachineBuilder;
+pub use crate::tape::extract::ResultStrategy;
+pub use crate::tape::input::{build_tape, parse_input, LeadingZeros};
+pub use crate::trace::Snapshot;
+pub use crate::{machine, registry, rules, tape, trace};
+pub use crate::{Direction, HaltReason, RuleTable, State, Tape, TapeMode, TuringMachine};
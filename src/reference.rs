@@ -0,0 +1,89 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Direction, RuleTable, State};
+
+/// A deliberately naive Turing machine, kept independent of `TuringMachine` and
+/// `CompiledMachine` so the three can be run against each other, see
+/// `testing::assert_same_traces`. The tape is a plain string, rules are searched
+/// one by one, and nothing is optimized.
+pub struct ReferenceMachine {
+    tape: String,                                      // One character per cell.
+    head: usize,                                       // Index of the head's cell, in characters.
+    state: State,                                      // The current state.
+    rules: Vec<(State, char, char, Direction, State)>, // The rules, in no particular order.
+    steps: usize,                                      // Number of rules applied so far.
+}
+
+impl ReferenceMachine {
+    /// Creates a machine with the head on cell `head` of `tape`, in state `state`.
+    pub fn new(tape: &[char], head: usize, state: State, rules: &RuleTable) -> Self {
+        ReferenceMachine {
+            tape: tape.iter().collect(),
+            head,
+            state,
+            rules: rules
+                .iter()
+                .map(|((state, read), (write, direction, next_state))| {
+                    (state.clone(), *read, *write, direction.clone(), next_state.clone())
+                })
+                .collect(),
+            steps: 0,
+        }
+    }
+
+    /// The tape, one character per cell.
+    pub fn tape(&self) -> &str {
+        &self.tape
+    }
+
+    /// The position of the head on the tape.
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// The current state.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Number of rules applied so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Applies the rule for the current state and symbol, or halts if there is none.
+    pub fn step(&mut self) {
+        if self.state == State::Halt {
+            return;
+        }
+        let read = self.tape.chars().nth(self.head).unwrap();
+        let mut found = None;
+        for rule in &self.rules {
+            if rule.0 == self.state && rule.1 == read {
+                found = Some(rule.clone());
+            }
+        }
+        let Some((_, _, write, direction, next_state)) = found else {
+            self.state = State::Halt;
+            return;
+        };
+
+        let mut cells: Vec<char> = self.tape.chars().collect();
+        cells[self.head] = write;
+        match direction {
+            Direction::Left if self.head == 0 => cells.insert(0, '_'),
+            Direction::Left => self.head -= 1,
+            Direction::Right => {
+                self.head += 1;
+                if self.head == cells.len() {
+                    cells.push('_');
+                }
+            }
+            Direction::Stay => {}
+        }
+        self.tape = cells.into_iter().collect();
+        self.state = next_state;
+        self.steps += 1;
+    }
+}
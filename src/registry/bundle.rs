@@ -0,0 +1,280 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use crate::registry::Entry;
+use crate::rules::{self, MachineDefinition};
+use crate::tape::display::DisplayMap;
+use crate::tape::input::parse_tape;
+use crate::{RunError, TuringMachine};
+
+pub use crate::error::{BundleError, BundleErrorKind};
+
+/// A machine with everything needed to share it: its rules, start and final states and
+/// result strategy, its alphabet, and example inputs with the answers they should give.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bundle {
+    pub name: String,                  // What the machine is called.
+    pub description: String,           // What it computes, in one line.
+    pub alphabet: Vec<char>,           // Every symbol its tapes hold, blank included.
+    pub markers: Vec<(char, char)>,    // Symbols that stand for others, see `DisplayMap::markers`.
+    pub definition: MachineDefinition, // The machine itself.
+    pub examples: Vec<Example>,        // Inputs with known answers, for `check_examples`.
+}
+
+/// An input tape and the answer the machine should read off it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,  // The tape, cell by cell, e.g. `_1011+11_`.
+    pub output: String, // The expected answer.
+}
+
+/// An example whose run didn't give the expected answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleFailure {
+    pub index: usize,                     // Position of the example in the bundle, from 0.
+    pub expected: String,                 // The answer the bundle gives.
+    pub actual: Result<String, RunError>, // The answer the machine gave, or why it gave none.
+}
+
+impl fmt::Display for ExampleFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "example {}: expected {}, ", self.index + 1, self.expected)?;
+        match &self.actual {
+            Ok(actual) => write!(f, "got {}", actual),
+            Err(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Bundle {
+    /// The bundle of a built-in machine.
+    pub fn from_entry(entry: &Entry) -> Self {
+        Bundle {
+            name: entry.name.to_string(),
+            description: entry.description.to_string(),
+            alphabet: entry.alphabet.chars().collect(),
+            markers: entry.markers.to_vec(),
+            definition: (entry.build)(),
+            examples: entry
+                .examples
+                .iter()
+                .map(|&(input, output)| Example {
+                    input: input.to_string(),
+                    output: output.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    /// A machine ready to run on `tape`, started, read and shown as the bundle says.
+    pub fn machine(&self, tape: Vec<char>) -> TuringMachine {
+        let definition = self.definition.clone();
+        let mut machine = TuringMachine::new(tape, definition.rules);
+        machine.set_display_map(DisplayMap::markers(&self.markers));
+        machine.set_state(definition.start);
+        machine.set_result_strategy(definition.result);
+        machine.set_descriptions(definition.descriptions);
+        machine.set_final_states(definition.final_states);
+        machine
+    }
+
+    /// Runs every example, each for at most `max_steps` steps, and returns those that
+    /// did not give their expected answer.
+    pub fn check_examples(&self, max_steps: usize) -> Vec<ExampleFailure> {
+        let mut failures = Vec::new();
+        for (index, example) in self.examples.iter().enumerate() {
+            let mut machine = self.machine(example.input.chars().collect());
+            let actual = machine.run_bounded(max_steps).map(|_| machine.result());
+            if actual.as_ref() != Ok(&example.output) {
+                failures.push(ExampleFailure {
+                    index,
+                    expected: example.output.clone(),
+                    actual,
+                });
+            }
+        }
+        failures
+    }
+}
+
+/// Reads a bundle, a small TOML document like
+///
+/// ```text
+/// name = "increment"
+/// description = "adds one to a binary number"
+/// alphabet = "_01"
+/// blank = "_"
+/// machine = '''
+/// start Start
+/// final Done
+/// result whole_tape_trimmed
+/// Start 1 1 R Seek
+/// ...
+/// '''
+///
+/// [[example]]
+/// input = "_1011_"
+/// output = "1100"
+/// ```
+///
+/// `machine` is a rule file, see `rules::parse_machine`, as a literal multi-line string
+/// whose closing `'''` is on a line of its own. Other values are basic strings on one
+/// line. `blank` may be left out, and must be '_' if given. `markers` may name symbols
+/// that stand for others, each followed by the symbol it is shown as, e.g. `"I1 O0"`
+/// for the adder's.
+pub fn parse(text: &str) -> Result<Bundle, BundleError> {
+    let (mut name, mut description, mut alphabet) = (String::new(), String::new(), Vec::new());
+    let mut markers = Vec::new();
+    let mut machine = None;
+    let mut examples = Vec::new();
+    // The example being read, with the line of its header.
+    let mut example: Option<(usize, Option<String>, Option<String>)> = None;
+
+    let mut lines = text.lines().enumerate();
+    while let Some((index, raw)) = lines.next() {
+        let line = index + 1;
+        let error = |kind| BundleError { line, kind };
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed == "[[example]]" {
+            if let Some(previous) = example.take() {
+                examples.push(finish_example(previous)?);
+            }
+            example = Some((line, None, None));
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once('=') else {
+            return Err(error(BundleErrorKind::Syntax(trimmed.to_string())));
+        };
+        let (key, value) = (key.trim(), value.trim());
+
+        if key == "machine" && example.is_none() {
+            if value != "'''" {
+                return Err(error(BundleErrorKind::Syntax(trimmed.to_string())));
+            }
+            let mut body = String::new();
+            loop {
+                let Some((_, raw)) = lines.next() else {
+                    return Err(error(BundleErrorKind::Syntax(String::from("an unclosed '''"))));
+                };
+                if raw.trim() == "'''" {
+                    break;
+                }
+                body.push_str(raw);
+                body.push('\n');
+            }
+            let definition = rules::parse_machine(&body).map_err(|e| BundleError {
+                line: line + e.line,
+                kind: BundleErrorKind::Machine(e.kind),
+            })?;
+            machine = Some(definition);
+            continue;
+        }
+
+        let value = basic_string(value).ok_or_else(|| error(BundleErrorKind::Syntax(trimmed.to_string())))?;
+        match (&mut example, key) {
+            (Some((_, input, _)), "input") => {
+                parse_tape(&value).map_err(|e| error(BundleErrorKind::Tape(e)))?;
+                *input = Some(value);
+            }
+            (Some((_, _, output)), "output") => *output = Some(value),
+            (None, "name") => name = value,
+            (None, "description") => description = value,
+            (None, "alphabet") => alphabet = parse_tape(&value).map_err(|e| error(BundleErrorKind::Tape(e)))?,
+            (None, "markers") => {
+                markers = parse_markers(&value).ok_or_else(|| error(BundleErrorKind::Markers(value.clone())))?
+            }
+            (None, "blank") if value == "_" => {}
+            (None, "blank") => return Err(error(BundleErrorKind::Blank(value))),
+            _ => return Err(error(BundleErrorKind::UnknownKey(key.to_string()))),
+        }
+    }
+    if let Some(last) = example {
+        examples.push(finish_example(last)?);
+    }
+    let definition = machine.ok_or(BundleError {
+        line: text.lines().count().max(1),
+        kind: BundleErrorKind::MissingMachine,
+    })?;
+    Ok(Bundle {
+        name,
+        description,
+        alphabet,
+        markers,
+        definition,
+        examples,
+    })
+}
+
+/// Writes a bundle in the format `parse` reads.
+pub fn export(bundle: &Bundle) -> String {
+    let mut text = String::new();
+    writeln!(text, "name = {}", quote(&bundle.name)).unwrap();
+    writeln!(text, "description = {}", quote(&bundle.description)).unwrap();
+    writeln!(text, "alphabet = {}", quote(&bundle.alphabet.iter().collect::<String>())).unwrap();
+    writeln!(text, "blank = \"_\"").unwrap();
+    if !bundle.markers.is_empty() {
+        let pairs: Vec<String> =
+            bundle.markers.iter().map(|(marker, symbol)| format!("{}{}", marker, symbol)).collect();
+        writeln!(text, "markers = {}", quote(&pairs.join(" "))).unwrap();
+    }
+    writeln!(text, "machine = '''").unwrap();
+    text.push_str(&rules::to_machine_text(&bundle.definition));
+    writeln!(text, "'''").unwrap();
+    for example in &bundle.examples {
+        writeln!(text).unwrap();
+        writeln!(text, "[[example]]").unwrap();
+        writeln!(text, "input = {}", quote(&example.input)).unwrap();
+        writeln!(text, "output = {}", quote(&example.output)).unwrap();
+    }
+    text
+}
+
+// Reads marker pairs, each a marker and the symbol it stands for, separated by spaces,
+// e.g. `I1 O0`.
+fn parse_markers(text: &str) -> Option<Vec<(char, char)>> {
+    text.split_whitespace()
+        .map(|pair| match pair.chars().collect::<Vec<_>>()[..] {
+            [marker, symbol] => Some((marker, symbol)),
+            _ => None,
+        })
+        .collect()
+}
+
+// Checks that an example has both halves.
+fn finish_example((line, input, output): (usize, Option<String>, Option<String>)) -> Result<Example, BundleError> {
+    match (input, output) {
+        (Some(input), Some(output)) => Ok(Example { input, output }),
+        _ => Err(BundleError {
+            line,
+            kind: BundleErrorKind::IncompleteExample,
+        }),
+    }
+}
+
+// Reads a one-line TOML basic string, `"..."`, undoing `\"` and `\\`.
+fn basic_string(text: &str) -> Option<String> {
+    let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                c @ ('"' | '\\') => value.push(c),
+                _ => return None,
+            },
+            '"' => return None,
+            c => value.push(c),
+        }
+    }
+    Some(value)
+}
+
+// Writes a TOML basic string, the reverse of `basic_string`.
+fn quote(text: &str) -> String {
+    ["\"", &text.replace('\\', "\\\\").replace('"', "\\\""), "\""].concat()
+}
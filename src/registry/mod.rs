@@ -0,0 +1,143 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::rules::{self, MachineDefinition};
+use crate::tape::display::ADDER_MARKERS;
+use crate::{ResultStrategy, RuleDescriptions, RuleTable, State};
+
+pub mod bundle;
+pub mod vectors;
+
+/// A built-in machine, with what a user needs to know to run it.
+pub struct Entry {
+    pub name: &'static str,                                // What to pass to `get` or `tm run --machine`.
+    pub description: &'static str,                         // What the machine computes, in one line.
+    pub tape_format: &'static str,                         // The input tape it expects.
+    pub alphabet: &'static str,                            // Every symbol its tapes hold, blank included.
+    pub markers: &'static [(char, char)],                  // Symbols that stand for others, see `DisplayMap::markers`.
+    pub examples: &'static [(&'static str, &'static str)], // Input tapes and the answers they give.
+    pub build: fn() -> MachineDefinition,                  // Builds the machine's rules, start state and result strategy.
+}
+
+/// Every built-in machine, by name.
+pub const MACHINES: &[Entry] = &[
+    Entry {
+        name: "add",
+        description: "adds two binary numbers",
+        tape_format: "_a+b_",
+        alphabet: "_01+IO",
+        markers: &ADDER_MARKERS,
+        examples: &[("_1011+11_", "1110"), ("_0+0_", "0"), ("_11111111+1_", "100000000")],
+        build: || MachineDefinition {
+            rules: rules::adder(),
+            start: State::FindPlus,
+            result: ResultStrategy::adder(),
+            descriptions: rules::adder_descriptions(),
+            final_states: vec![State::GetLast],
+            description: None,
+        },
+    },
+    Entry {
+        name: "increment",
+        description: "adds one to a binary number",
+        tape_format: "n or _n_",
+        alphabet: "_01",
+        markers: &[],
+        examples: &[("_1011_", "1100"), ("_111_", "1000"), ("_0_", "1")],
+        build: || MachineDefinition {
+            rules: rules::increment(),
+            start: State::Named(String::from("Start")),
+            result: ResultStrategy::WholeTapeTrimmed,
+            descriptions: RuleDescriptions::new(),
+            final_states: vec![State::Named(String::from("Done"))],
+            description: None,
+        },
+    },
+    Entry {
+        name: "double",
+        description: "computes 2^n in unary, slowly, by repeated doubling",
+        tape_format: "_1…1#1_ with n ones before the #",
+        alphabet: "_1#XY",
+        markers: &[],
+        examples: &[("_#1_", "1"), ("_1#1_", "11"), ("_111#1_", "11111111")],
+        build: || MachineDefinition {
+            rules: rules::doubling(),
+            start: State::Named(String::from("Count")),
+            result: ResultStrategy::RightOfSeparator {
+                separator: '#',
+                map: vec![],
+            },
+            descriptions: RuleDescriptions::new(),
+            final_states: vec![State::Named(String::from("Done"))],
+            description: None,
+        },
+    },
+    Entry {
+        name: "palindrome",
+        description: "decides whether a word over a and b reads the same backwards",
+        tape_format: "<w> with the word w between end markers",
+        alphabet: "_ab<>",
+        markers: &[],
+        examples: &[("<abba>", "accept"), ("<aba>", "accept"), ("<ab>", "reject"), ("<>", "accept")],
+        build: || MachineDefinition {
+            rules: rules::palindrome(),
+            start: State::Named(String::from("Pick")),
+            result: ResultStrategy::AcceptStateOnly {
+                accept: vec![State::Named(String::from("Accept"))],
+            },
+            descriptions: RuleDescriptions::new(),
+            final_states: vec![State::Named(String::from("Accept")), State::Named(String::from("Reject"))],
+            description: None,
+        },
+    },
+];
+
+/// The names of the built-in machines, in the order they are listed.
+pub fn names() -> impl Iterator<Item = &'static str> {
+    MACHINES.iter().map(|entry| entry.name)
+}
+
+/// The built-in machine called `name`, if there is one.
+pub fn entry(name: &str) -> Option<&'static Entry> {
+    MACHINES.iter().find(|entry| entry.name == name)
+}
+
+/// Builds the built-in machine called `name`, if there is one.
+pub fn get(name: &str) -> Option<MachineDefinition> {
+    entry(name).map(|entry| (entry.build)())
+}
+
+/// The built-in machine whose rules are `rules`, if any, e.g. for a rule file that is a
+/// copy of one.
+pub fn identify(rules: &RuleTable) -> Option<&'static Entry> {
+    MACHINES.iter().find(|entry| (entry.build)().rules == *rules)
+}
+
+/// The names of the built-in machines closest to `name`, for "did you mean" hints:
+/// those within two edits, or that start with it. Closest first.
+pub fn suggest(name: &str) -> Vec<&'static str> {
+    let mut close: Vec<(usize, &'static str)> = MACHINES
+        .iter()
+        .map(|entry| (edit_distance(name, entry.name), entry.name))
+        .filter(|&(distance, candidate)| distance <= 2 || (!name.is_empty() && candidate.starts_with(name)))
+        .collect();
+    close.sort();
+    close.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+// Levenshtein distance between two strings, by characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = Vec::with_capacity(b.len() + 1);
+        current.push(i + 1);
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
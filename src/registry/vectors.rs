@@ -0,0 +1,218 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter;
+
+use crate::rng::SplitMix64;
+use crate::tape::input::{build_tape, LeadingZeros};
+use crate::testing::random_input;
+use crate::trace::formats::{self, Format};
+use crate::trace::json::Json;
+use crate::{RuleTable, TuringMachine};
+
+pub use crate::error::VectorsError;
+
+// Far more than any generated vector needs, so a looping rule table fails quickly.
+const MAX_STEPS: usize = 1_000_000;
+
+/// One addition with its known answer, and the steps the adder took for it when the
+/// vector was generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vector {
+    pub a: String,             // The first operand, in binary.
+    pub b: String,             // The second operand, in binary.
+    pub expected_sum: String,  // The sum, in binary, worked out without the machine.
+    pub expected_steps: usize, // The steps the adder took.
+}
+
+/// A vector the machine got wrong, see `verify`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VectorFailure {
+    Sum { index: usize, vector: Vector, found: String },      // It gave another sum.
+    Steps { index: usize, vector: Vector, found: usize },     // It gave the sum, in another number of steps.
+    DidNotHalt { index: usize, vector: Vector, limit: usize }, // It did not halt within the limit.
+}
+
+impl fmt::Display for VectorFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VectorFailure::Sum { index, vector, found } => write!(
+                f,
+                "vector {}: {}+{} gave {}, expected {}",
+                index, vector.a, vector.b, found, vector.expected_sum
+            ),
+            VectorFailure::Steps { index, vector, found } => write!(
+                f,
+                "vector {}: {}+{} took {} steps, expected {}",
+                index, vector.a, vector.b, found, vector.expected_steps
+            ),
+            VectorFailure::DidNotHalt { index, vector, limit } => {
+                write!(f, "vector {}: {}+{} did not halt within {} steps", index, vector.a, vector.b, limit)
+            }
+        }
+    }
+}
+
+/// The canonical test vectors: edge cases (zeros, single bits, operands of very
+/// different lengths, all ones), then random pairs of up to 24 bits drawn from `seed`,
+/// `count` in all. Sums are worked out digit by digit, and the steps by running the adder.
+pub fn generate(count: usize, seed: u64) -> Vec<Vector> {
+    let edge_cases = [
+        ("0", "0"),
+        ("0", "1"),
+        ("1", "0"),
+        ("1", "1"),
+        ("1", "11111111"),
+        ("11111111", "1"),
+        ("10000000", "1"),
+        ("1", "10000000"),
+        ("0", "11111111"),
+        ("11111111", "0"),
+        ("1111", "1111"),
+        ("11111111", "11111111"),
+        ("1111111111111111", "1111111111111111"),
+    ];
+    let mut rng = SplitMix64::new(seed);
+    let edge_cases = edge_cases.iter().map(|&(a, b)| (a.to_string(), b.to_string()));
+    let random = iter::repeat_with(|| {
+        let input = random_input(&mut rng, 24);
+        let (a, b) = input.split_once('+').expect("a random input has a '+'");
+        (a.to_string(), b.to_string())
+    });
+    edge_cases
+        .chain(random)
+        .take(count)
+        .map(|(a, b)| {
+            let tape = build_tape(&a, &b, LeadingZeros::Strip).expect("generated operands are binary");
+            let mut machine = TuringMachine::new(tape, crate::rules::adder());
+            let expected_steps = machine.run_bounded(MAX_STEPS).expect("the adder halts on every generated vector");
+            Vector {
+                expected_sum: binary_sum(&a, &b),
+                a,
+                b,
+                expected_steps,
+            }
+        })
+        .collect()
+}
+
+/// Runs `rules`, from `FindPlus` on each vector's `_a+b_` tape, and reports every vector
+/// it gets wrong. With `ignore_steps`, only the sums are checked, for rule tables that
+/// are meant to take a different number of steps.
+pub fn verify(rules: &RuleTable, vectors: &[Vector], ignore_steps: bool) -> Vec<VectorFailure> {
+    let mut failures = Vec::new();
+    // One machine for every vector, reset for each, so the table is copied once.
+    let mut machine = TuringMachine::new(vec!['_'], rules.clone());
+    for (index, vector) in vectors.iter().enumerate() {
+        let tape = match build_tape(&vector.a, &vector.b, LeadingZeros::Strip) {
+            Ok(tape) => tape,
+            Err(_) => unreachable!("`parse` only accepts binary operands"),
+        };
+        machine.reset_with_input(tape);
+        let vector = vector.clone();
+        let failure = match machine.run_bounded(MAX_STEPS) {
+            Err(_) => VectorFailure::DidNotHalt {
+                index,
+                vector,
+                limit: MAX_STEPS,
+            },
+            Ok(_) if machine.result() != vector.expected_sum => VectorFailure::Sum {
+                index,
+                found: machine.result(),
+                vector,
+            },
+            Ok(steps) if !ignore_steps && steps != vector.expected_steps => {
+                VectorFailure::Steps { index, vector, found: steps }
+            }
+            Ok(_) => continue,
+        };
+        failures.push(failure);
+    }
+    failures
+}
+
+/// Writes vectors in the file format `parse` reads, a `tm-vectors` envelope (see
+/// `formats`) laid out by hand to keep one vector a line:
+///
+/// ```json
+/// {
+///   "format": "tm-vectors",
+///   "version": 2,
+///   "payload": {
+///     "seed": 1,
+///     "vectors": [
+///       {"a":"1011","b":"11","expected_sum":"1110","expected_steps":62},
+///       ...
+///     ]
+///   }
+/// }
+/// ```
+pub fn export(vectors: &[Vector], seed: u64) -> String {
+    let envelope = formats::envelope(Format::Vectors, Json::Null);
+    let field = |name| envelope.get(name).map(ToString::to_string).unwrap_or_default();
+    let mut text = format!("{{\n  \"format\": {},\n  \"version\": {},\n", field("format"), field("version"));
+    text.push_str(&format!("  \"payload\": {{\n    \"seed\": {},\n    \"vectors\": [\n", seed));
+    for (index, vector) in vectors.iter().enumerate() {
+        let json = Json::object()
+            .field("a", vector.a.as_str())
+            .field("b", vector.b.as_str())
+            .field("expected_sum", vector.expected_sum.as_str())
+            .field("expected_steps", vector.expected_steps);
+        let comma = if index + 1 < vectors.len() { "," } else { "" };
+        text.push_str(&format!("      {}{}\n", json, comma));
+    }
+    text.push_str("    ]\n  }\n}\n");
+    text
+}
+
+/// Reads a vector file written by `export`, or any JSON of the same shape, including
+/// files from before the envelope.
+pub fn parse(text: &str) -> Result<Vec<Vector>, VectorsError> {
+    let json: Json = text.parse().map_err(VectorsError::Json)?;
+    let json = formats::open(Format::Vectors, &json).map_err(VectorsError::Format)?;
+    let vectors = json.get("vectors").and_then(Json::as_array);
+    let vectors = vectors.ok_or(VectorsError::Field { vector: None, field: "vectors" })?;
+    vectors
+        .iter()
+        .enumerate()
+        .map(|(index, vector)| {
+            let error = |field| VectorsError::Field { vector: Some(index), field };
+            let binary = |field| match vector.get(field).and_then(Json::as_str) {
+                Some(digits) if !digits.is_empty() && digits.chars().all(|c| c == '0' || c == '1') => {
+                    Ok(digits.to_string())
+                }
+                _ => Err(error(field)),
+            };
+            let steps = vector.get("expected_steps").and_then(Json::as_i64);
+            Ok(Vector {
+                a: binary("a")?,
+                b: binary("b")?,
+                expected_sum: binary("expected_sum")?,
+                expected_steps: steps.and_then(|steps| usize::try_from(steps).ok()).ok_or(error("expected_steps"))?,
+            })
+        })
+        .collect()
+}
+
+// The sum of two binary numbers, digit by digit, without leading zeros.
+fn binary_sum(a: &str, b: &str) -> String {
+    let (mut a, mut b) = (a.bytes().rev(), b.bytes().rev());
+    let mut digits = Vec::new();
+    let mut carry = 0;
+    loop {
+        let (x, y) = (a.next(), b.next());
+        if x.is_none() && y.is_none() && carry == 0 {
+            break;
+        }
+        let sum = carry + x.map_or(0, |x| x - b'0') + y.map_or(0, |y| y - b'0');
+        digits.push(char::from(b'0' + sum % 2));
+        carry = sum / 2;
+    }
+    let sum: String = digits.into_iter().rev().collect();
+    match sum.trim_start_matches('0') {
+        "" => String::from("0"),
+        trimmed => trimmed.to_string(),
+    }
+}
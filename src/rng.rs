@@ -0,0 +1,27 @@
+/// A small seedable pseudo-random generator (SplitMix64), so that randomized tools
+/// give the same answers for the same seed on every platform.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64, // Advanced by a fixed odd constant on every draw.
+}
+
+impl SplitMix64 {
+    /// Creates a generator; equal seeds give equal sequences.
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    /// The next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`. `bound` must not be zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
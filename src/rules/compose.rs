@@ -0,0 +1,83 @@
+use alloc::format;
+#[cfg(not(feature = "std"))]
+use hashbrown::HashSet;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+use crate::{RuleTable, State};
+
+pub use crate::error::ComposeError;
+
+/// Building larger machines out of rule tables, e.g. "the adder, then the incrementer".
+pub trait Compose: Sized {
+    /// The same table with every state renamed to `prefix` followed by its name, `Halt`
+    /// included, so it can be merged with tables that use the same names.
+    fn namespaced(&self, prefix: &str) -> Self;
+
+    /// Both tables' rules in one, with each `(from, to)` in `rewire` sending runs that
+    /// would stop in `from`, a state of this table, on into `to`, a state of `other`:
+    /// rules into a `from` that has no rules of its own now go to `to`, and a `from` with
+    /// rules gets `to`'s for the symbols it has none for, so halting there (as the adder
+    /// does in `GetLast`) carries on in `to` on the same cell. Fails if a state with rules
+    /// in one table appears in the other; states only ever moved into, like a shared
+    /// `Halt`, may.
+    fn merge(&self, other: &Self, rewire: &[(State, State)]) -> Result<Self, ComposeError>;
+}
+
+impl Compose for RuleTable {
+    fn namespaced(&self, prefix: &str) -> Self {
+        let rename = |state: &State| State::Named(format!("{}{}", prefix, state));
+        self.iter()
+            .map(|((state, read), (write, direction, next))| {
+                ((rename(state), *read), (*write, direction.clone(), rename(next)))
+            })
+            .collect()
+    }
+
+    fn merge(&self, other: &Self, rewire: &[(State, State)]) -> Result<Self, ComposeError> {
+        let (sources, states) = (source_states(self), all_states(self));
+        let (other_sources, other_states) = (source_states(other), all_states(other));
+        for &state in states.intersection(&other_states) {
+            if sources.contains(state) || other_sources.contains(state) {
+                return Err(ComposeError::Collision(state.clone()));
+            }
+        }
+        for (from, to) in rewire {
+            if !states.contains(from) {
+                return Err(ComposeError::UnknownState(from.clone()));
+            }
+            if !other_sources.contains(to) {
+                return Err(ComposeError::UnknownState(to.clone()));
+            }
+        }
+
+        let mut merged = self.clone();
+        for (from, to) in rewire {
+            if sources.contains(from) {
+                for ((state, read), action) in other {
+                    if state == to && !self.contains_key(&(from.clone(), *read)) {
+                        merged.insert((from.clone(), *read), action.clone());
+                    }
+                }
+            } else {
+                for (_, _, next) in merged.values_mut() {
+                    if next == from {
+                        *next = to.clone();
+                    }
+                }
+            }
+        }
+        merged.extend(other.iter().map(|(key, action)| (key.clone(), action.clone())));
+        Ok(merged)
+    }
+}
+
+// The states that have rules.
+fn source_states(rules: &RuleTable) -> HashSet<&State> {
+    rules.keys().map(|(state, _)| state).collect()
+}
+
+// The states that have rules or that rules move into.
+fn all_states(rules: &RuleTable) -> HashSet<&State> {
+    rules.iter().flat_map(|((state, _), (_, _, next))| [state, next]).collect()
+}
@@ -0,0 +1,106 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::rules::SortedRules;
+use crate::{RuleTable, State};
+
+/// Something suspicious about a rule table that isn't strictly an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintWarning {
+    /// A rule reads a symbol that is neither in the input alphabet nor written by any
+    /// rule that can fire, so it can never fire either.
+    UnreadableSymbol { state: State, symbol: char },
+    /// No path through the rules leads from this state to a halt.
+    HaltUnreachable(State),
+}
+
+impl LintWarning {
+    /// A short, stable identifier for the kind of warning.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LintWarning::UnreadableSymbol { .. } => "unreadable-symbol",
+            LintWarning::HaltUnreachable(_) => "halt-unreachable",
+        }
+    }
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] ", self.code())?;
+        match self {
+            LintWarning::UnreadableSymbol { state, symbol } => write!(
+                f,
+                "the rule for state {} reading '{}' can never fire: nothing puts '{}' on the tape",
+                state, symbol, symbol
+            ),
+            LintWarning::HaltUnreachable(state) => write!(f, "the machine can never halt once it is in state {}", state),
+        }
+    }
+}
+
+/// Checks a rule table against the symbols that can appear in its input.
+///
+/// A state can halt when some symbol it may read has no rule, or when a rule leads to
+/// `Halt` directly; a state that can only reach states that never halt is reported.
+/// Warnings are sorted by state name, then symbol.
+pub fn lint(rules: &RuleTable, alphabet: &[char]) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    // Every symbol that can ever be under the head: the alphabet, plus whatever rules
+    // that can fire write, until nothing new turns up.
+    let mut symbols: Vec<char> = alphabet.to_vec();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for ((_, read), &(write, _, _)) in rules {
+            if symbols.contains(read) && !symbols.contains(&write) {
+                symbols.push(write);
+                changed = true;
+            }
+        }
+    }
+
+    for (state, symbol, ..) in rules.iter_sorted() {
+        if !symbols.contains(&symbol) {
+            warnings.push(LintWarning::UnreadableSymbol {
+                state: state.clone(),
+                symbol,
+            });
+        }
+    }
+
+    // Start from the states that can halt on their own and walk the rules backwards.
+    let mut states: Vec<&State> = Vec::new();
+    for ((state, _), (_, _, next_state)) in rules {
+        for state in [state, next_state] {
+            if !states.contains(&state) {
+                states.push(state);
+            }
+        }
+    }
+    states.sort_by_cached_key(|state| state.to_string());
+
+    let mut can_halt: Vec<&State> = states
+        .iter()
+        .copied()
+        .filter(|&state| *state == State::Halt || symbols.iter().any(|&s| !rules.contains_key(&(state.clone(), s))))
+        .collect();
+    changed = true;
+    while changed {
+        changed = false;
+        for ((state, _), (_, _, next_state)) in rules {
+            if can_halt.contains(&next_state) && !can_halt.contains(&state) {
+                can_halt.push(state);
+                changed = true;
+            }
+        }
+    }
+
+    for state in states {
+        if !can_halt.contains(&state) {
+            warnings.push(LintWarning::HaltUnreachable(state.clone()));
+        }
+    }
+    warnings
+}
@@ -0,0 +1,782 @@
+use alloc::collections::{BTreeSet, VecDeque};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::rules::window::WindowTable;
+use crate::tape::input::is_cell_symbol;
+use crate::trace::formats::{self, Format};
+use crate::trace::json::Json;
+use crate::{Direction, ResultStrategy, RuleDescriptions, RuleTable, State};
+
+pub use crate::error::{RuleError, RuleJsonError, RuleParseError, RuleParseErrorKind};
+pub use crate::fingerprint::Fingerprint;
+
+pub mod compose;
+pub mod lint;
+pub mod mutate;
+pub mod turingmachine_io;
+pub mod window;
+
+/// A rule written out flat: (current state, read symbol, write symbol, move, next state).
+pub type Rule = (State, char, char, Direction, State);
+
+/// Assembles a rule table from flat rules, rejecting two rules for the same (state, symbol).
+pub fn from_rules(rules: impl IntoIterator<Item = Rule>) -> Result<RuleTable, RuleError> {
+    let mut table = RuleTable::new();
+    for (state, read, write, direction, next_state) in rules {
+        if table.contains_key(&(state.clone(), read)) {
+            return Err(RuleError::Duplicate(state, read));
+        }
+        table.insert((state, read), (write, direction, next_state));
+    }
+    Ok(table)
+}
+
+/// A machine as read from a rule file: its rules, where it starts and how to read its answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MachineDefinition {
+    pub rules: RuleTable,               // Transition rules.
+    pub start: State,                   // The start state, `FindPlus` unless the file says otherwise.
+    pub result: ResultStrategy,         // How to read the answer, the adder's way unless the file says otherwise.
+    pub descriptions: RuleDescriptions, // Explanations given after a rule's fields, as in `R 1 1 R R # keep going`.
+    pub final_states: Vec<State>,       // States where halting means finishing, `GetLast` unless the file says otherwise.
+    pub description: Option<String>,    // What the machine does, if the file says.
+}
+
+/// Reads a rule table from text, see `parse_machine`. Any `start`, `final` or `result` lines and
+/// descriptions are checked but dropped.
+pub fn parse_rules(text: &str) -> Result<RuleTable, RuleParseError> {
+    parse_machine(text).map(|definition| definition.rules)
+}
+
+/// Reads a machine from text with one rule per line, written as five whitespace
+/// separated fields: `state read write move next`, e.g. `FindPlus 1 1 R FindPlus`.
+/// The move is `L`, `R` or `S`, or any other form `Direction` parses. Blank lines and lines starting with `#` are ignored.
+/// A rule may be followed by `# description`, explaining it for `Verbosity::Explain`.
+///
+/// Three keywords may start a line instead of a state:
+/// - `start STATE` sets the start state.
+/// - `final STATE ...` sets the states where halting means the machine finished, see `HaltReason`.
+/// - `result STRATEGY` sets how the answer is read, one of
+///   `right_of_separator SEP [FROM=TO ...]`, `whole_tape_trimmed` or `accept_state_only STATE ...`.
+pub fn parse_machine(text: &str) -> Result<MachineDefinition, RuleParseError> {
+    let mut definition = MachineDefinition {
+        rules: RuleTable::new(),
+        start: State::FindPlus,
+        result: ResultStrategy::adder(),
+        descriptions: RuleDescriptions::new(),
+        final_states: vec![State::GetLast],
+        description: None,
+    };
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |kind| RuleParseError { line: index + 1, kind };
+        let symbol = |field: &str| {
+            let mut chars = field.chars();
+            match (chars.next(), chars.next()) {
+                (Some(symbol), None) if is_cell_symbol(symbol) => Ok(symbol),
+                _ => Err(error(RuleParseErrorKind::Symbol(field.to_string()))),
+            }
+        };
+
+        let mut fields: Vec<&str> = line.split_whitespace().collect();
+        match fields[..] {
+            ["start", state] => {
+                definition.start = State::from_name(state);
+                continue;
+            }
+            ["final", ref states @ ..] if !states.is_empty() => {
+                definition.final_states = states.iter().map(|state| State::from_name(state)).collect();
+                continue;
+            }
+            ["result", ref strategy @ ..] => {
+                definition.result = parse_strategy(strategy).map_err(|kind| match kind {
+                    RuleParseErrorKind::Directive(_) => error(RuleParseErrorKind::Directive(line.to_string())),
+                    kind => error(kind),
+                })?;
+                continue;
+            }
+            _ => {}
+        }
+
+        // A `#` field after the rule starts its description, which runs to the end of the line.
+        let mut description = None;
+        if let Some(hash) = fields.get(5).filter(|field| field.starts_with('#')) {
+            let offset = hash.as_ptr() as usize - line.as_ptr() as usize;
+            description = Some(line[offset + 1..].trim().to_string());
+            fields.truncate(5);
+        }
+        let [state, read, write, direction, next_state] = fields[..] else {
+            return Err(error(RuleParseErrorKind::FieldCount(fields.len())));
+        };
+        let (read, write) = (symbol(read)?, symbol(write)?);
+        let direction: Direction = direction.parse().map_err(|e| error(RuleParseErrorKind::Direction(e)))?;
+
+        let state = State::from_name(state);
+        if definition.rules.contains_key(&(state.clone(), read)) {
+            return Err(error(RuleParseErrorKind::Duplicate(state, read)));
+        }
+        if let Some(description) = description {
+            definition.descriptions.insert((state.clone(), read), description);
+        }
+        definition.rules.insert((state, read), (write, direction, State::from_name(next_state)));
+    }
+    Ok(definition)
+}
+
+// Reads the fields of a `result` line after the keyword, see `parse_machine`.
+fn parse_strategy(fields: &[&str]) -> Result<ResultStrategy, RuleParseErrorKind> {
+    let directive = || RuleParseErrorKind::Directive(format!("result {}", fields.join(" ")));
+    let symbol = |field: &str| {
+        let mut chars = field.chars();
+        match (chars.next(), chars.next()) {
+            (Some(symbol), None) if is_cell_symbol(symbol) => Ok(symbol),
+            _ => Err(RuleParseErrorKind::Symbol(field.to_string())),
+        }
+    };
+    match fields {
+        ["right_of_separator", separator, ref map @ ..] => {
+            let mut pairs = Vec::new();
+            for pair in map {
+                let (from, to) = pair.split_once('=').ok_or_else(directive)?;
+                pairs.push((symbol(from)?, symbol(to)?));
+            }
+            Ok(ResultStrategy::RightOfSeparator {
+                separator: symbol(separator)?,
+                map: pairs,
+            })
+        }
+        ["whole_tape_trimmed"] => Ok(ResultStrategy::WholeTapeTrimmed),
+        ["accept_state_only", ref states @ ..] if !states.is_empty() => Ok(ResultStrategy::AcceptStateOnly {
+            accept: states.iter().map(|state| State::from_name(state)).collect(),
+        }),
+        _ => Err(directive()),
+    }
+}
+
+/// The states the machine can enter when started in `initial`, including `initial`.
+///
+/// Any symbol may be under the head, since the input is arbitrary, so every rule of a
+/// reachable state counts as able to fire.
+pub fn reachable_states(rules: &RuleTable, initial: &State) -> BTreeSet<State> {
+    let mut reached = BTreeSet::from([initial.clone()]);
+    let mut queue = VecDeque::from([initial.clone()]);
+    while let Some(state) = queue.pop_front() {
+        for (_, (_, _, next_state)) in rules.iter().filter(|((from, _), _)| *from == state) {
+            if reached.insert(next_state.clone()) {
+                queue.push_back(next_state.clone());
+            }
+        }
+    }
+    reached
+}
+
+/// Returns only the rules that can fire when the machine starts in `initial`, i.e. those
+/// of the states in `reachable_states`. The machine behaves the same on every input.
+pub fn prune_rules(rules: &RuleTable, initial: &State) -> RuleTable {
+    let reachable = reachable_states(rules, initial);
+    rules
+        .iter()
+        .filter(|((state, _), _)| reachable.contains(state))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// The `(state, symbol)` pairs over `states` and `alphabet` that have no rule, in the
+/// order given. `Halt` is skipped, as the machine stops there whatever it reads.
+pub fn missing_rules(rules: &RuleTable, alphabet: &[char], states: &[State]) -> Vec<(State, char)> {
+    states
+        .iter()
+        .filter(|&state| *state != State::Halt)
+        .flat_map(|state| alphabet.iter().map(move |&symbol| (state.clone(), symbol)))
+        .filter(|key| !rules.contains_key(key))
+        .collect()
+}
+
+/// Whether the rules are a total function: every one of `states` but `Halt` has a rule
+/// for every symbol of `alphabet`, see `missing_rules` for the gaps. Unlike `lint`, this
+/// asks nothing about which rules can fire. The adder is deliberately not total: it halts
+/// by finding no rule, in `GetLast` once the first operand is used up, and has no rules
+/// for symbols a state never meets, such as `FindPlus` reading a marker.
+pub fn is_total(rules: &RuleTable, alphabet: &[char], states: &[State]) -> bool {
+    missing_rules(rules, alphabet, states).is_empty()
+}
+
+/// A canonical order for the rules of a table, which the `HashMap` doesn't keep. Every
+/// export and the fingerprint walk the rules in this order, so changing it is a format
+/// break; tests/golden/adder_rules.txt pins it.
+pub trait SortedRules {
+    /// The rules as `(state, read, write, move, next state)`, ordered by the state's name
+    /// (as `Display` prints it), then by the symbol read.
+    fn iter_sorted(&self) -> vec::IntoIter<(&State, char, char, &Direction, &State)>;
+}
+
+impl SortedRules for RuleTable {
+    fn iter_sorted(&self) -> vec::IntoIter<(&State, char, char, &Direction, &State)> {
+        let mut sorted: Vec<_> = self
+            .iter()
+            .map(|((state, read), (write, direction, next_state))| (state, *read, *write, direction, next_state))
+            .collect();
+        sorted.sort_by_cached_key(|&(state, read, ..)| (state.to_string(), read));
+        sorted.into_iter()
+    }
+}
+
+/// Writes the rule table in the format `parse_rules` reads.
+pub fn to_text(rules: &RuleTable) -> String {
+    to_text_described(rules, &RuleDescriptions::new())
+}
+
+/// Writes the rule table like `to_text`, following each described rule with `# description`
+/// as `parse_machine` reads it.
+pub fn to_text_described(rules: &RuleTable, descriptions: &RuleDescriptions) -> String {
+    let mut text = String::new();
+    for (state, read, write, direction, next_state) in rules.iter_sorted() {
+        write!(text, "{} {} {} {} {}", state, read, write, direction, next_state).unwrap();
+        if let Some(description) = descriptions.get(&(state.clone(), read)) {
+            write!(text, " # {}", description).unwrap();
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Writes a whole machine in the format `parse_machine` reads: its description as a
+/// comment, its `start`, `final` and `result` lines, then its described rules.
+pub fn to_machine_text(definition: &MachineDefinition) -> String {
+    let mut text = String::new();
+    if let Some(description) = &definition.description {
+        for line in description.lines() {
+            writeln!(text, "# {}", line).unwrap();
+        }
+    }
+    writeln!(text, "start {}", definition.start).unwrap();
+    if !definition.final_states.is_empty() {
+        write!(text, "final").unwrap();
+        for state in &definition.final_states {
+            write!(text, " {}", state).unwrap();
+        }
+        text.push('\n');
+    }
+    writeln!(text, "result {}", definition.result).unwrap();
+    text.push_str(&to_text_described(&definition.rules, &definition.descriptions));
+    text
+}
+
+/// Renders the rule table as a Graphviz graph, one edge per rule labelled `read→write,move`.
+pub fn to_dot(rules: &RuleTable) -> String {
+    to_dot_described(rules, &RuleDescriptions::new())
+}
+
+/// Renders the rule table like `to_dot`, giving each described rule's edge its description as a tooltip.
+pub fn to_dot_described(rules: &RuleTable, descriptions: &RuleDescriptions) -> String {
+    let mut dot = String::from("digraph turing {\n    rankdir=LR;\n");
+    for (state, read, write, direction, next_state) in rules.iter_sorted() {
+        let label = format!("{}→{},{}", read, write, direction);
+        write!(
+            dot,
+            "    \"{}\" -> \"{}\" [label=\"{}\"",
+            escape(&state.to_string()),
+            escape(&next_state.to_string()),
+            escape(&label)
+        )
+        .unwrap();
+        if let Some(description) = descriptions.get(&(state.clone(), read)) {
+            write!(dot, " tooltip=\"{}\"", escape(description)).unwrap();
+        }
+        dot.push_str("];\n");
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Renders a whole machine like `to_dot_described`, with its description, if any, as the
+/// graph's label.
+pub fn to_machine_dot(definition: &MachineDefinition) -> String {
+    let dot = to_dot_described(&definition.rules, &definition.descriptions);
+    match &definition.description {
+        Some(description) => {
+            let label = format!("rankdir=LR;\n    label=\"{}\";\n", escape(description));
+            dot.replacen("rankdir=LR;\n", &label, 1)
+        }
+        None => dot,
+    }
+}
+
+/// Writes a whole machine in the JSON rule format `from_json` reads, in a `tm-machine`
+/// envelope (see `formats`) around:
+///
+/// ```json
+/// {"description": "adds two binary numbers", "start": "FindPlus", "final": ["GetLast"],
+///  "result": "right_of_separator + I=1 O=0", "rules": [{"state": "FindPlus", "read": "_",
+///  "write": "_", "move": "R", "next": "FindPlus", "comment": "skip the leading blank"}, ...]}
+/// ```
+///
+/// `description` and each rule's `comment`, which is its description, are left out when
+/// there are none. Rules are sorted as in `to_text`.
+pub fn to_json(definition: &MachineDefinition) -> Json {
+    let rules: Vec<Json> = definition
+        .rules
+        .iter_sorted()
+        .map(|(state, read, write, direction, next_state)| {
+            let rule = Json::object()
+                .field("state", state.to_string())
+                .field("read", read.to_string())
+                .field("write", write.to_string())
+                .field("move", direction.to_string())
+                .field("next", next_state.to_string());
+            match definition.descriptions.get(&(state.clone(), read)) {
+                Some(comment) => rule.field("comment", comment.as_str()),
+                None => rule,
+            }
+        })
+        .collect();
+    let json = match &definition.description {
+        Some(description) => Json::object().field("description", description.as_str()),
+        None => Json::object(),
+    };
+    let final_states: Vec<Json> = definition.final_states.iter().map(|state| Json::from(state.to_string())).collect();
+    let payload = json
+        .field("start", definition.start.to_string())
+        .field("final", final_states)
+        .field("result", definition.result.to_string())
+        .field("rules", rules);
+    formats::envelope(Format::Machine, payload)
+}
+
+/// Reads a machine in the JSON rule format `to_json` writes. Only `rules` is required:
+/// `start`, `final` and `result` default as in `parse_machine`, `description` and
+/// `comment` may be left out or null, and fields it doesn't know are ignored. Files from
+/// before the envelope load too, see `formats::open`.
+pub fn from_json(text: &str) -> Result<MachineDefinition, RuleJsonError> {
+    let json: Json = text.parse().map_err(RuleJsonError::Json)?;
+    let json = formats::open(Format::Machine, &json).map_err(RuleJsonError::Format)?;
+    let top = |field| RuleJsonError::Field { rule: None, field };
+
+    let mut definition = MachineDefinition {
+        rules: RuleTable::new(),
+        start: State::FindPlus,
+        result: ResultStrategy::adder(),
+        descriptions: RuleDescriptions::new(),
+        final_states: vec![State::GetLast],
+        description: optional(json, "description").map_err(top)?.map(String::from),
+    };
+    if let Some(start) = optional(json, "start").map_err(top)? {
+        definition.start = State::from_name(start);
+    }
+    if let Some(result) = optional(json, "result").map_err(top)? {
+        let fields: Vec<&str> = result.split_whitespace().collect();
+        definition.result = parse_strategy(&fields).map_err(RuleJsonError::Result)?;
+    }
+    match json.get("final") {
+        None | Some(Json::Null) => {}
+        Some(states) => {
+            let states = states.as_array().ok_or(top("final"))?;
+            let states = states.iter().map(|state| state.as_str().map(State::from_name).ok_or(top("final")));
+            definition.final_states = states.collect::<Result<_, _>>()?;
+        }
+    }
+
+    let rules = json.get("rules").and_then(Json::as_array).ok_or(top("rules"))?;
+    for (index, rule) in rules.iter().enumerate() {
+        let error = |field| RuleJsonError::Field { rule: Some(index), field };
+        let text = |field| rule.get(field).and_then(Json::as_str).ok_or(error(field));
+        let symbol = |field| {
+            let mut chars = text(field)?.chars();
+            match (chars.next(), chars.next()) {
+                (Some(symbol), None) if is_cell_symbol(symbol) => Ok(symbol),
+                _ => Err(RuleJsonError::Rule {
+                    rule: index,
+                    kind: RuleParseErrorKind::Symbol(text(field)?.to_string()),
+                }),
+            }
+        };
+        let state = State::from_name(text("state")?);
+        let read = symbol("read")?;
+        let write = symbol("write")?;
+        let direction: Direction = text("move")?.parse().map_err(|error| RuleJsonError::Rule {
+            rule: index,
+            kind: RuleParseErrorKind::Direction(error),
+        })?;
+        let next_state = State::from_name(text("next")?);
+        if definition.rules.contains_key(&(state.clone(), read)) {
+            return Err(RuleJsonError::Rule {
+                rule: index,
+                kind: RuleParseErrorKind::Duplicate(state, read),
+            });
+        }
+        if let Some(comment) = optional(rule, "comment").map_err(error)? {
+            definition.descriptions.insert((state.clone(), read), comment.to_string());
+        }
+        definition.rules.insert((state, read), (write, direction, next_state));
+    }
+    Ok(definition)
+}
+
+// An optional string field of `object`, `None` if missing or null, or the field's name
+// if it holds something else.
+fn optional<'a>(object: &'a Json, field: &'static str) -> Result<Option<&'a str>, &'static str> {
+    match object.get(field) {
+        None | Some(Json::Null) => Ok(None),
+        Some(value) => value.as_str().map(Some).ok_or(field),
+    }
+}
+
+/// Writes the rule table in SMT-LIB 2.6, for proving properties of a machine in a solver:
+/// datatypes for its states, its alphabet (every symbol a rule reads or writes, plus the
+/// blank) and its moves, and the transition function `delta` from a state and a symbol
+/// to an `Action`, which is `(action write move next)` or `stuck` where no rule applies.
+///
+/// States keep their names where those are plain identifiers, and are quoted as `|...|`
+/// otherwise. A symbol `c` is the quoted `|'c'|`, with `|` and `\` spelled `%7C` and `%5C`
+/// (and `%` as `%25`) since quoted symbols can't hold them. Everything is sorted, so a
+/// table always gives the same text.
+pub fn to_smtlib(rules: &RuleTable) -> String {
+    let mut states = BTreeSet::new();
+    let mut alphabet = BTreeSet::from(['_']);
+    for ((state, read), (write, _, next_state)) in rules {
+        states.insert(state.to_string());
+        states.insert(next_state.to_string());
+        alphabet.extend([*read, *write]);
+    }
+    let constructors = |names: Vec<String>| names.iter().map(|name| format!("({})", name)).collect::<Vec<_>>().join(" ");
+
+    let mut smt = String::from("; The transition function of a Turing machine.\n");
+    writeln!(smt, "(declare-datatype State ({}))", constructors(states.iter().map(|s| smt_state(s)).collect())).unwrap();
+    writeln!(smt, "(declare-datatype Symbol ({}))", constructors(alphabet.iter().map(|&c| smt_symbol(c)).collect())).unwrap();
+    writeln!(smt, "(declare-datatype Move ((L) (R) (S)))").unwrap();
+    writeln!(smt, "(declare-datatype Action ((action (write Symbol) (move Move) (next State)) (stuck)))").unwrap();
+    writeln!(smt, "(define-fun delta ((state State) (read Symbol)) Action").unwrap();
+    let rules: Vec<_> = rules.iter_sorted().collect();
+    for &(state, read, write, direction, next_state) in &rules {
+        writeln!(
+            smt,
+            "  (ite (and (= state {}) (= read {})) (action {} {} {})",
+            smt_state(&state.to_string()),
+            smt_symbol(read),
+            smt_symbol(write),
+            direction,
+            smt_state(&next_state.to_string())
+        )
+        .unwrap();
+    }
+    writeln!(smt, "  stuck{})", ")".repeat(rules.len())).unwrap();
+    smt
+}
+
+// A state's name as an SMT-LIB symbol, quoted unless it is a plain identifier.
+fn smt_state(name: &str) -> String {
+    // SMT-LIB's own words, and the names `to_smtlib` declares.
+    const RESERVED: [&str; 21] = [
+        "as", "let", "exists", "forall", "match", "par", "and", "or", "not", "ite", "true", "false", "State",
+        "Symbol", "Move", "Action", "action", "stuck", "L", "R", "S",
+    ];
+    let plain = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !RESERVED.contains(&name);
+    if plain {
+        name.to_string()
+    } else {
+        format!("|{}|", smt_quoted(name))
+    }
+}
+
+// A symbol as an SMT-LIB constant, `|'c'|`.
+fn smt_symbol(symbol: char) -> String {
+    format!("|'{}'|", smt_quoted(symbol.encode_utf8(&mut [0; 4])))
+}
+
+// Spells out what a quoted SMT-LIB symbol can't hold.
+fn smt_quoted(text: &str) -> String {
+    text.replace('%', "%25").replace('|', "%7C").replace('\\', "%5C")
+}
+
+// Escapes a DOT string literal.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// The symbols that can appear on the adder's tape: its input plus the `I`/`O` markers it writes.
+pub const ADDER_ALPHABET: [char; 6] = ['_', '0', '1', '+', 'I', 'O'];
+
+/// Builds the transition rules for adding the two binary numbers on either side of a '+'.
+///
+/// The sum is written right to left as `I`/`O` markers, and the markers always form one
+/// contiguous run at the right end of the tape (`invariant::MarkersAreSuffix`): each new
+/// one goes just left of the run, and a carry only turns unmarked digits or `+` cells
+/// further left. So `AddZero` and `AddOne` stop at the first marker or blank they meet
+/// and never walk over the digits already added. They take the same path, so a `1` in the
+/// first operand costs as many steps as a `0` unless it carries.
+pub fn adder() -> RuleTable {
+    let mut rules = RuleTable::new();
+
+    /*
+     * The rules below are defined as follows:
+     * *IF* I am currently in a state X, looking at a cell of valy Y, then write Z in said cell,
+     * Move in direction A, setting my state to state B. B could be X or any other state.
+     * */
+
+    //skips over the first empty cell
+    rules.insert((State::FindPlus, '_'), ('_', Direction::Right, State::FindPlus));
+    //keeps moving until we find a plus
+    rules.insert((State::FindPlus, '1'), ('1', Direction::Right, State::FindPlus));
+    rules.insert((State::FindPlus, '0'), ('0', Direction::Right, State::FindPlus));
+    //turns back to get the last number
+    rules.insert((State::FindPlus, '+'), ('+', Direction::Left, State::GetLast));
+    //if previous number is 0, add zer0 to the second number, be sure to delete the number
+    rules.insert((State::GetLast, '0'), ('+', Direction::Right, State::AddZero));
+    //move all the way to the right, ignoring 1, 0 and + symbols
+    rules.insert((State::AddZero, '1'), ('1', Direction::Right, State::AddZero));
+    rules.insert((State::AddZero, '0'), ('0', Direction::Right, State::AddZero));
+    rules.insert((State::AddZero, '+'), ('+', Direction::Right, State::AddZero));
+    //stop at a _, I or O, and turn to the number on our left
+    rules.insert((State::AddZero, 'I'), ('I', Direction::Left, State::AddDigitZero));
+    rules.insert((State::AddZero, 'O'), ('O', Direction::Left, State::AddDigitZero));
+    rules.insert((State::AddZero, '_'), ('_', Direction::Left, State::AddDigitZero));
+    //adds a zero to the current digit, and moves all the way back to the start of the first number
+    rules.insert((State::AddDigitZero, '1'), ('I', Direction::Left, State::BackToStart));
+    rules.insert((State::AddDigitZero, '0'), ('O', Direction::Left, State::BackToStart));
+    rules.insert((State::AddDigitZero, '+'), ('O', Direction::Left, State::BackToStart));
+
+    //carries a 1 to the right and deletes the current digit
+    rules.insert((State::GetLast, '1'), ('+', Direction::Right, State::AddOne));
+    rules.insert((State::AddOne, '1'), ('1', Direction::Right, State::AddOne));
+    rules.insert((State::AddOne, '0'), ('0', Direction::Right, State::AddOne));
+    rules.insert((State::AddOne, '+'), ('+', Direction::Right, State::AddOne));
+    rules.insert((State::AddOne, '_'), ('_', Direction::Left, State::AddDigitOne));
+    rules.insert((State::AddOne, 'I'), ('I', Direction::Left, State::AddDigitOne));
+    rules.insert((State::AddOne, 'O'), ('O', Direction::Left, State::AddDigitOne));
+   
+    //adds a zero to the last digit of the second number and moves back to plus
+    rules.insert((State::AddDigitOne, '1'), ('O', Direction::Left, State::Carry));
+    rules.insert((State::AddDigitOne, '0'), ('I', Direction::Left, State::BackToStart));
+    rules.insert((State::AddDigitOne, '+'), ('I', Direction::Left, State::BackToStart));
+    
+    rules.insert((State::Carry, '0'), ('1', Direction::Left, State::BackToStart));
+    rules.insert((State::Carry, '1'), ('0', Direction::Left, State::Carry));
+    rules.insert((State::Carry, '+'), ('1', Direction::Left, State::BackToStart));
+
+    //moves all the way back to the start of the tape
+    rules.insert((State::BackToStart, '0'), ('0', Direction::Left, State::BackToStart));
+    rules.insert((State::BackToStart, '1'), ('1', Direction::Left, State::BackToStart));
+    rules.insert((State::BackToStart, '+'), ('+', Direction::Left, State::BackToStart));
+
+    //once we reach the start, we pretend to start again
+    rules.insert((State::BackToStart, '_'), ('_', Direction::Right, State::FindPlus));
+
+    rules
+}
+
+/// Explanations of the adder's rules, in words, for `Verbosity::Explain`.
+pub fn adder_descriptions() -> RuleDescriptions {
+    let descriptions = [
+        (State::FindPlus, '_', "skip the blank before the first number"),
+        (State::FindPlus, '1', "keep moving right until we find the plus"),
+        (State::FindPlus, '0', "keep moving right until we find the plus"),
+        (State::FindPlus, '+', "found the plus, turn back to the last digit of the first number"),
+        (State::GetLast, '0', "erase it and remember we must add zero"),
+        (State::GetLast, '1', "erase it and remember we must add one"),
+        (State::AddZero, '1', "move right over the second number"),
+        (State::AddZero, '0', "move right over the second number"),
+        (State::AddZero, '+', "move right over the second number"),
+        (State::AddZero, 'I', "stop at the digits already added and turn to the next one"),
+        (State::AddZero, 'O', "stop at the digits already added and turn to the next one"),
+        (State::AddZero, '_', "stop at the end of the tape and turn to the last digit"),
+        (State::AddDigitZero, '1', "add zero to this 1, marking it done as I"),
+        (State::AddDigitZero, '0', "add zero to this 0, marking it done as O"),
+        (State::AddDigitZero, '+', "the second number has run out, write a done 0 (O)"),
+        (State::AddOne, '1', "move right over the second number"),
+        (State::AddOne, '0', "move right over the second number"),
+        (State::AddOne, '+', "move right over the second number"),
+        (State::AddOne, '_', "stop at the end of the tape and turn to the last digit"),
+        (State::AddOne, 'I', "stop at the digits already added and turn to the next one"),
+        (State::AddOne, 'O', "stop at the digits already added and turn to the next one"),
+        (State::AddDigitOne, '1', "1 plus one is 10: write a done 0 (O) and carry one to the left"),
+        (State::AddDigitOne, '0', "0 plus one is 1: write a done 1 (I)"),
+        (State::AddDigitOne, '+', "the second number has run out, write a done 1 (I)"),
+        (State::Carry, '0', "absorb the carry, turning 0 into 1"),
+        (State::Carry, '1', "1 plus the carry is 10: write 0 and keep carrying"),
+        (State::Carry, '+', "carry into a new digit in place of the plus"),
+        (State::BackToStart, '0', "move back to the start of the tape"),
+        (State::BackToStart, '1', "move back to the start of the tape"),
+        (State::BackToStart, '+', "move back to the start of the tape"),
+        (State::BackToStart, '_', "back at the start, look for the plus again"),
+    ];
+    descriptions
+        .into_iter()
+        .map(|(state, read, description)| ((state, read), String::from(description)))
+        .collect()
+}
+
+/// Builds an adder whose rules see two cells, the one under the head and its right
+/// neighbour. Seeing the `+` ahead lets it take the first number's last digit without
+/// stepping back, find the second number's next digit without stepping back, and stop
+/// its way back at the next digit to take instead of walking to the start of the tape,
+/// so it needs neither `GetLast`, `AddDigitZero`/`AddDigitOne` nor a return trip to
+/// `FindPlus`. Starts in `FindPlus` on the classic adder's tape and halts in `Halt`.
+pub fn window_adder() -> WindowTable {
+    let rule = |state: State, read: [char; 2], write: [char; 2], direction: Direction, next_state: State| {
+        (state, read.iter().collect(), write.iter().collect(), direction, next_state)
+    };
+    let mut rules = Vec::new();
+
+    //walk right over the first number until the plus is next, then take its last digit
+    for x in ['_', '0', '1'] {
+        for y in ['0', '1'] {
+            rules.push(rule(State::FindPlus, [x, y], [x, y], Direction::Right, State::FindPlus));
+        }
+    }
+    //on the digit before the plus: erase it and carry it right; with no digit left, done
+    let take = |state: State| {
+        [
+            rule(state.clone(), ['0', '+'], ['+', '+'], Direction::Right, State::AddZero),
+            rule(state.clone(), ['1', '+'], ['+', '+'], Direction::Right, State::AddOne),
+            rule(state, ['_', '+'], ['_', '+'], Direction::Stay, State::Halt),
+        ]
+    };
+    rules.extend(take(State::FindPlus));
+
+    //move right until the next cell holds a blank or a digit already added, then add here
+    for x in ['0', '1', '+'] {
+        for y in ['0', '1', '+'] {
+            rules.push(rule(State::AddZero, [x, y], [x, y], Direction::Right, State::AddZero));
+            rules.push(rule(State::AddOne, [x, y], [x, y], Direction::Right, State::AddOne));
+        }
+        for y in ['_', 'I', 'O'] {
+            let zero = if x == '1' { 'I' } else { 'O' };
+            rules.push(rule(State::AddZero, [x, y], [zero, y], Direction::Left, State::BackToStart));
+            rules.push(match x {
+                '1' => rule(State::AddOne, [x, y], ['O', y], Direction::Left, State::Carry),
+                _ => rule(State::AddOne, [x, y], ['I', y], Direction::Left, State::BackToStart),
+            });
+        }
+    }
+
+    //ripple the carry left, whatever is next to it
+    for y in ADDER_ALPHABET {
+        rules.push(rule(State::Carry, ['0', y], ['1', y], Direction::Left, State::BackToStart));
+        rules.push(rule(State::Carry, ['1', y], ['0', y], Direction::Left, State::Carry));
+        rules.push(rule(State::Carry, ['+', y], ['1', y], Direction::Left, State::BackToStart));
+    }
+
+    //walk back left until the plus is next to a digit of the first number (or the blank)
+    for x in ['0', '1', '+'] {
+        for y in ADDER_ALPHABET {
+            if x == '+' || y != '+' {
+                rules.push(rule(State::BackToStart, [x, y], [x, y], Direction::Left, State::BackToStart));
+            }
+        }
+    }
+    rules.extend(take(State::BackToStart));
+
+    WindowTable::new(2, rules).expect("the window adder has one rule per (state, window)")
+}
+
+/// Builds the transition rules for adding one to a binary number, starting in `Start`
+/// on the number or on the blank before it. The answer is the whole tape.
+pub fn increment() -> RuleTable {
+    let state = |name: &str| State::Named(String::from(name));
+    let mut rules = RuleTable::new();
+
+    //step onto the number, then walk right to the blank after it
+    rules.insert((state("Start"), '_'), ('_', Direction::Right, state("Seek")));
+    rules.insert((state("Start"), '0'), ('0', Direction::Right, state("Seek")));
+    rules.insert((state("Start"), '1'), ('1', Direction::Right, state("Seek")));
+    rules.insert((state("Seek"), '0'), ('0', Direction::Right, state("Seek")));
+    rules.insert((state("Seek"), '1'), ('1', Direction::Right, state("Seek")));
+    rules.insert((state("Seek"), '_'), ('_', Direction::Left, state("Increment")));
+
+    //turn trailing ones into zeros until a zero (or the blank before the number) takes the one
+    rules.insert((state("Increment"), '1'), ('0', Direction::Left, state("Increment")));
+    rules.insert((state("Increment"), '0'), ('1', Direction::Left, state("Done")));
+    rules.insert((state("Increment"), '_'), ('1', Direction::Left, state("Done")));
+
+    rules
+}
+
+/// Rules for a linear bounded automaton that decides whether a word over `a` and `b` is a
+/// palindrome, on the tape `<word>` (see `bounded::machine`), starting in `Pick` on the `<`.
+/// It halts in `Accept` or `Reject`.
+///
+/// Each round blanks the first letter left, walks to the last one, rejects if the two
+/// differ and blanks it too, then walks back; the markers tell it where the word ends.
+pub fn palindrome() -> RuleTable {
+    let state = |name: &str| State::Named(String::from(name));
+    let mut rules = RuleTable::new();
+
+    //blank the first letter left, remembering it, or accept when none is left
+    rules.insert((state("Pick"), '<'), ('<', Direction::Right, state("Pick")));
+    rules.insert((state("Pick"), 'a'), ('_', Direction::Right, state("HaveA")));
+    rules.insert((state("Pick"), 'b'), ('_', Direction::Right, state("HaveB")));
+    rules.insert((state("Pick"), '_'), ('_', Direction::Stay, state("Accept")));
+    rules.insert((state("Pick"), '>'), ('>', Direction::Stay, state("Accept")));
+
+    //walk to the end of what is left of the word
+    for (have, check) in [("HaveA", "CheckA"), ("HaveB", "CheckB")] {
+        rules.insert((state(have), 'a'), ('a', Direction::Right, state(have)));
+        rules.insert((state(have), 'b'), ('b', Direction::Right, state(have)));
+        rules.insert((state(have), '_'), ('_', Direction::Left, state(check)));
+        rules.insert((state(have), '>'), ('>', Direction::Left, state(check)));
+    }
+
+    //the last letter must match the first; a blank means the first was the middle one
+    rules.insert((state("CheckA"), 'a'), ('_', Direction::Left, state("Back")));
+    rules.insert((state("CheckA"), 'b'), ('b', Direction::Stay, state("Reject")));
+    rules.insert((state("CheckA"), '_'), ('_', Direction::Stay, state("Accept")));
+    rules.insert((state("CheckB"), 'b'), ('_', Direction::Left, state("Back")));
+    rules.insert((state("CheckB"), 'a'), ('a', Direction::Stay, state("Reject")));
+    rules.insert((state("CheckB"), '_'), ('_', Direction::Stay, state("Accept")));
+
+    //walk back to the blank left of the word
+    rules.insert((state("Back"), 'a'), ('a', Direction::Left, state("Back")));
+    rules.insert((state("Back"), 'b'), ('b', Direction::Left, state("Back")));
+    rules.insert((state("Back"), '_'), ('_', Direction::Right, state("Pick")));
+
+    rules
+}
+
+/// Builds the transition rules for computing `2^n` in unary by doubling a block of ones
+/// `n` times, starting in `Count` on a tape `_1…1#1_` with `n` ones before the `#`, see
+/// `input::doubling_tape`. The answer is the ones right of the `#`.
+///
+/// Every doubling marks the block's ones as `X` one at a time, walking to the end of the
+/// block for each to append a `Y`, so it takes time quadratic in the block and the whole
+/// run about `2.7 * 4^n` steps: a machine that is slow on purpose, for stress tests.
+pub fn doubling() -> RuleTable {
+    let state = |name: &str| State::Named(String::from(name));
+    let mut rules = RuleTable::new();
+
+    //take a one off the counter, or halt when it is used up
+    rules.insert((state("Count"), '_'), ('_', Direction::Right, state("Count")));
+    rules.insert((state("Count"), '1'), ('_', Direction::Right, state("Seek")));
+    rules.insert((state("Count"), '#'), ('#', Direction::Stay, state("Done")));
+    rules.insert((state("Seek"), '1'), ('1', Direction::Right, state("Seek")));
+    rules.insert((state("Seek"), '#'), ('#', Direction::Right, state("Mark")));
+
+    //mark the next one of the block and append a copy of it at the end
+    rules.insert((state("Mark"), 'X'), ('X', Direction::Right, state("Mark")));
+    rules.insert((state("Mark"), '1'), ('X', Direction::Right, state("Append")));
+    rules.insert((state("Append"), '1'), ('1', Direction::Right, state("Append")));
+    rules.insert((state("Append"), 'Y'), ('Y', Direction::Right, state("Append")));
+    rules.insert((state("Append"), '_'), ('Y', Direction::Left, state("Back")));
+    rules.insert((state("Back"), '1'), ('1', Direction::Left, state("Back")));
+    rules.insert((state("Back"), 'Y'), ('Y', Direction::Left, state("Back")));
+    rules.insert((state("Back"), 'X'), ('X', Direction::Right, state("Mark")));
+
+    //every one is marked: turn the copies and the marks back into ones and rewind to the counter
+    rules.insert((state("Mark"), 'Y'), ('1', Direction::Right, state("Unmark")));
+    rules.insert((state("Unmark"), 'Y'), ('1', Direction::Right, state("Unmark")));
+    rules.insert((state("Unmark"), '_'), ('_', Direction::Left, state("Rewind")));
+    rules.insert((state("Rewind"), '1'), ('1', Direction::Left, state("Rewind")));
+    rules.insert((state("Rewind"), 'X'), ('1', Direction::Left, state("Rewind")));
+    rules.insert((state("Rewind"), '#'), ('#', Direction::Left, state("Rewind")));
+    rules.insert((state("Rewind"), '_'), ('_', Direction::Right, state("Count")));
+
+    rules
+}
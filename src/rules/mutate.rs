@@ -0,0 +1,116 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::rng::SplitMix64;
+use crate::rules::SortedRules;
+use crate::testing::{adder_vectors, passes};
+use crate::{Direction, RuleTable, State};
+
+/// A single change to one rule of a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mutation {
+    Write { state: State, read: char, from: char, to: char },          // The rule writes another symbol.
+    Move { state: State, read: char, from: Direction, to: Direction }, // The rule moves another way.
+    NextState { state: State, read: char, from: State, to: State },    // The rule goes to another state.
+    Delete { state: State, read: char },                               // The rule is removed.
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mutation::Write { state, read, from, to } => write!(f, "{} {}: write {} instead of {}", state, read, to, from),
+            Mutation::Move { state, read, from, to } => write!(f, "{} {}: move {} instead of {}", state, read, to, from),
+            Mutation::NextState { state, read, from, to } => write!(f, "{} {}: go to {} instead of {}", state, read, to, from),
+            Mutation::Delete { state, read } => write!(f, "{} {}: rule deleted", state, read),
+        }
+    }
+}
+
+/// Whether the test vectors noticed a mutation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutantOutcome {
+    pub mutation: Mutation, // What was changed.
+    pub killed: bool,       // Whether any test vector failed on the mutant.
+}
+
+/// Applies one random mutation to a copy of `rules`, which must not be empty.
+///
+/// A rule is picked uniformly, then one of: write another symbol of the table's alphabet,
+/// move another way, go to another state of the table, or delete the rule. When the
+/// table has no other symbol or state to use, the rule is deleted instead.
+pub fn mutate(rules: &RuleTable, rng: &mut SplitMix64) -> (RuleTable, Mutation) {
+    let entries: Vec<_> = rules.iter_sorted().collect();
+    let (state, read, write, direction, next_state) = entries[rng.below(entries.len())];
+    let (state, read) = (state.clone(), read);
+
+    let mut symbols = BTreeSet::new();
+    let mut states = BTreeSet::from([State::Halt]);
+    for (state, read, write, _, next_state) in &entries {
+        symbols.extend([*read, *write]);
+        states.extend([(*state).clone(), (*next_state).clone()]);
+    }
+    let other_symbols: Vec<char> = symbols.into_iter().filter(|&s| s != write).collect();
+    let other_states: Vec<State> = states.into_iter().filter(|s| s != next_state).collect();
+
+    let mutation = match rng.below(4) {
+        0 if !other_symbols.is_empty() => Mutation::Write {
+            state,
+            read,
+            from: write,
+            to: other_symbols[rng.below(other_symbols.len())],
+        },
+        1 => {
+            let others: Vec<Direction> = [Direction::Left, Direction::Right, Direction::Stay]
+                .into_iter()
+                .filter(|other| other != direction)
+                .collect();
+            Mutation::Move {
+                state,
+                read,
+                from: direction.clone(),
+                to: others[rng.below(others.len())].clone(),
+            }
+        }
+        2 if !other_states.is_empty() => Mutation::NextState {
+            state,
+            read,
+            from: next_state.clone(),
+            to: other_states[rng.below(other_states.len())].clone(),
+        },
+        _ => Mutation::Delete { state, read },
+    };
+
+    let mut mutant = rules.clone();
+    match &mutation {
+        Mutation::Write { state, read, to, .. } => mutant.get_mut(&(state.clone(), *read)).unwrap().0 = *to,
+        Mutation::Move { state, read, to, .. } => mutant.get_mut(&(state.clone(), *read)).unwrap().1 = to.clone(),
+        Mutation::NextState { state, read, to, .. } => mutant.get_mut(&(state.clone(), *read)).unwrap().2 = to.clone(),
+        Mutation::Delete { state, read } => {
+            mutant.remove(&(state.clone(), *read));
+        }
+    }
+    (mutant, mutation)
+}
+
+/// Runs `iterations` random single mutations of `rules` against the adder's test
+/// vectors, see `testing::adder_vectors`. The same seed gives the same mutants.
+///
+/// Returns `None` if `rules` itself fails the vectors, as every mutant would then count as killed.
+pub fn mutation_test(rules: &RuleTable, iterations: usize, seed: u64) -> Option<Vec<MutantOutcome>> {
+    let vectors = adder_vectors();
+    if rules.is_empty() || !passes(rules, &vectors) {
+        return None;
+    }
+    let mut rng = SplitMix64::new(seed);
+    let outcomes = (0..iterations)
+        .map(|_| {
+            let (mutant, mutation) = mutate(rules, &mut rng);
+            MutantOutcome {
+                killed: !passes(&mutant, &vectors),
+                mutation,
+            }
+        })
+        .collect();
+    Some(outcomes)
+}
@@ -0,0 +1,336 @@
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::rules::{MachineDefinition, SortedRules};
+use crate::tape::input::is_cell_symbol;
+use crate::{Direction, ResultStrategy, RuleDescriptions, RuleTable, State};
+
+pub use crate::error::{ImportError, ImportErrorKind};
+
+/// A machine read from the YAML format of the turingmachine.io simulator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Imported {
+    pub definition: MachineDefinition, // Rules and start state; the answer is the whole tape.
+    pub input: Vec<char>,              // The file's `input`, one cell per symbol, blanks as '_'.
+}
+
+// A transition as written in the file, kept until the blank symbol is known.
+struct Transition {
+    line: usize,                // Where it was written.
+    state: String,              // The state it belongs to.
+    reads: Vec<String>,         // The symbols it applies to.
+    write: Option<String>,      // The symbol to write, if not the one read.
+    direction: Direction,       // Where to move.
+    next_state: Option<String>, // The state to go to, if not the same one.
+}
+
+/// Reads a machine in the turingmachine.io format, e.g.
+///
+/// ```text
+/// input: '1011'
+/// blank: ' '
+/// start state: right
+/// table:
+///   right:
+///     [1, 0]: R
+///     ' ': {L: carry}
+///   carry:
+///     1: {write: 0, L}
+///     [0, ' ']: {write: 1, L: done}
+///   done:
+/// ```
+///
+/// Only the subset of YAML the simulator's examples use is understood: `key: value` lines,
+/// quoted or plain scalars, `[a, b]` lists of symbols and `{write: s, L: state}` maps on one
+/// line. Other top-level keys, like `name` or `source code`, are skipped. The file's blank is
+/// mapped to '_', a state without transitions halts as a final state, and the answer is read
+/// off the whole tape.
+pub fn parse(text: &str) -> Result<Imported, ImportError> {
+    let mut input = String::new();
+    let mut input_line = 0;
+    let mut blank = ' ';
+    let mut start = None;
+    let mut table_line = None;
+    let mut transitions: Vec<Transition> = Vec::new();
+    let mut states: Vec<String> = Vec::new();
+
+    let mut section = "";
+    let mut state_indent = None;
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let error = |kind| ImportError { line, kind };
+        let content = strip_comment(raw).trim_end();
+        if content.trim().is_empty() {
+            continue;
+        }
+        let indent = content.len() - content.trim_start().len();
+        let content = content.trim_start();
+
+        if indent == 0 {
+            let (key, value) = split_key(content).ok_or_else(|| error(ImportErrorKind::Syntax(content.to_string())))?;
+            section = match key {
+                "input" => {
+                    input = unquote(value);
+                    input_line = line;
+                    "input"
+                }
+                "blank" => {
+                    blank = symbol(&unquote(value)).map_err(error)?;
+                    "blank"
+                }
+                "start state" => {
+                    start = Some(unquote(value));
+                    "start state"
+                }
+                "table" => {
+                    table_line = Some(line);
+                    "table"
+                }
+                _ => "other",
+            };
+            continue;
+        }
+        if section != "table" {
+            continue;
+        }
+
+        let (key, value) = split_key(content).ok_or_else(|| error(ImportErrorKind::Syntax(content.to_string())))?;
+        if *state_indent.get_or_insert(indent) == indent {
+            if !value.is_empty() && value != "{}" {
+                return Err(error(ImportErrorKind::Syntax(content.to_string())));
+            }
+            states.push(unquote(key));
+            continue;
+        }
+        let Some(state) = states.last() else {
+            return Err(error(ImportErrorKind::Syntax(content.to_string())));
+        };
+
+        let reads = if let Some(list) = key.strip_prefix('[').and_then(|key| key.strip_suffix(']')) {
+            split_top_level(list).iter().map(|item| unquote(item)).collect()
+        } else {
+            Vec::from([unquote(key)])
+        };
+        let (write, direction, next_state) = instruction(value).map_err(error)?;
+        transitions.push(Transition {
+            line,
+            state: state.clone(),
+            reads,
+            write,
+            direction,
+            next_state,
+        });
+    }
+
+    let table_line = table_line.ok_or(ImportError {
+        line: text.lines().count(),
+        kind: ImportErrorKind::MissingTable,
+    })?;
+    let start = start.ok_or(ImportError {
+        line: table_line,
+        kind: ImportErrorKind::MissingStartState,
+    })?;
+
+    // Map the file's blank to ours, and its names onto states.
+    let cell = |text: &str| {
+        let symbol = symbol(text)?;
+        let symbol = if symbol == blank { '_' } else { symbol };
+        if is_cell_symbol(symbol) {
+            Ok(symbol)
+        } else {
+            Err(ImportErrorKind::Symbol(text.to_string()))
+        }
+    };
+    let mut rules = RuleTable::new();
+    for Transition { line, state, reads, write, direction, next_state } in transitions {
+        let error = |kind| ImportError { line, kind };
+        for read in reads {
+            let read = cell(&read).map_err(error)?;
+            let write = match &write {
+                Some(write) => cell(write).map_err(error)?,
+                None => read,
+            };
+            let from = State::from_name(&state);
+            let to = State::from_name(next_state.as_deref().unwrap_or(&state));
+            if rules.insert((from.clone(), read), (write, direction.clone(), to)).is_some() {
+                return Err(error(ImportErrorKind::Duplicate(from, read)));
+            }
+        }
+    }
+
+    // The simulator halts in states without transitions, so those are where machines end.
+    let final_states = states
+        .iter()
+        .map(|state| State::from_name(state))
+        .filter(|state| !rules.keys().any(|(from, _)| from == state))
+        .collect();
+
+    Ok(Imported {
+        definition: MachineDefinition {
+            rules,
+            start: State::from_name(&start),
+            result: ResultStrategy::WholeTapeTrimmed,
+            descriptions: RuleDescriptions::new(),
+            final_states,
+            description: None,
+        },
+        input: (input.chars())
+            .map(|symbol| cell(symbol.encode_utf8(&mut [0; 4])))
+            .collect::<Result<_, _>>()
+            .map_err(|kind| ImportError { line: input_line, kind })?,
+    })
+}
+
+/// Writes a machine in the turingmachine.io format, which `parse` reads back. The blank
+/// is written as ' ', every state named by a rule gets an entry, and `input` is the tape.
+pub fn export(definition: &MachineDefinition, input: &[char]) -> String {
+    let symbol = |symbol: char| quote(&(if symbol == '_' { ' ' } else { symbol }).to_string());
+    let mut states = BTreeSet::new();
+    for ((state, _), (_, _, next_state)) in &definition.rules {
+        states.insert(state.to_string());
+        states.insert(next_state.to_string());
+    }
+    states.insert(definition.start.to_string());
+
+    let input: String = input.iter().map(|&c| if c == '_' { ' ' } else { c }).collect();
+    let mut text = String::new();
+    writeln!(text, "input: {}", quote(&input)).unwrap();
+    writeln!(text, "blank: ' '").unwrap();
+    writeln!(text, "start state: {}", quote(&definition.start.to_string())).unwrap();
+    writeln!(text, "table:").unwrap();
+    let rules: Vec<_> = definition.rules.iter_sorted().collect();
+    for state in states {
+        writeln!(text, "  {}:", quote(&state)).unwrap();
+        for (_, read, write, direction, next_state) in rules.iter().filter(|rule| rule.0.to_string() == state) {
+            let direction = match direction {
+                Direction::Left => "L",
+                Direction::Right => "R",
+                Direction::Stay => "S",
+            };
+            writeln!(
+                text,
+                "    {}: {{write: {}, {}: {}}}",
+                symbol(*read),
+                symbol(*write),
+                direction,
+                quote(&next_state.to_string())
+            )
+            .unwrap();
+        }
+    }
+    text
+}
+
+// Drops a `#` comment, unless the `#` is inside quotes or part of a word.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            (None, '#') if previous.is_whitespace() => return &line[..index],
+            _ => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+// Splits `key: value` at the first colon outside quotes and brackets.
+fn split_key(line: &str) -> Option<(&str, &str)> {
+    let position = top_level(line).find(|&(_, c)| c == ':')?.0;
+    let (key, value) = (&line[..position], &line[position + 1..]);
+    if !value.is_empty() && !value.starts_with(' ') {
+        return None;
+    }
+    Some((key.trim(), value.trim()))
+}
+
+// Splits a flow list or map body at the commas outside quotes and brackets.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    for (index, c) in top_level(text) {
+        if c == ',' {
+            items.push(text[start..index].trim());
+            start = index + 1;
+        }
+    }
+    items.push(text[start..].trim());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+// The characters of `text` that are outside quotes and brackets, with their byte offsets.
+fn top_level(text: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut quote = None;
+    let mut depth = 0usize;
+    text.char_indices().filter(move |&(_, c)| {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth = depth.saturating_sub(1),
+            (None, _) if depth == 0 => return true,
+            _ => {}
+        }
+        false
+    })
+}
+
+// Removes the quotes around a scalar, undoing YAML's escapes for them.
+fn unquote(text: &str) -> String {
+    let text = text.trim();
+    if text.len() >= 2 && text.starts_with('\'') && text.ends_with('\'') {
+        text[1..text.len() - 1].replace("''", "'")
+    } else if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        text[1..text.len() - 1].replace("\\\"", "\"").replace("\\\\", "\\")
+    } else {
+        text.to_string()
+    }
+}
+
+// Quotes a scalar unless it is a plain word.
+fn quote(text: &str) -> String {
+    if !text.is_empty() && text.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        text.to_string()
+    } else {
+        ["'", &text.replace('\'', "''"), "'"].concat()
+    }
+}
+
+// A scalar that must be a single symbol.
+fn symbol(text: &str) -> Result<char, ImportErrorKind> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(symbol), None) => Ok(symbol),
+        _ => Err(ImportErrorKind::Symbol(text.to_string())),
+    }
+}
+
+// Reads a transition's value: `L`, `R`, or a map like `{write: 1, L: done}`.
+fn instruction(value: &str) -> Result<(Option<String>, Direction, Option<String>), ImportErrorKind> {
+    let entries = match value.strip_prefix('{').and_then(|value| value.strip_suffix('}')) {
+        Some(body) => split_top_level(body),
+        None => Vec::from([value]),
+    };
+    let mut write = None;
+    let mut movement = None;
+    for entry in entries {
+        let (key, target) = match split_key(entry) {
+            Some((key, target)) => (key, (!target.is_empty()).then(|| unquote(target))),
+            None => (entry, None),
+        };
+        match key {
+            "write" => write = Some(target.ok_or_else(|| ImportErrorKind::Syntax(value.to_string()))?),
+            "L" | "R" | "S" => movement = Some((key.parse().unwrap(), target)),
+            _ => return Err(ImportErrorKind::Syntax(value.to_string())),
+        }
+    }
+    let (direction, next_state) = movement.ok_or_else(|| ImportErrorKind::Move(value.to_string()))?;
+    Ok((write, direction, next_state))
+}
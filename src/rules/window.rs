@@ -0,0 +1,141 @@
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{Direction, RuleTable, State};
+
+pub use crate::error::WindowRuleError;
+
+/// A window rule written out flat: (current state, symbols read, symbols written, move,
+/// next state). The windows start under the head and run right, as wide as the table.
+pub type WindowRule = (State, String, String, Direction, State);
+
+/// What a window rule does: (symbols written, move, next state).
+pub type WindowAction = (Vec<char>, Direction, State);
+
+/// Rules that read and write a window of several cells at once, starting under the head,
+/// and then move the head by one cell as usual. Some machines are far simpler when a rule
+/// can see the next cell too, see `rules::window_adder`.
+///
+/// The table runs as a standard machine, see `to_single_cell`, so traces, verbosity and
+/// everything else work on it one cell at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowTable {
+    width: usize,                                     // Cells every rule reads and writes.
+    rules: HashMap<(State, Vec<char>), WindowAction>, // The rules, keyed by (state, window read).
+}
+
+impl WindowTable {
+    /// Assembles a table `width` cells wide, rejecting windows of another size and two
+    /// rules for the same (state, window). `width` must not be zero.
+    pub fn new(width: usize, rules: impl IntoIterator<Item = WindowRule>) -> Result<Self, WindowRuleError> {
+        assert!(width > 0, "a window must be at least one cell wide");
+        let mut table = WindowTable {
+            width,
+            rules: HashMap::new(),
+        };
+        for (state, read, write, direction, next_state) in rules {
+            let window = |symbols: String| {
+                let cells: Vec<char> = symbols.chars().collect();
+                if cells.len() == width {
+                    Ok(cells)
+                } else {
+                    Err(WindowRuleError::Width { width, symbols })
+                }
+            };
+            let (read_cells, write) = (window(read.clone())?, window(write)?);
+            if table.rules.contains_key(&(state.clone(), read_cells.clone())) {
+                return Err(WindowRuleError::Duplicate(state, read));
+            }
+            table.rules.insert((state, read_cells), (write, direction, next_state));
+        }
+        Ok(table)
+    }
+
+    /// Number of cells every rule reads and writes.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Whether the table has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Every state a rule starts in or leads to.
+    pub fn states(&self) -> BTreeSet<State> {
+        self.rules
+            .iter()
+            .flat_map(|((state, _), (_, _, next_state))| [state.clone(), next_state.clone()])
+            .collect()
+    }
+
+    /// The rule for `state` reading `window`, if any.
+    pub fn get(&self, state: &State, window: &[char]) -> Option<&WindowAction> {
+        self.rules.get(&(state.clone(), window.to_vec()))
+    }
+
+    /// Converts the table into standard single-cell rules that take `2 * width - 1` steps
+    /// per window rule: walk right over the window remembering it in the state, write its
+    /// last cell, walk back left writing the rest, then make the rule's move.
+    ///
+    /// Each state of the table keeps its name and starts a window rule, so start and final
+    /// states carry over. The states in between are named `State@read` while reading and
+    /// `Next!written,move,cell` while writing. A window with no rule halts the machine in
+    /// the `@` state that read it, right of where the window starts.
+    pub fn to_single_cell(&self) -> RuleTable {
+        let last = self.width - 1;
+        let mut table = RuleTable::new();
+        for ((state, read), (write, direction, next_state)) in &self.rules {
+            if last == 0 {
+                table.insert((state.clone(), read[0]), (write[0], direction.clone(), next_state.clone()));
+                continue;
+            }
+            for cell in 0..last {
+                table.insert(
+                    (reading(state, &read[..cell]), read[cell]),
+                    (read[cell], Direction::Right, reading(state, &read[..cell + 1])),
+                );
+            }
+            table.insert(
+                (reading(state, &read[..last]), read[last]),
+                (write[last], Direction::Left, writing(next_state, write, direction, last - 1)),
+            );
+            // The cells still hold what was read, so each write step knows its symbol.
+            for cell in (0..last).rev() {
+                let action = if cell == 0 {
+                    (write[0], direction.clone(), next_state.clone())
+                } else {
+                    (write[cell], Direction::Left, writing(next_state, write, direction, cell - 1))
+                };
+                table.insert((writing(next_state, write, direction, cell), read[cell]), action);
+            }
+        }
+        table
+    }
+}
+
+// The state that has read `seen` of a window for `state`; `state` itself before the first cell.
+fn reading(state: &State, seen: &[char]) -> State {
+    if seen.is_empty() {
+        state.clone()
+    } else {
+        State::Named(format!("{}@{}", state, seen.iter().collect::<String>()))
+    }
+}
+
+// The state that writes `cell` of `write` on its way back, before moving and going to `next_state`.
+fn writing(next_state: &State, write: &[char], direction: &Direction, cell: usize) -> State {
+    let write: String = write.iter().collect();
+    State::Named(format!("{}!{},{},{}", next_state, write, direction, cell))
+}
@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+
+use crate::rules::SortedRules;
+use crate::{RuleTable, State, TapeMode, TuringMachine};
+
+pub use crate::error::BoundedError;
+
+/// Checks that `rules` leave the end markers alone: every rule that reads one writes it
+/// back, and no rule writes one over another symbol.
+pub fn check_rules(rules: &RuleTable, left_marker: char, right_marker: char) -> Result<(), BoundedError> {
+    let marker = |symbol: char| symbol == left_marker || symbol == right_marker;
+    for (state, read, write, _, _) in rules.iter_sorted() {
+        if marker(read) && write != read {
+            return Err(BoundedError::OverwritesMarker {
+                state: state.clone(),
+                marker: read,
+                write,
+            });
+        }
+        if marker(write) && write != read {
+            return Err(BoundedError::WritesMarker {
+                state: state.clone(),
+                read,
+                marker: write,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `tape` is an input between the markers: it starts with the left one, ends
+/// with the right one and holds neither in between.
+pub fn check_tape(tape: &[char], left_marker: char, right_marker: char) -> Result<(), BoundedError> {
+    if tape.first() != Some(&left_marker) {
+        return Err(BoundedError::MissingMarker(left_marker));
+    }
+    if tape.len() < 2 || tape.last() != Some(&right_marker) {
+        return Err(BoundedError::MissingMarker(right_marker));
+    }
+    let inside = tape[1..tape.len() - 1].iter().position(|&symbol| symbol == left_marker || symbol == right_marker);
+    match inside {
+        Some(cell) => Err(BoundedError::StrayMarker {
+            cell: cell + 1,
+            marker: tape[cell + 1],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Builds the tape `<input>` with the markers at both ends, failing if the input holds one.
+pub fn tape(input: &str, left_marker: char, right_marker: char) -> Result<Vec<char>, BoundedError> {
+    let tape: Vec<char> = core::iter::once(left_marker).chain(input.chars()).chain([right_marker]).collect();
+    check_tape(&tape, left_marker, right_marker)?;
+    Ok(tape)
+}
+
+/// Builds a linear bounded automaton: a machine in `TapeMode::Bounded` whose tape is
+/// exactly `input` between the markers, as in `<abba>`, with the head on the left marker
+/// in state `start`. The markers are ordinary symbols to the rules, which read them to find
+/// the ends, but the bound is part of what the machine means, not a limit on memory as
+/// `TuringMachine::set_max_tape_len` is: rules that overwrite a marker are rejected here,
+/// and a move past one, or a marker no rule reads, ends a bounded run with
+/// `RunError::PastMarker` or `RunError::StuckOnMarker`.
+pub fn machine(
+    input: &str,
+    rules: RuleTable,
+    start: State,
+    left_marker: char,
+    right_marker: char,
+) -> Result<TuringMachine, BoundedError> {
+    check_rules(&rules, left_marker, right_marker)?;
+    let mut machine = TuringMachine::new(tape(input, left_marker, right_marker)?, rules);
+    machine.set_state(start);
+    machine.set_tape_mode(TapeMode::Bounded { left_marker, right_marker });
+    Ok(machine)
+}
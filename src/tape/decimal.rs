@@ -0,0 +1,90 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub use crate::error::ParseError;
+
+// Largest power of ten that fits a 32-bit limb, and its number of digits.
+const CHUNK: u64 = 1_000_000_000;
+const CHUNK_DIGITS: usize = 9;
+
+/// Converts a decimal number of any size to binary digits, without leading zeros.
+///
+/// Works on 32-bit limbs, so converting `n` digits takes O(n²) time, which is
+/// negligible next to the machine's own quadratic step count.
+pub fn decimal_to_binary(digits: &str) -> Result<String, ParseError> {
+    if let Some(symbol) = digits.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(ParseError::InvalidDecimalDigit(symbol));
+    }
+
+    // Little-endian base 2^32 limbs, built by multiplying in nine decimal digits at a time.
+    let mut limbs: Vec<u32> = Vec::new();
+    let mut start = 0;
+    while start < digits.len() {
+        // The first chunk takes the odd digits, so the rest are whole.
+        let end = if start == 0 && !digits.len().is_multiple_of(CHUNK_DIGITS) {
+            digits.len() % CHUNK_DIGITS
+        } else {
+            start + CHUNK_DIGITS
+        };
+        let chunk = &digits[start..end];
+        start = end;
+
+        let scale = 10u64.pow(chunk.len() as u32);
+        let mut carry: u64 = chunk.parse().unwrap();
+        for limb in limbs.iter_mut() {
+            let value = *limb as u64 * scale + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+    }
+
+    let Some(top) = limbs.pop() else {
+        return Ok(String::from("0"));
+    };
+    let mut binary = format!("{:b}", top);
+    for limb in limbs.iter().rev() {
+        binary.push_str(&format!("{:032b}", limb));
+    }
+    Ok(binary)
+}
+
+/// Converts binary digits of any size to a decimal number, without leading zeros.
+pub fn binary_to_decimal(bits: &str) -> Result<String, ParseError> {
+    if let Some(symbol) = bits.chars().find(|c| *c != '0' && *c != '1') {
+        return Err(ParseError::InvalidSymbol(symbol));
+    }
+
+    // Big-endian base 2^32 limbs.
+    let mut limbs: Vec<u32> = Vec::new();
+    let mut start = 0;
+    while start < bits.len() {
+        let end = if start == 0 && !bits.len().is_multiple_of(32) { bits.len() % 32 } else { start + 32 };
+        limbs.push(u32::from_str_radix(&bits[start..end], 2).unwrap());
+        start = end;
+    }
+
+    // Divide by 10^9 until nothing is left, collecting the remainders as decimal chunks.
+    let mut chunks = Vec::new();
+    while limbs.iter().any(|&limb| limb != 0) {
+        let mut remainder = 0u64;
+        for limb in limbs.iter_mut() {
+            let value = (remainder << 32) | *limb as u64;
+            *limb = (value / CHUNK) as u32;
+            remainder = value % CHUNK;
+        }
+        chunks.push(remainder);
+    }
+
+    let Some(top) = chunks.pop() else {
+        return Ok(String::from("0"));
+    };
+    let mut decimal = format!("{}", top);
+    for chunk in chunks.iter().rev() {
+        decimal.push_str(&format!("{:09}", chunk));
+    }
+    Ok(decimal)
+}
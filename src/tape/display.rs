@@ -0,0 +1,60 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+
+/// How symbols are shown when a tape or a rule is printed, e.g. the adder's `I` and `O`
+/// markers as the digits they stand for. Only the printing changes: the tape keeps its
+/// symbols and the rules match them as they are. Symbols without an entry are shown as
+/// themselves, so the default map shows everything as it is.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DisplayMap {
+    shown: BTreeMap<char, String>, // What each remapped symbol is shown as.
+}
+
+impl DisplayMap {
+    /// A map that shows every symbol as itself.
+    pub fn new() -> Self {
+        DisplayMap::default()
+    }
+
+    /// Shows each marker of `pairs` as the symbol it stands for, underlined with a
+    /// combining low line: `('I', '1')` shows `I` as `1̲`. The underline keeps a marked
+    /// digit apart from a plain one without taking up a column.
+    pub fn markers(pairs: &[(char, char)]) -> Self {
+        let mut map = DisplayMap::new();
+        for &(marker, symbol) in pairs {
+            map.insert(marker, [symbol, '\u{332}'].iter().collect::<String>());
+        }
+        map
+    }
+
+    /// The adder's markers, `I` and `O`, shown as underlined `1` and `0`.
+    pub fn adder() -> Self {
+        DisplayMap::markers(&ADDER_MARKERS)
+    }
+
+    /// Shows `symbol` as `shown`, e.g. a bold digit with terminal escapes.
+    pub fn insert(&mut self, symbol: char, shown: impl Into<String>) {
+        self.shown.insert(symbol, shown.into());
+    }
+
+    /// Whether the map shows every symbol as itself.
+    pub fn is_empty(&self) -> bool {
+        self.shown.is_empty()
+    }
+
+    /// How `symbol` is shown.
+    pub fn show(&self, symbol: char) -> String {
+        match self.shown.get(&symbol) {
+            Some(shown) => shown.clone(),
+            None => symbol.to_string(),
+        }
+    }
+
+    /// The cells of `tape`, each as it is shown, one after the other.
+    pub fn show_tape<'a>(&self, tape: impl IntoIterator<Item = &'a char>) -> String {
+        tape.into_iter().map(|&symbol| self.show(symbol)).collect()
+    }
+}
+
+/// The adder's markers and the digits they stand for, see `DisplayMap::adder`.
+pub const ADDER_MARKERS: [(char, char); 2] = [('I', '1'), ('O', '0')];
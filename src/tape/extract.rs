@@ -0,0 +1,103 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{State, Tape};
+
+/// How to read a machine's answer off its final tape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ResultStrategy {
+    /// The cells after the last `separator` up to the next blank, with every symbol
+    /// listed in `map` replaced. With no separator on the tape, the cells after the
+    /// leading blanks. This is how the adder is read.
+    RightOfSeparator { separator: char, map: Vec<(char, char)> },
+    /// The whole tape with the blanks at both ends trimmed.
+    WholeTapeTrimmed,
+    /// `accept` if the machine halted in one of these states, `reject` otherwise.
+    AcceptStateOnly { accept: Vec<State> },
+}
+
+impl ResultStrategy {
+    /// The adder's strategy: right of `+`, reading the `I`/`O` markers as `1`/`0`.
+    pub fn adder() -> Self {
+        ResultStrategy::RightOfSeparator {
+            separator: '+',
+            map: vec![('I', '1'), ('O', '0')],
+        }
+    }
+
+    /// Reads the answer from a final tape. `halted_in` is the state the machine halted in.
+    pub fn extract(&self, tape: &Tape, halted_in: Option<&State>) -> String {
+        match self {
+            ResultStrategy::AcceptStateOnly { accept } => {
+                let accepted = halted_in.is_some_and(|state| accept.contains(state));
+                String::from(if accepted { "accept" } else { "reject" })
+            }
+            _ => self.answer(tape).collect(),
+        }
+    }
+
+    /// Number of significant bits in the answer on a final tape: the symbols from its
+    /// first `1` on, so 0 for a zero answer. Cheaper than `extract`, as nothing is copied.
+    /// An accept/reject answer has no bits.
+    pub fn bit_length(&self, tape: &Tape) -> usize {
+        match self {
+            ResultStrategy::AcceptStateOnly { .. } => 0,
+            _ => self.answer(tape).skip_while(|&c| c != '1').count(),
+        }
+    }
+
+    // The symbols of a tape-based answer, with markers already mapped.
+    fn answer<'a>(&'a self, tape: &'a Tape) -> impl Iterator<Item = char> + 'a {
+        let (start, end, map) = match self {
+            ResultStrategy::RightOfSeparator { separator, map } => {
+                let start = match tape.iter().rposition(|c| c == separator) {
+                    Some(position) => position + 1,
+                    None => tape.iter().take_while(|&&c| c == '_').count(),
+                };
+                let end = start + tape.range(start..).take_while(|&&c| c != '_').count();
+                (start, end, &map[..])
+            }
+            ResultStrategy::WholeTapeTrimmed => {
+                let start = tape.iter().take_while(|&&c| c == '_').count();
+                let end = tape.len() - tape.range(start..).rev().take_while(|&&c| c == '_').count();
+                (start, end, &[][..])
+            }
+            ResultStrategy::AcceptStateOnly { .. } => (0, 0, &[][..]),
+        };
+        tape.range(start..end)
+            .map(move |&c| map.iter().find(|(from, _)| *from == c).map_or(c, |&(_, to)| to))
+    }
+}
+
+impl Default for ResultStrategy {
+    fn default() -> Self {
+        ResultStrategy::adder()
+    }
+}
+
+impl fmt::Display for ResultStrategy {
+    // Written as in a rule file's `result` line, e.g. `right_of_separator + I=1 O=0`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResultStrategy::RightOfSeparator { separator, map } => {
+                write!(f, "right_of_separator {}", separator)?;
+                for (from, to) in map {
+                    write!(f, " {}={}", from, to)?;
+                }
+                Ok(())
+            }
+            ResultStrategy::WholeTapeTrimmed => write!(f, "whole_tape_trimmed"),
+            ResultStrategy::AcceptStateOnly { accept } => {
+                write!(f, "accept_state_only")?;
+                for state in accept {
+                    write!(f, " {}", state)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{decode_result, TapeLayout};
+
+pub use crate::error::ParseError;
+
+/// What the tape builder does with leading zeros in the operands.
+///
+/// The machine writes the sum over the cells of both operands, so the result is
+/// `max(len(a), len(b))` digits wide, plus one more when the addition carries out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeadingZeros {
+    #[default]
+    Strip,    // Strip them, keeping a single '0' for zero. The result then has no leading zeros either.
+    Preserve, // Keep them. Operands padded to the same width give a result of that width (plus a carry digit).
+}
+
+/// The tape of the built-in example, the first of `DEMO_INPUTS`: 1010011011 + 1011.
+pub const DEMO_TAPE: &str = "_1010011011+1011_";
+
+/// The additions `tm` runs without a command, each with what it shows of the adder.
+pub const DEMO_INPUTS: [(&str, &str); 7] = [
+    ("1010011011+1011", "the built-in example"),
+    ("1111+1", "a carry rippling through every digit"),
+    ("11111111+11111111", "a carry out of every digit"),
+    ("1+100000", "a shorter first operand"),
+    ("100000+1", "a shorter second operand"),
+    ("0+101", "a zero operand"),
+    ("0+0", "zero plus zero"),
+];
+
+/// Checks that `input` has the form `a+b` with binary operands, failing with the first
+/// problem `diagnose` finds.
+pub fn validate_input(input: &str) -> Result<(), ParseError> {
+    match diagnose(input).into_iter().next() {
+        Some(diagnostic) => Err(diagnostic.problem),
+        None => Ok(()),
+    }
+}
+
+/// A problem with an `a+b` input and where it is, see `diagnose`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub column: usize,       // The character the problem is at, counting from 1; one past the end for missing parts.
+    pub problem: ParseError, // What is wrong there.
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.problem)
+    }
+}
+
+/// Finds every problem with an `a+b` input, in column order, rather than stopping at the
+/// first: symbols other than binary digits (the adder's `I`/`O` markers among them), a
+/// missing or extra '+', a missing operand, or no input at all. Empty when the input is
+/// fine.
+pub fn diagnose(input: &str) -> Vec<Diagnostic> {
+    let at = |column: usize, problem: ParseError| Diagnostic { column, problem };
+    let len = input.chars().count();
+    if len == 0 {
+        return vec![at(1, ParseError::EmptyInput)];
+    }
+    let mut diagnostics = Vec::new();
+    let mut separator = None;
+    for (index, symbol) in input.chars().enumerate() {
+        let column = index + 1;
+        match symbol {
+            '0' | '1' => {}
+            '+' if separator.is_none() => separator = Some(column),
+            '+' => diagnostics.push(at(column, ParseError::ExtraOperator)),
+            'I' | 'O' => diagnostics.push(at(column, ParseError::ReservedMarker(symbol))),
+            _ => diagnostics.push(at(column, ParseError::InvalidSymbol(symbol))),
+        }
+    }
+    match separator {
+        None => diagnostics.push(at(len + 1, ParseError::MissingOperator)),
+        Some(1) => diagnostics.push(at(1, ParseError::MissingOperand { second: false })),
+        Some(_) => {}
+    }
+    // The second operand is missing when nothing but more '+' follows the first.
+    if separator.is_some_and(|column| input.chars().skip(column).all(|symbol| symbol == '+')) {
+        diagnostics.push(at(len + 1, ParseError::MissingOperand { second: true }));
+    }
+    diagnostics.sort_by_key(|diagnostic| diagnostic.column);
+    diagnostics
+}
+
+/// Shows `input` with a caret under every problem, then the problems, one per line:
+///
+/// ```text
+///   10a1+1O
+///     ^    ^
+/// column 3: 'a' is not a binary digit
+/// column 7: 'O' is not a binary digit, and is reserved: ...
+/// ```
+pub fn render_diagnostics(input: &str, diagnostics: &[Diagnostic]) -> String {
+    let mut carets = String::from("  ");
+    for diagnostic in diagnostics {
+        let width = diagnostic.column + 1;
+        if carets.chars().count() <= width {
+            carets.push_str(&format!("{:>width$}", '^', width = width + 1 - carets.chars().count()));
+        }
+    }
+    let mut text = format!("  {}\n{}", input, carets);
+    for diagnostic in diagnostics {
+        text.push_str(&format!("\n{}", diagnostic));
+    }
+    text
+}
+
+/// Whether `symbol` can go in a cell: one printable ASCII character. The engine and the
+/// formats it reads and writes assume a cell is one plain character, which a letter with
+/// a combining accent, an emoji or a control character is not.
+pub(crate) fn is_cell_symbol(symbol: char) -> bool {
+    symbol.is_ascii_graphic()
+}
+
+/// Reads a tape given cell by cell, e.g. `_1011+11_`, checking that every cell holds
+/// one printable ASCII character.
+pub fn parse_tape(text: &str) -> Result<Vec<char>, ParseError> {
+    match text.chars().enumerate().find(|&(_, symbol)| !is_cell_symbol(symbol)) {
+        Some((cell, symbol)) => Err(ParseError::TapeSymbol { cell, symbol }),
+        None => Ok(text.chars().collect()),
+    }
+}
+
+/// Builds the adder's input tape from an `a+b` string, see `build_tape`. Fails with the
+/// first problem `diagnose` finds.
+pub fn parse_input(input: &str, leading_zeros: LeadingZeros) -> Result<Vec<char>, ParseError> {
+    validate_input(input)?;
+    let (a, b) = split_input(input)?;
+    build_tape(a, b, leading_zeros)
+}
+
+// Splits `a+b` at the operator.
+fn split_input(input: &str) -> Result<(&str, &str), ParseError> {
+    input.split_once('+').ok_or(ParseError::MissingOperator)
+}
+
+/// Builds the adder's input tape `_a+b_` from two binary operands.
+pub fn build_tape(a: &str, b: &str, leading_zeros: LeadingZeros) -> Result<Vec<char>, ParseError> {
+    Ok(build_tape_with_layout(a, b, leading_zeros)?.0)
+}
+
+/// Builds the adder's input tape like `build_tape`, along with its layout: where the
+/// operands and the separator are, see `TuringMachine::set_layout`.
+pub fn build_tape_with_layout(a: &str, b: &str, leading_zeros: LeadingZeros) -> Result<(Vec<char>, TapeLayout), ParseError> {
+    let a = operand(a, leading_zeros)?;
+    let b = operand(b, leading_zeros)?;
+
+    let mut tape = Vec::with_capacity(a.len() + b.len() + 3);
+    tape.push('_');
+    tape.extend(a.chars());
+    tape.push('+');
+    tape.extend(b.chars());
+    tape.push('_');
+    Ok((tape, TapeLayout::adder(a.len(), b.len())))
+}
+
+/// Builds the adder's input tape `_a+b_` straight from bits, most significant first, with
+/// no leading zero policy: every bit given lands on the tape.
+pub fn bits_tape(a: &[bool], b: &[bool]) -> Vec<char> {
+    let digit = |&bit: &bool| if bit { '1' } else { '0' };
+    let mut tape = Vec::with_capacity(a.len() + b.len() + 3);
+    tape.push('_');
+    tape.extend(a.iter().map(digit));
+    tape.push('+');
+    tape.extend(b.iter().map(digit));
+    tape.push('_');
+    tape
+}
+
+/// The bits of big-endian `bytes`, most significant first, for `bits_tape`.
+pub fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes.iter().flat_map(|byte| (0..8).rev().map(move |bit| byte >> bit & 1 == 1)).collect()
+}
+
+/// Builds the tape `_1…1#1_` on which `rules::doubling` computes `2^n`.
+pub fn doubling_tape(n: usize) -> Vec<char> {
+    let mut tape = vec!['_'; n + 4];
+    tape[1..=n].fill('1');
+    tape[n + 1] = '#';
+    tape[n + 2] = '1';
+    tape
+}
+
+/// Builds the tape for adding `operand` to the answer on an adder's final tape, so
+/// additions can be chained.
+///
+/// A final tape looks like `_+++IIIO_`: the first operand's cells overwritten with `+`,
+/// then the sum with `I`/`O` for `1`/`0`. The chained tape keeps the sum, read the same
+/// way as `TuringMachine::result`, as the first operand: `_1110+operand_`, which the
+/// adder starts on in `FindPlus` like any other input. A plain `_n_` tape works too,
+/// giving `_n+operand_`.
+pub fn chain_tape<'a>(
+    previous: impl IntoIterator<Item = &'a char>,
+    operand: &str,
+    leading_zeros: LeadingZeros,
+) -> Result<Vec<char>, ParseError> {
+    build_tape(&decode_result(previous), operand, leading_zeros)
+}
+
+// Checks an operand's symbols and applies the leading zero policy. The adder's `I`/`O`
+// markers get an error of their own, as they look like digits but would be taken for
+// digits of the sum.
+pub(crate) fn operand(digits: &str, leading_zeros: LeadingZeros) -> Result<&str, ParseError> {
+    match digits.chars().find(|c| *c != '0' && *c != '1') {
+        Some(symbol @ ('I' | 'O')) => return Err(ParseError::ReservedMarker(symbol)),
+        Some(symbol) => return Err(ParseError::InvalidSymbol(symbol)),
+        None => {}
+    }
+    match leading_zeros {
+        LeadingZeros::Preserve => Ok(digits),
+        LeadingZeros::Strip => {
+            let stripped = digits.trim_start_matches('0');
+            if stripped.is_empty() && !digits.is_empty() {
+                Ok("0")
+            } else {
+                Ok(stripped)
+            }
+        }
+    }
+}
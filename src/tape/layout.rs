@@ -0,0 +1,132 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{ResultStrategy, Tape, TuringMachine};
+
+/// Named regions of a structured tape, such as the adder's `operand_a`, `separator` and
+/// `operand_b`, made by the tape builder, see `input::build_tape_with_layout`.
+///
+/// Regions are ranges of logical cells, where cell 0 is the first cell of the tape as it
+/// was built, so growth on the left doesn't move them; a cell grown left of it is -1. A
+/// machine keeps its layout up to date as it runs: a separator written at the end of the
+/// region left of the separator moves that cell into the separator, as when the adder
+/// takes a digit of the first operand, and anything else written at the separator's right
+/// end moves that cell into the region right of it, as when a carry grows the sum left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TapeLayout {
+    regions: Vec<(String, Range<isize>)>, // Named ranges of logical cells, left to right.
+    separator: char,                      // The symbol filling the region called `separator`.
+    origin: usize,                        // Index on the tape of logical cell 0; grows as the tape grows left.
+}
+
+impl TapeLayout {
+    /// The layout of the adder's tape `_a+b_`, for operands of `a` and `b` digits.
+    pub fn adder(a: usize, b: usize) -> Self {
+        let (a, b) = (a as isize, b as isize);
+        TapeLayout {
+            regions: Vec::from([
+                (String::from("operand_a"), 1..1 + a),
+                (String::from("separator"), 1 + a..2 + a),
+                (String::from("operand_b"), 2 + a..2 + a + b),
+            ]),
+            separator: '+',
+            origin: 0,
+        }
+    }
+
+    /// The logical cells of a region.
+    pub fn region(&self, name: &str) -> Option<Range<isize>> {
+        self.regions.iter().find(|(region, _)| region == name).map(|(_, range)| range.clone())
+    }
+
+    /// The tape indices of a region's cells. Cells left of the tape's start are left out.
+    pub fn cells(&self, name: &str) -> Option<Range<usize>> {
+        let range = self.region(name)?;
+        let index = |cell: isize| (cell + self.origin as isize).max(0) as usize;
+        Some(index(range.start)..index(range.end))
+    }
+
+    /// A region's symbols on `tape`, blanks for cells past its end.
+    pub fn slice(&self, name: &str, tape: &Tape) -> Option<String> {
+        Some(self.cells(name)?.map(|index| tape.get(index).copied().unwrap_or('_')).collect())
+    }
+
+    // Shifts the tape indices after the tape grew by a cell on the left.
+    pub(crate) fn grew_left(&mut self) {
+        self.origin += 1;
+    }
+
+    // Moves region boundaries after `symbol` was written at tape index `index`, see above.
+    pub(crate) fn record_write(&mut self, index: usize, symbol: char) {
+        let cell = index as isize - self.origin as isize;
+        let Some(separator) = self.regions.iter().position(|(name, _)| name == "separator") else {
+            return;
+        };
+        let range = self.regions[separator].1.clone();
+        if symbol == self.separator && separator > 0 {
+            let left = &mut self.regions[separator - 1].1;
+            if left.start < left.end && cell == left.end - 1 {
+                left.end -= 1;
+                self.regions[separator].1.start -= 1;
+            }
+        } else if symbol != self.separator && range.len() > 1 && cell == range.end - 1 {
+            if let Some((_, right)) = self.regions.get_mut(separator + 1) {
+                right.start -= 1;
+                self.regions[separator].1.end -= 1;
+            }
+        }
+    }
+}
+
+impl TuringMachine {
+    /// The logical cells of a region of the machine's tape layout, see `TapeLayout`.
+    pub fn region(&self, name: &str) -> Option<Range<isize>> {
+        self.layout.as_ref()?.region(name)
+    }
+
+    /// The symbols in a region of the machine's tape layout, see `TapeLayout`.
+    pub fn slice(&self, name: &str) -> Option<String> {
+        self.layout.as_ref()?.slice(name, &self.tape)
+    }
+
+    /// Checks that a halted adder left a well-formed tape, using its layout: the first
+    /// operand all taken, a separator of nothing but `+`, only digits and `I`/`O` markers
+    /// in the answer, the markers after every digit, and blanks everywhere else. Explains
+    /// what is wrong if not.
+    pub fn verify_final_tape(&self) -> Result<(), String> {
+        let layout = self.layout.as_ref().ok_or("the machine has no tape layout")?;
+        let cells = |name: &str| layout.cells(name).ok_or_else(|| format!("the layout has no {}", name));
+        let (a, separator, b) = (cells("operand_a")?, cells("separator")?, cells("operand_b")?);
+        if !a.is_empty() {
+            return Err(format!("the first operand still holds {}", self.slice("operand_a").unwrap()));
+        }
+        if separator.is_empty() || self.tape.range(separator.clone()).any(|&c| c != '+') {
+            return Err(format!("the separator is '{}'", self.slice("separator").unwrap()));
+        }
+        if let Some(c) = self.tape.range(b.clone()).find(|c| !matches!(c, '0' | '1' | 'I' | 'O')) {
+            return Err(format!("the answer holds '{}'", c));
+        }
+        let answer = self.slice("operand_b").unwrap();
+        if answer.trim_start_matches(['0', '1']).contains(['0', '1']) {
+            return Err(format!("the answer {} has a digit after a marker", answer));
+        }
+        let outside = (self.tape.iter().enumerate())
+            .find(|&(index, &c)| c != '_' && !separator.contains(&index) && !b.contains(&index));
+        match outside {
+            Some((index, c)) => Err(format!("cell {} holds '{}' outside the layout", index, c)),
+            None => Ok(()),
+        }
+    }
+
+    // The answer read from the `operand_b` region of the layout, if the machine has one and
+    // reads its answer right of the separator.
+    pub(crate) fn layout_result(&self) -> Option<String> {
+        let ResultStrategy::RightOfSeparator { map, .. } = &self.result_strategy else {
+            return None;
+        };
+        let answer = self.slice("operand_b")?;
+        Some(answer.chars().map(|c| map.iter().find(|(from, _)| *from == c).map_or(c, |&(_, to)| to)).collect())
+    }
+}
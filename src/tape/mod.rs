@@ -0,0 +1,11 @@
+// What goes on the tape and comes off it: the adder's inputs, named regions, how
+// symbols are shown, decimal operands, reading the answer, and bounded tapes.
+
+pub mod bounded;
+pub mod decimal;
+pub mod display;
+pub mod extract;
+pub mod input;
+pub mod layout;
+
+pub use crate::compact::parse_configuration;
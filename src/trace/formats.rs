@@ -0,0 +1,121 @@
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+
+use crate::trace::json::Json;
+
+pub use crate::error::FormatError;
+
+/// The JSON artifacts this crate writes. Each is written in an envelope naming its format
+/// and version, `{"format": "tm-trace", "version": 2, "payload": {...}}`, see `envelope`,
+/// and read back through `open`, so a file of another kind or from a newer version is
+/// turned down with a clear error instead of misread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Trace,     // A recorded run, see `replay::Trace::to_json`.
+    Machine,   // A machine in the JSON rule format, see `rules::to_json`.
+    Stats,     // A batch run's summary, see `stats::AggregateStats::to_json`.
+    Vectors,   // Test vectors for the adder, see `vectors::export`.
+    Snapshots, // A recorded `Vec<Snapshot>`, saved with serde through `Envelope`.
+}
+
+impl Format {
+    /// The name in the envelope's `format` field.
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Trace => "tm-trace",
+            Format::Machine => "tm-machine",
+            Format::Stats => "tm-stats",
+            Format::Vectors => "tm-vectors",
+            Format::Snapshots => "tm-snapshots",
+        }
+    }
+
+    /// The version written now. `open` also reads the version before it, which for every
+    /// format is 1: the same payload, without an envelope.
+    pub fn version(self) -> i64 {
+        match self {
+            Format::Trace | Format::Machine | Format::Stats | Format::Vectors | Format::Snapshots => 2,
+        }
+    }
+}
+
+/// Wraps an artifact's payload in the envelope for its format at the current version.
+pub fn envelope(format: Format, payload: Json) -> Json {
+    Json::object()
+        .field("format", format.name())
+        .field("version", Json::Int(format.version()))
+        .field("payload", payload)
+}
+
+/// Checks that `json` is an artifact of `format`, at the current or the previous version,
+/// and returns its payload. A document without a `format` field is taken as version 1,
+/// from before the envelope, and is its own payload.
+pub fn open(format: Format, json: &Json) -> Result<&Json, FormatError> {
+    let Some(name) = json.get("format") else {
+        return match json.get("version").map(Json::as_i64) {
+            None | Some(Some(1)) => Ok(json),
+            Some(Some(version)) => Err(unsupported(format, version)),
+            Some(None) => Err(FormatError::Field("version")),
+        };
+    };
+    let name = name.as_str().ok_or(FormatError::Field("format"))?;
+    check(format, name, json.get("version").and_then(Json::as_i64))?;
+    json.get("payload").ok_or(FormatError::Field("payload"))
+}
+
+// Checks an envelope's `format` name and then its `version` against `format`.
+fn check(format: Format, name: &str, version: Option<i64>) -> Result<(), FormatError> {
+    if name != format.name() {
+        return Err(FormatError::WrongFormat {
+            expected: format.name(),
+            found: name.into(),
+        });
+    }
+    let version = version.ok_or(FormatError::Field("version"))?;
+    if version != format.version() && version != format.version() - 1 {
+        return Err(unsupported(format, version));
+    }
+    Ok(())
+}
+
+// The error for an artifact of `format` at a `version` this crate doesn't read.
+fn unsupported(format: Format, version: i64) -> FormatError {
+    FormatError::UnsupportedVersion {
+        format: format.name(),
+        version,
+        current: format.version(),
+    }
+}
+
+/// An artifact whose payload serde (de)serializes, with the `serde` feature: the document
+/// `envelope` writes, or the bare payload of version 1, read back through `Envelope::open`.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum Envelope<T> {
+    Current { format: String, version: i64, payload: T }, // As `envelope` writes it.
+    Bare(T),                                                // Version 1, from before the envelope.
+}
+
+#[cfg(feature = "serde")]
+impl<T> Envelope<T> {
+    /// Wraps `payload` in the envelope for `format` at the current version.
+    pub fn new(format: Format, payload: T) -> Self {
+        Envelope::Current {
+            format: format.name().to_string(),
+            version: format.version(),
+            payload,
+        }
+    }
+
+    /// Checks that this is an artifact of `format`, as `open` does, and returns its payload.
+    pub fn open(self, format: Format) -> Result<T, FormatError> {
+        match self {
+            Envelope::Current { format: name, version, payload } => {
+                check(format, &name, Some(version))?;
+                Ok(payload)
+            }
+            Envelope::Bare(payload) => Ok(payload),
+        }
+    }
+}
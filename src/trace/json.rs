@@ -0,0 +1,349 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+pub use crate::error::JsonError;
+
+/// A JSON value, just enough to write the machine's reports without a serializer.
+/// Object keys keep their insertion order, so output is deterministic.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Starts an empty object, to be filled with `field`.
+    pub fn object() -> Self {
+        Json::Object(Vec::new())
+    }
+
+    /// Appends a field to an object. Does nothing to other values.
+    pub fn field(mut self, key: &str, value: impl Into<Json>) -> Self {
+        if let Json::Object(fields) = &mut self {
+            fields.push((String::from(key), value.into()));
+        }
+        self
+    }
+
+    /// The value of an object's field, the first if the key repeats.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// The text of a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::Str(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The value of an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Json::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The values of an array.
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a JSON document. Numbers without a fraction or exponent that fit become `Int`.
+impl FromStr for Json {
+    type Err = JsonError;
+
+    fn from_str(text: &str) -> Result<Self, JsonError> {
+        let mut parser = Parser { text, offset: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.offset < text.len() {
+            return Err(parser.error("the end of the document"));
+        }
+        Ok(value)
+    }
+}
+
+// Reads JSON from `text`, starting at byte `offset`.
+struct Parser<'a> {
+    text: &'a str, // The whole document.
+    offset: usize, // Byte offset of the next character to read.
+}
+
+impl Parser<'_> {
+    fn error(&self, expected: &'static str) -> JsonError {
+        JsonError {
+            offset: self.offset,
+            expected,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.offset..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.offset..];
+        self.offset += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    // Consumes `token` if the text continues with it.
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.text[self.offset..].starts_with(token);
+        if found {
+            self.offset += token.len();
+        }
+        found
+    }
+
+    fn value(&mut self) -> Result<Json, JsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => Ok(Json::Str(self.string()?)),
+            Some('-' | '0'..='9') => self.number(),
+            _ if self.eat("null") => Ok(Json::Null),
+            _ if self.eat("true") => Ok(Json::Bool(true)),
+            _ if self.eat("false") => Ok(Json::Bool(false)),
+            _ => Err(self.error("a value")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, JsonError> {
+        self.offset += 1;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.eat("}") {
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("a key"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            if !self.eat(":") {
+                return Err(self.error("':'"));
+            }
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            if self.eat("}") {
+                return Ok(Json::Object(fields));
+            }
+            if !self.eat(",") {
+                return Err(self.error("',' or '}'"));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, JsonError> {
+        self.offset += 1;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.eat("]") {
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat("]") {
+                return Ok(Json::Array(values));
+            }
+            if !self.eat(",") {
+                return Err(self.error("',' or ']'"));
+            }
+        }
+    }
+
+    // Reads a string literal, the parser being on its opening quote.
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.offset += 1;
+        let mut text = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("a closing '\"'"));
+            };
+            self.offset += c.len_utf8();
+            match c {
+                '"' => return Ok(text),
+                '\\' => {
+                    let escaped = match self.peek() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            let code = self.text.get(self.offset + 1..self.offset + 5);
+                            let c = code.and_then(|code| u32::from_str_radix(code, 16).ok()).and_then(char::from_u32);
+                            self.offset += 4;
+                            c.ok_or(self.error("four hex digits of a character that isn't a surrogate"))?
+                        }
+                        _ => return Err(self.error("an escape")),
+                    };
+                    self.offset += 1;
+                    text.push(escaped);
+                }
+                c if (c as u32) < 0x20 => return Err(self.error("no control characters in a string")),
+                c => text.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, JsonError> {
+        let start = self.offset;
+        let rest = &self.text[start..];
+        let len = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit() || "+-.eE".contains(c)).len();
+        self.offset += len;
+        let number = &rest[..len];
+        if let Ok(value) = number.parse() {
+            return Ok(Json::Int(value));
+        }
+        number.parse().map(Json::Float).map_err(|_| JsonError { offset: start, expected: "a number" })
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Json::Int(value as i64)
+    }
+}
+
+impl From<u64> for Json {
+    fn from(value: u64) -> Self {
+        Json::Int(value as i64)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Json::Float(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::Str(String::from(value))
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::Str(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Self {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+// Writes a string literal with the escapes JSON requires.
+fn write_string(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+impl fmt::Display for Json {
+    // Compact JSON, no whitespace between tokens, or indented two spaces a level with `{:#}`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let indent = f.alternate().then_some(0);
+        self.write(f, indent)
+    }
+}
+
+impl Json {
+    // Writes the value, its first line at `indent` levels when indenting.
+    fn write(&self, f: &mut fmt::Formatter, indent: Option<usize>) -> fmt::Result {
+        let inner = indent.map(|level| level + 1);
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Int(value) => write!(f, "{}", value),
+            Json::Float(value) if value.is_finite() => write!(f, "{}", value),
+            Json::Float(_) => f.write_str("null"),
+            Json::Str(text) => write_string(f, text),
+            Json::Array(values) if values.is_empty() => f.write_str("[]"),
+            Json::Array(values) => {
+                f.write_str("[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    new_line(f, inner)?;
+                    value.write(f, inner)?;
+                }
+                new_line(f, indent)?;
+                f.write_str("]")
+            }
+            Json::Object(fields) if fields.is_empty() => f.write_str("{}"),
+            Json::Object(fields) => {
+                f.write_str("{")?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    new_line(f, inner)?;
+                    write_string(f, key)?;
+                    f.write_str(if indent.is_some() { ": " } else { ":" })?;
+                    value.write(f, inner)?;
+                }
+                new_line(f, indent)?;
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+// Starts a new line at `indent` levels when indenting.
+fn new_line(f: &mut fmt::Formatter, indent: Option<usize>) -> fmt::Result {
+    match indent {
+        Some(level) => write!(f, "\n{:width$}", "", width = 2 * level),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::tape::display::DisplayMap;
+use crate::State;
+
+pub mod formats;
+pub mod json;
+pub mod narrate;
+pub mod replay;
+pub mod stats;
+
+/// A recorded configuration of the machine. With the `serde` feature, a recorded trace,
+/// a `Vec<Snapshot>`, can be saved in a `tm-snapshots` `formats::Envelope` and rendered
+/// again later without rerunning the machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    pub tape: Vec<char>,            // The tape contents at this point.
+    pub head: usize,                // The position of the head on the tape.
+    pub state: State,               // The state the machine was in.
+    pub partial_value: Option<u64>, // The answer region's value, when recorded and decodable.
+}
+
+/// How much `TuringMachine::run` logs at every step, see `STEP_TARGET`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    #[default]
+    Quiet,   // Nothing.
+    Steps,   // The configuration at every step.
+    Rules,   // The configuration at every step, followed by the rule about to fire.
+    Explain, // The rule about to fire at every step, as a sentence.
+}
+
+/// The `log` target of the lines `Verbosity` asks `run` for, logged at info level, so a
+/// logger can print them as output apart from the diagnostics.
+pub const STEP_TARGET: &str = "turing::steps";
+
+/// Number of cells shown around the head when a trace line is windowed.
+pub const DEFAULT_WINDOW: usize = 40;
+
+/// Renders `width` cells of the tape centered on the head, e.g. `[1203] … 1 0 I [I] O 0 … [1243]`,
+/// each symbol as `display` shows it.
+///
+/// The numbers are the absolute offsets of the window, start inclusive and end exclusive.
+/// An ellipsis marks a side where the tape continues past the window, and the head cell is bracketed.
+pub fn render_window<'a>(
+    tape: impl ExactSizeIterator<Item = &'a char>,
+    head: usize,
+    width: usize,
+    display: &DisplayMap,
+) -> String {
+    let len = tape.len();
+    let width = width.clamp(1, len.max(1));
+    let start = head.saturating_sub(width / 2).min(len - width.min(len));
+    let end = (start + width).min(len);
+
+    let mut cells = Vec::with_capacity(width + 2);
+    if start > 0 {
+        cells.push("…".to_string());
+    }
+    for (index, symbol) in tape.enumerate().skip(start).take(end - start) {
+        if index == head {
+            cells.push(format!("[{}]", display.show(*symbol)));
+        } else {
+            cells.push(display.show(*symbol));
+        }
+    }
+    if end < len {
+        cells.push("…".to_string());
+    }
+
+    format!("[{}] {} [{}]", start, cells.join(" "), end)
+}
+
+/// Renders the tape as a row of boxed cells on three lines, each symbol as `display` shows
+/// it and the head's cell drawn in heavy lines:
+///
+/// ```text
+/// ┌─┬─┲━┱─┐
+/// │_│1┃0┃1│
+/// └─┴─┺━┹─┘
+/// ```
+pub fn render_boxed<'a>(tape: impl ExactSizeIterator<Item = &'a char>, head: usize, display: &DisplayMap) -> String {
+    let len = tape.len();
+    // A border is the junctions between cells, from the left end to the right one, and
+    // the edges between them: `corners` are the plain and heavy left end, then right end;
+    // `tees` are the plain junction, then those heavy to the right and to the left.
+    let border = |corners: [char; 4], tees: [char; 3]| {
+        let mut line = String::new();
+        for boundary in 0..=len {
+            let (heavy_left, heavy_right) = (boundary == head + 1, boundary == head);
+            line.push(match (boundary == 0, boundary == len) {
+                (true, true) => continue,
+                (true, false) => corners[heavy_right as usize],
+                (false, true) => corners[2 + heavy_left as usize],
+                (false, false) if heavy_right => tees[1],
+                (false, false) if heavy_left => tees[2],
+                (false, false) => tees[0],
+            });
+            if boundary < len {
+                line.push(if heavy_right { '━' } else { '─' });
+            }
+        }
+        line
+    };
+    let mut cells = String::new();
+    for (index, symbol) in tape.enumerate() {
+        cells.push(if index == head || index == head + 1 { '┃' } else { '│' });
+        cells.push_str(&display.show(*symbol));
+    }
+    if len > 0 {
+        cells.push(if len == head + 1 { '┃' } else { '│' });
+    }
+    let top = border(['┌', '┏', '┐', '┓'], ['┬', '┲', '┱']);
+    let bottom = border(['└', '┗', '┘', '┛'], ['┴', '┺', '┹']);
+    format!("{}\n{}\n{}", top, cells, bottom)
+}
+
+/// Shows the tape before and after a run on two rows, aligned by logical cell, and marks
+/// every cell that changed with a `^` on a third:
+///
+/// ```text
+/// before: _1011+11_
+/// after:  _+++IIIO_
+///          ^^^^^^^
+/// ```
+///
+/// `grown_left` is how many cells the tape grew on the left (see
+/// `TuringMachine::grown_left`); the `before` row is padded by as many so its cells sit
+/// above the ones they became. A cell the tape grew counts as blank before the run. With
+/// `highlight`, the changed cells of the `after` row are also shown in reverse video.
+pub fn render_tape_diff(before: &[char], after: &[char], grown_left: usize, highlight: bool) -> String {
+    let was = |cell: usize| cell.checked_sub(grown_left).and_then(|cell| before.get(cell)).copied();
+    let changed = |cell: usize| after.get(cell).copied().unwrap_or('_') != was(cell).unwrap_or('_');
+    let width = after.len().max(before.len() + grown_left);
+    let mut text = String::from("before: ");
+    text.extend((0..width).map(|cell| was(cell).unwrap_or(' ')));
+    text.push_str("\nafter:  ");
+    for (cell, &symbol) in after.iter().enumerate() {
+        match highlight && changed(cell) {
+            true => write!(text, "\x1b[7m{}\x1b[0m", symbol).unwrap(),
+            false => text.push(symbol),
+        }
+    }
+    let marks: String = (0..width).map(|cell| if changed(cell) { '^' } else { ' ' }).collect();
+    write!(text, "\n        {}", marks.trim_end()).unwrap();
+    text
+}
+
+/// Renders a trace as the lines `Verbosity::Steps` prints with the full tape, one per
+/// snapshot, the last being the `Final Tape` line. Meant for comparing whole runs, e.g.
+/// against a golden file.
+pub fn trace_to_text(trace: &[Snapshot]) -> String {
+    let mut text = String::new();
+    for (index, snapshot) in trace.iter().enumerate() {
+        let prefix = if index + 1 == trace.len() { "Final Tape" } else { "Tape" };
+        writeln!(text, "{}: {:?}, Head: {}, State: {:?}", prefix, snapshot.tape, snapshot.head, snapshot.state).unwrap();
+    }
+    text
+}
+
+// Size of a tape cell in `trace_to_svg`, in pixels.
+const CELL: usize = 24;
+
+// How long each frame of `trace_to_svg` is shown, in seconds.
+const FRAME_SECONDS: f64 = 0.5;
+
+/// Renders a trace as an animated SVG: one frame per snapshot, showing the tape as a row
+/// of cells with the head's cell highlighted and the state and step above it. The
+/// animation loops, and frames are laid out on each snapshot's own tape, so a tape that
+/// grows to the left shifts by a cell.
+pub fn trace_to_svg(trace: &[Snapshot]) -> String {
+    let cells = trace.iter().map(|snapshot| snapshot.tape.len()).max().unwrap_or(0);
+    let (width, height) = (cells.max(1) * CELL + 2 * CELL, 3 * CELL);
+    let duration = FRAME_SECONDS * trace.len().max(1) as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"monospace\" font-size=\"16\">\n",
+        w = width,
+        h = height
+    );
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    for (step, snapshot) in trace.iter().enumerate() {
+        // Shown from this frame's share of the loop to the next one's.
+        let (from, to) = (step as f64 / trace.len() as f64, (step + 1) as f64 / trace.len() as f64);
+        svg.push_str("<g visibility=\"hidden\">\n");
+        svg.push_str(&format!(
+            "<animate attributeName=\"visibility\" values=\"hidden;visible;hidden\" keyTimes=\"0;{:.4};{:.4}\" \
+             dur=\"{}s\" calcMode=\"discrete\" repeatCount=\"indefinite\"/>\n",
+            from, to, duration
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\">step {}, state {}</text>\n",
+            CELL,
+            CELL * 2 / 3,
+            step,
+            escape(&snapshot.state.to_string())
+        ));
+        for (index, symbol) in snapshot.tape.iter().enumerate() {
+            let (x, y) = (CELL + index * CELL, CELL);
+            let fill = if index == snapshot.head { "#ffd54f" } else { "#f5f5f5" };
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{c}\" height=\"{c}\" fill=\"{}\" stroke=\"#616161\"/>\
+                 <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                x,
+                y,
+                fill,
+                x + CELL / 2,
+                y + CELL * 3 / 4,
+                escape(&symbol.to_string()),
+                c = CELL
+            ));
+        }
+        // A marker under the head's cell.
+        let x = CELL + snapshot.head * CELL + CELL / 2;
+        svg.push_str(&format!(
+            "<path d=\"M{} {} l-6 8 h12 z\" fill=\"#e65100\"/>\n",
+            x,
+            2 * CELL + 2
+        ));
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Escapes the characters XML gives a meaning to.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
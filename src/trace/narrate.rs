@@ -0,0 +1,79 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::tape::decimal::binary_to_decimal;
+use crate::{State, TuringMachine};
+
+impl TuringMachine {
+    /// Runs the adder until it halts, describing the addition bit by bit as it goes, e.g.
+    /// `Adding bit 0: 1 + 1 = 10, write 0 and carry 1.` Bits count from the right, from 0.
+    ///
+    /// A layer over `run_observing`: the sentences come from the rules that fire, so they
+    /// only make sense for the adder's rules.
+    pub fn narrate(&mut self) -> Vec<String> {
+        if self.operands().is_none() {
+            self.preserve_operands();
+        }
+        let (a, b) = self.operands().map(|(a, b)| (String::from(a), String::from(b))).unwrap_or_default();
+        let mut sentences = Vec::new();
+        sentences.push(format!("Adding {} ({}) and {} ({}).", or_zero(&a), decimal(&a), or_zero(&b), decimal(&b)));
+
+        let mut bit = 0; // The bit being added, once the first number's last digit is taken.
+        let mut digit = '0'; // That digit of the first number.
+        let mut carry = 0; // The bit a carry is rippling into.
+        self.run_observing(|from, read, _, _, _| match from {
+            State::GetLast => {
+                digit = read;
+            }
+            State::AddDigitZero | State::AddDigitOne => {
+                let other = if read == '+' { '0' } else { read };
+                let sum = (digit == '1') as u8 + (other == '1') as u8;
+                let outcome = if sum == 2 {
+                    String::from("10, write 0 and carry 1")
+                } else {
+                    format!("{}, carry 0", sum)
+                };
+                sentences.push(format!("Adding bit {}: {} + {} = {}.", bit, digit, other, outcome));
+                carry = bit + 1;
+                bit += 1;
+            }
+            State::Carry => {
+                let sentence = match read {
+                    '1' => format!("Carrying into bit {}: 1 + 1 = 10, write 0 and carry 1.", carry),
+                    '0' => format!("Carrying into bit {}: 0 + 1 = 1, carry 0.", carry),
+                    _ => format!("Carrying into bit {}, a new digit: 1.", carry),
+                };
+                sentences.push(sentence);
+                carry += 1;
+            }
+            _ => {}
+        });
+
+        let sum = self.result();
+        sentences.push(format!(
+            "Done: {} + {} = {} ({} + {} = {}).",
+            or_zero(&a),
+            or_zero(&b),
+            sum,
+            decimal(&a),
+            decimal(&b),
+            decimal(&sum)
+        ));
+        sentences
+    }
+}
+
+// An operand as written in a sentence; an empty one is zero.
+fn or_zero(bits: &str) -> &str {
+    if bits.is_empty() {
+        "0"
+    } else {
+        bits
+    }
+}
+
+// An operand in decimal, `?` if it isn't binary.
+fn decimal(bits: &str) -> String {
+    binary_to_decimal(or_zero(bits)).unwrap_or_else(|_| String::from("?"))
+}
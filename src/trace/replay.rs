@@ -0,0 +1,202 @@
+use alloc::borrow::Cow;
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::trace::formats::{self, Format};
+use crate::trace::json::Json;
+use crate::{decode_result, Direction, RunError, State, TuringMachine};
+
+pub use crate::error::TraceError;
+
+/// One step of a recorded run: the rule that fired and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEvent {
+    pub state: State,         // The state the machine was in.
+    pub head: isize,          // The cell under the head, counted from the first cell of the initial tape.
+    pub read: char,           // The symbol read there.
+    pub write: char,          // The symbol written over it.
+    pub direction: Direction, // Where the head moved.
+    pub next: State,          // The state the machine went to.
+}
+
+/// A run written down step by step, as a trace file holds it, to be checked without
+/// the rules that made it. The head is given as a cell of the initial tape, so cells
+/// grown on the left are negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub events: Vec<TraceEvent>,    // Every step, in order.
+    pub final_tape: Option<String>, // The tape the run claims to end with, blanks at both ends trimmed.
+    pub result: Option<String>,     // The answer the run claims, read the adder's way.
+}
+
+impl Trace {
+    /// Runs `machine` until it halts or `max_steps` rules have been applied, recording
+    /// every step, and the final tape and answer.
+    pub fn record(machine: &mut TuringMachine, max_steps: usize) -> Result<Trace, RunError> {
+        let mut events = Vec::new();
+        let mut head = machine.head() as isize;
+        while *machine.state() != State::Halt {
+            if machine.steps() >= max_steps {
+                return Err(RunError::StepLimitExceeded(max_steps));
+            }
+            let state = machine.state().clone();
+            let read = machine.tape().get(machine.head()).copied().unwrap_or('_');
+            let rule = machine.rule(&state, read).map(Cow::into_owned);
+            machine.step();
+            let Some((write, direction, next)) = rule else {
+                break;
+            };
+            let moved = head + offset(&direction);
+            events.push(TraceEvent {
+                state,
+                head,
+                read,
+                write,
+                direction,
+                next,
+            });
+            head = moved;
+        }
+        let tape: String = machine.tape().iter().collect();
+        Ok(Trace {
+            events,
+            final_tape: Some(tape.trim_matches('_').to_string()),
+            result: Some(machine.result()),
+        })
+    }
+
+    /// Replays the events on `initial_tape` and checks that they hold together: every
+    /// event starts in the state the one before went to, at the cell its move left the
+    /// head on, and reads what the tape holds there. Then checks the claimed final tape
+    /// and answer, if any, against the tape the events leave. Stops at the first problem.
+    pub fn verify_consistency(&self, initial_tape: &[char]) -> Result<(), TraceError> {
+        let mut tape: VecDeque<char> = initial_tape.iter().copied().collect();
+        let mut origin = 0; // Index on `tape` of the initial tape's first cell.
+        let mut head = self.events.first().map_or(0, |event| event.head);
+        let mut state: Option<&State> = None;
+        for (index, event) in self.events.iter().enumerate() {
+            if let Some(expected) = state.filter(|&expected| *expected != event.state) {
+                return Err(TraceError::State {
+                    event: index,
+                    expected: expected.clone(),
+                    found: event.state.clone(),
+                });
+            }
+            if event.head != head {
+                return Err(TraceError::Head {
+                    event: index,
+                    expected: head,
+                    found: event.head,
+                });
+            }
+            // Grow the tape with blanks until the head is on it.
+            while head + (origin as isize) < 0 {
+                tape.push_front('_');
+                origin += 1;
+            }
+            let cell = (head + origin as isize) as usize;
+            if cell >= tape.len() {
+                tape.resize(cell + 1, '_');
+            }
+            if tape[cell] != event.read {
+                return Err(TraceError::Read {
+                    event: index,
+                    expected: tape[cell],
+                    found: event.read,
+                });
+            }
+            tape[cell] = event.write;
+            head += offset(&event.direction);
+            state = Some(&event.next);
+        }
+
+        let found: String = tape.iter().collect();
+        let found = found.trim_matches('_');
+        if let Some(expected) = self.final_tape.as_ref().filter(|&expected| expected.trim_matches('_') != found) {
+            return Err(TraceError::FinalTape {
+                expected: expected.clone(),
+                found: found.to_string(),
+            });
+        }
+        let actual = decode_result(&tape);
+        match &self.result {
+            Some(claimed) if *claimed != actual => Err(TraceError::Result {
+                claimed: claimed.clone(),
+                actual,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// The trace as JSON, in a `tm-trace` envelope (see `formats`) around `{"events":
+    /// [{"state": "FindPlus", "head": 0, "read": "_", "write": "_", "move": "R", "next":
+    /// "FindPlus"}, ...], "final_tape": ..., "result": ...}`.
+    pub fn to_json(&self) -> Json {
+        let events: Vec<Json> = self
+            .events
+            .iter()
+            .map(|event| {
+                Json::object()
+                    .field("state", event.state.to_string())
+                    .field("head", Json::Int(event.head as i64))
+                    .field("read", event.read.to_string())
+                    .field("write", event.write.to_string())
+                    .field("move", event.direction.to_string())
+                    .field("next", event.next.to_string())
+            })
+            .collect();
+        let payload = Json::object()
+            .field("events", events)
+            .field("final_tape", self.final_tape.clone())
+            .field("result", self.result.clone());
+        formats::envelope(Format::Trace, payload)
+    }
+
+    /// Reads a trace written by `to_json`, or by a version before the envelope.
+    /// `final_tape` and `result` may be left out or null.
+    pub fn from_json(text: &str) -> Result<Trace, TraceError> {
+        let json: Json = text.parse().map_err(TraceError::Json)?;
+        let json = formats::open(Format::Trace, &json).map_err(TraceError::Format)?;
+        let top = |field| TraceError::Field { event: None, field };
+        let claim = |field| match json.get(field) {
+            None | Some(Json::Null) => Ok(None),
+            Some(value) => value.as_str().map(|text| Some(text.to_string())).ok_or(top(field)),
+        };
+        let (final_tape, result) = (claim("final_tape")?, claim("result")?);
+        let events = json.get("events").and_then(Json::as_array).ok_or(top("events"))?;
+        let events = events.iter().enumerate().map(|(index, event)| {
+            let error = |field| TraceError::Field { event: Some(index), field };
+            let text = |field| event.get(field).and_then(Json::as_str).ok_or(error(field));
+            let symbol = |field| {
+                let mut chars = text(field)?.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(symbol), None) => Ok(symbol),
+                    _ => Err(error(field)),
+                }
+            };
+            Ok(TraceEvent {
+                state: State::from_name(text("state")?),
+                head: event.get("head").and_then(Json::as_i64).ok_or(error("head"))? as isize,
+                read: symbol("read")?,
+                write: symbol("write")?,
+                direction: text("move")?.parse().map_err(|_| error("move"))?,
+                next: State::from_name(text("next")?),
+            })
+        });
+        Ok(Trace {
+            events: events.collect::<Result<_, _>>()?,
+            final_tape,
+            result,
+        })
+    }
+}
+
+// How far a move takes the head.
+fn offset(direction: &Direction) -> isize {
+    match direction {
+        Direction::Left => -1,
+        Direction::Right => 1,
+        Direction::Stay => 0,
+    }
+}
@@ -0,0 +1,103 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::trace::json::Json;
+
+/// Aggregates the outcomes of many runs, e.g. a batch.
+///
+/// Percentiles are exact: every value is kept and sorted when summarizing. At two
+/// `usize`s per run that is 16 bytes per entry, so even a million-entry batch costs
+/// about 16 MB, which beats the complexity and error of an approximate sketch.
+#[derive(Debug, Clone, Default)]
+pub struct AggregateStats {
+    steps: Vec<usize>,                 // Steps taken by each successful run.
+    tape_cells: Vec<usize>,            // Final tape length of each successful run.
+    failures: BTreeMap<String, usize>, // Number of failed runs, by category.
+}
+
+/// Minimum, median, 95th percentile and maximum of a series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Distribution {
+    pub min: usize,    // Smallest value.
+    pub median: usize, // 50th percentile.
+    pub p95: usize,    // 95th percentile.
+    pub max: usize,    // Largest value.
+}
+
+impl Distribution {
+    /// Summarizes `values` with nearest-rank percentiles, so every reported value is one
+    /// that occurred (for an even count the median is the lower middle value).
+    pub fn of(values: &[usize]) -> Option<Self> {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let rank = |percent: usize| sorted[(percent * sorted.len()).div_ceil(100).max(1) - 1];
+        Some(Distribution {
+            min: *sorted.first()?,
+            median: rank(50),
+            p95: rank(95),
+            max: *sorted.last()?,
+        })
+    }
+
+    /// The distribution as a JSON object.
+    pub fn to_json(&self) -> Json {
+        Json::object()
+            .field("min", self.min)
+            .field("median", self.median)
+            .field("p95", self.p95)
+            .field("max", self.max)
+    }
+}
+
+impl AggregateStats {
+    /// Starts with no runs recorded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a run that finished after `steps` steps with a tape of `tape_cells` cells,
+    /// if known (a cached result only remembers its steps).
+    pub fn record_success(&mut self, steps: usize, tape_cells: Option<usize>) {
+        self.steps.push(steps);
+        self.tape_cells.extend(tape_cells);
+    }
+
+    /// Records a run that failed, e.g. with category "invalid input" or "step limit".
+    pub fn record_failure(&mut self, category: &str) {
+        *self.failures.entry(String::from(category)).or_default() += 1;
+    }
+
+    /// Number of successful runs.
+    pub fn successes(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Number of failed runs, by category.
+    pub fn failures(&self) -> &BTreeMap<String, usize> {
+        &self.failures
+    }
+
+    /// Distribution of the steps taken by successful runs.
+    pub fn steps(&self) -> Option<Distribution> {
+        Distribution::of(&self.steps)
+    }
+
+    /// Distribution of the final tape lengths of successful runs.
+    pub fn tape_cells(&self) -> Option<Distribution> {
+        Distribution::of(&self.tape_cells)
+    }
+
+    /// The summary as a JSON object, the payload of a `tm-stats` artifact, see `formats`.
+    pub fn to_json(&self) -> Json {
+        let failures = self
+            .failures
+            .iter()
+            .fold(Json::object(), |object, (category, count)| object.field(category, *count));
+        Json::object()
+            .field("successes", self.successes())
+            .field("failures", failures)
+            .field("steps", self.steps().map_or(Json::Null, |d| d.to_json()))
+            .field("tape_cells", self.tape_cells().map_or(Json::Null, |d| d.to_json()))
+    }
+}
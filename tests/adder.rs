@@ -0,0 +1,76 @@
+use std::time::{Duration, Instant};
+
+use num_bigint::BigUint;
+use turing::prelude::*;
+use turing::testing::SplitMix64;
+
+// Runs the adder on `a+b` and returns the decoded sum.
+fn add(a: &str, b: &str) -> String {
+    let tape = build_tape(a, b, LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    machine.run_bounded(1_000_000).unwrap();
+    machine.result()
+}
+
+#[test]
+fn long_first_operand_short_second() {
+    assert_eq!(add("11111111", "1"), "100000000");
+    assert_eq!(add("1000000000000000", "1"), "1000000000000001");
+}
+
+#[test]
+fn short_first_operand_long_second() {
+    assert_eq!(add("1", "11111111"), "100000000");
+    assert_eq!(add("1", "1000000000000000"), "1000000000000001");
+}
+
+// `bits` random bits, the first one set so the operand is exactly that wide.
+fn random_operand(rng: &mut SplitMix64, bits: usize) -> String {
+    let rest: String = (1..bits).map(|_| if rng.next_u64() & 1 == 1 { '1' } else { '0' }).collect();
+    format!("1{}", rest)
+}
+
+#[test]
+fn adds_512_bit_operands_under_a_step_bound() {
+    let mut rng = SplitMix64::new(512);
+    let start = Instant::now();
+    for _ in 0..3 {
+        let (a, b) = (random_operand(&mut rng, 512), random_operand(&mut rng, 512));
+        let mut machine = TuringMachine::new(build_tape(&a, &b, LeadingZeros::Strip).unwrap(), rules::adder());
+        // About 3 * 512 * 512 steps, some 790,000, of scanning back and forth.
+        let steps = machine.run_bounded(2_000_000).unwrap();
+        let expected = BigUint::parse_bytes(a.as_bytes(), 2).unwrap() + BigUint::parse_bytes(b.as_bytes(), 2).unwrap();
+        assert_eq!(machine.result(), expected.to_str_radix(2), "{}+{} after {} steps", a, b, steps);
+    }
+    assert!(start.elapsed() < Duration::from_secs(60), "took {:?}", start.elapsed());
+}
+
+// The final tape of the adder run on `a+b`.
+fn final_tape(a: &str, b: &str) -> Tape {
+    let mut machine = TuringMachine::new(build_tape(a, b, LeadingZeros::Strip).unwrap(), rules::adder());
+    machine.run_bounded(10_000).unwrap();
+    machine.tape().clone()
+}
+
+#[test]
+fn chaining_adds_a_third_operand_to_the_sum() {
+    let previous = final_tape("1011", "11");
+    let mut machine = TuringMachine::chained(&previous, "1").unwrap();
+    assert_eq!(machine.tape().iter().collect::<String>(), "_1110+1_");
+    machine.run_bounded(10_000).unwrap();
+    assert_eq!(machine.result(), "1111");
+}
+
+#[test]
+fn chaining_nothing_keeps_the_sum() {
+    let mut machine = TuringMachine::chained(&final_tape("1011", "11"), "").unwrap();
+    machine.run_bounded(10_000).unwrap();
+    assert_eq!(machine.result(), "1110");
+}
+
+#[test]
+fn chaining_onto_a_blank_tape_starts_from_zero() {
+    let mut machine = TuringMachine::chained(&Tape::from(Vec::new()), "101").unwrap();
+    machine.run_bounded(10_000).unwrap();
+    assert_eq!(machine.result(), "101");
+}
@@ -0,0 +1,76 @@
+use turing::prelude::*;
+use turing::testing::adder_vectors;
+
+// Runs the adder on a + b, returning every step's state and column, the halted
+// configuration included.
+fn columns(a: &str, b: &str) -> Vec<(State, Option<usize>)> {
+    let mut machine = TuringMachine::new(build_tape(a, b, LeadingZeros::Strip).unwrap(), rules::adder());
+    let mut columns = vec![(machine.state().clone(), machine.current_bit_index())];
+    while machine.state() != &State::Halt {
+        machine.step();
+        columns.push((machine.state().clone(), machine.current_bit_index()));
+    }
+    columns
+}
+
+// The columns as `State:column` words, repeats dropped, `-` for none.
+fn phases(a: &str, b: &str) -> String {
+    let mut words: Vec<String> = columns(a, b)
+        .into_iter()
+        .map(|(state, column)| format!("{}:{}", state, column.map_or(String::from("-"), |column| column.to_string())))
+        .collect();
+    words.dedup();
+    words.join(" ")
+}
+
+#[test]
+fn columns_follow_the_addition_of_1011_and_11() {
+    let expected = "FindPlus:- GetLast:0 AddOne:0 AddDigitOne:0 Carry:1 Carry:2 BackToStart:- \
+                    FindPlus:- GetLast:1 AddOne:1 AddDigitOne:1 BackToStart:- \
+                    FindPlus:- GetLast:2 AddZero:2 AddDigitZero:2 BackToStart:- \
+                    FindPlus:- GetLast:3 AddOne:3 AddDigitOne:3 BackToStart:- \
+                    FindPlus:- GetLast:- Halt:-";
+    assert_eq!(phases("1011", "11"), expected);
+}
+
+#[test]
+fn a_carry_reaches_the_column_left_of_the_one_added() {
+    let expected = "FindPlus:- GetLast:0 AddOne:0 AddDigitOne:0 Carry:1 BackToStart:- \
+                    FindPlus:- GetLast:1 AddOne:1 AddDigitOne:1 Carry:2 BackToStart:- \
+                    FindPlus:- GetLast:2 AddOne:2 AddDigitOne:2 Carry:3 BackToStart:- \
+                    FindPlus:- GetLast:- Halt:-";
+    assert_eq!(phases("111", "1"), expected);
+}
+
+#[test]
+fn every_column_of_the_first_operand_is_worked_on_in_turn() {
+    for (a, b, _) in adder_vectors() {
+        let mut added = Vec::new();
+        let mut current = None;
+        for (state, column) in columns(&a, &b) {
+            match state {
+                State::FindPlus | State::BackToStart | State::Halt => {
+                    assert_eq!(column, None, "{}+{} {}", a, b, state)
+                }
+                State::Carry => assert!(column > current, "{}+{}: carry at {:?} from {:?}", a, b, column, current),
+                // No digit left to take: about to halt.
+                State::GetLast if column.is_none() => {}
+                _ => {
+                    current = Some(column.unwrap());
+                    added.push(column.unwrap());
+                }
+            }
+        }
+        added.dedup();
+        assert_eq!(added, (0..a.len()).collect::<Vec<_>>(), "{}+{}", a, b);
+    }
+}
+
+#[test]
+fn other_machines_have_no_column() {
+    let mut machine = TuringMachine::new("_11_".chars().collect(), rules::increment());
+    while machine.state() != &State::Halt {
+        assert_eq!(machine.current_bit_index(), None, "{}", machine.state());
+        machine.step();
+    }
+}
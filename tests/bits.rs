@@ -0,0 +1,36 @@
+use turing::prelude::*;
+
+// Runs the adder on two byte strings and reads the answer back as bytes.
+fn add_bytes(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut machine = TuringMachine::from_bytes(a, b, rules::adder());
+    machine.run_bounded(100_000).unwrap();
+    machine.result_bytes().unwrap()
+}
+
+#[test]
+fn bytes_plus_zero_come_back_unchanged() {
+    assert_eq!(add_bytes(&[0x12, 0x34], &[0x00]), [0x12, 0x34]);
+    assert_eq!(add_bytes(&[0x00], &[0x80, 0x01]), [0x80, 0x01]);
+}
+
+#[test]
+fn a_carry_out_of_the_top_byte_adds_a_byte() {
+    assert_eq!(add_bytes(&[0xff], &[0x01]), [0x01, 0x00]);
+}
+
+#[test]
+fn added_bits_pack_into_whole_bytes() {
+    let mut machine = TuringMachine::from_bits(&[true, false, true], &[false, true, true], rules::adder());
+    machine.run_bounded(10_000).unwrap();
+    assert_eq!(machine.result_bits(), Some(vec![true, false, false, false]));
+    assert_eq!(machine.result_bytes(), Some(vec![0x08]));
+}
+
+#[test]
+fn empty_operands_give_an_empty_answer() {
+    assert_eq!(add_bytes(&[], &[]), Vec::<u8>::new());
+
+    let mut machine = TuringMachine::from_bits(&[], &[], rules::adder());
+    machine.run_bounded(100).unwrap();
+    assert_eq!(machine.result_bits(), Some(Vec::new()));
+}
@@ -0,0 +1,93 @@
+use std::fs;
+
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::registry::bundle::{self, Bundle, BundleErrorKind};
+
+fn tm() -> Command {
+    Command::cargo_bin("tm").unwrap()
+}
+
+// What `tm export NAME` prints.
+fn export(name: &str) -> String {
+    let output = tm().args(["export", name]).output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// Writes `text` to a scratch file unique to this test process and returns its path.
+fn scratch(name: &str, text: &str) -> String {
+    let path = std::env::temp_dir().join(format!("tm-bundle-{}-{}.toml", std::process::id(), name));
+    fs::write(&path, text).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn the_exported_adder_is_the_adder() {
+    let original = Bundle::from_entry(registry::entry("add").unwrap());
+    let text = export("add");
+    assert_eq!(text, bundle::export(&original));
+    let imported = bundle::parse(&text).unwrap();
+    assert_eq!(imported, original);
+    assert_eq!(imported.definition.rules, rules::adder());
+    assert_eq!(imported.definition.start, State::FindPlus);
+    assert_eq!(imported.markers, [('I', '1'), ('O', '0')]);
+    assert_eq!(imported.examples.len(), 3);
+    assert_eq!(imported.check_examples(100_000), []);
+}
+
+#[test]
+fn the_imported_adder_runs_as_the_one_in_code() {
+    let imported = bundle::parse(&export("add")).unwrap();
+    for example in &imported.examples {
+        let tape: Vec<char> = example.input.chars().collect();
+        let mut loaded = imported.machine(tape.clone());
+        let mut built_in = TuringMachine::new(tape, rules::adder());
+        assert_eq!(loaded.run_bounded(100_000), built_in.run_bounded(100_000), "{}", example.input);
+        assert_eq!(loaded, built_in, "{}", example.input);
+        assert_eq!(loaded.result(), example.output);
+    }
+}
+
+#[test]
+fn every_built_in_machine_round_trips() {
+    for entry in registry::MACHINES {
+        let original = Bundle::from_entry(entry);
+        assert_eq!(bundle::parse(&bundle::export(&original)), Ok(original), "{}", entry.name);
+    }
+}
+
+#[test]
+fn wrong_examples_are_reported() {
+    let text = export("add").replace("output = \"1110\"", "output = \"1111\"");
+    let failures = bundle::parse(&text).unwrap().check_examples(100_000);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].to_string(), "example 1: expected 1111, got 1110");
+}
+
+#[test]
+fn broken_bundles_are_reported_by_line() {
+    let error = bundle::parse("name = \"x\"\ncolour = \"red\"\n").unwrap_err();
+    assert_eq!((error.line, error.kind), (2, BundleErrorKind::UnknownKey(String::from("colour"))));
+    assert_eq!(bundle::parse("name = \"x\"\n").unwrap_err().kind, BundleErrorKind::MissingMachine);
+    let blank = bundle::parse("blank = \" \"\n").unwrap_err();
+    assert_eq!(blank.to_string(), "line 1: the blank must be '_', not ' '");
+}
+
+#[test]
+fn tm_runs_and_verifies_an_exported_bundle() {
+    let path = scratch("add", &export("add"));
+    let output = tm().args(["run", "--bundle", &path, "--verify-bundle", "--input", "_1+1_"]).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("all 3 examples passed"));
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Result: 10"));
+
+    let wrong = scratch("wrong", &export("add").replace("output = \"0\"", "output = \"1\""));
+    let output = tm().args(["run", "--bundle", &wrong, "--verify-bundle", "--input", "_1+1_"]).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("example 2: expected 1, got 0"), "{}", stderr);
+    assert!(stderr.contains("1 of 3 examples failed"), "{}", stderr);
+    fs::remove_file(path).unwrap();
+    fs::remove_file(wrong).unwrap();
+}
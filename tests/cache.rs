@@ -0,0 +1,77 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::rules::Fingerprint;
+use turing::Tape;
+
+// A fresh directory in the temporary directory for `name`, unique to this test process.
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tm-cache-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Runs `tm batch` on `pairs` with the cache in `cache`, returning stdout and stderr.
+fn batch(pairs: &Path, cache: &Path) -> (String, String) {
+    let args = [pairs.to_str().unwrap(), "--cache", cache.to_str().unwrap()];
+    let output = Command::cargo_bin("tm").unwrap().arg("batch").args(args).output().unwrap();
+    assert!(output.status.success());
+    (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn second_batch_run_takes_no_machine_steps() {
+    let dir = scratch("batch");
+    let pairs = dir.join("pairs.csv");
+    fs::write(&pairs, "1,1\n10,11\n1011,11\n").unwrap();
+    let cache = dir.join("cache");
+
+    let (first, summary) = batch(&pairs, &cache);
+    assert_eq!(first, "a,b,sum,steps,cached\n1,1,10,12,no\n10,11,101,28,no\n1011,11,1110,62,no\n");
+    assert!(summary.starts_with("3 pairs, 0 cache hits, 102 machine steps executed\n"), "{}", summary);
+    let (second, summary) = batch(&pairs, &cache);
+    assert_eq!(second, first.replace(",no\n", ",yes\n"));
+    assert!(summary.starts_with("3 pairs, 3 cache hits, 0 machine steps executed\n"), "{}", summary);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn fingerprints_are_stable_and_ignore_insertion_order() {
+    let adder = rules::adder();
+    let mut entries: Vec<_> = adder.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+    entries.sort_by(|x, y| x.0.cmp(&y.0));
+    let forward: RuleTable = entries.iter().cloned().collect();
+    let reversed: RuleTable = entries.into_iter().rev().collect();
+    assert_eq!(forward.fingerprint(), adder.fingerprint());
+    assert_eq!(reversed.fingerprint(), adder.fingerprint());
+    assert_eq!(rules::adder().fingerprint(), adder.fingerprint());
+    assert_ne!(rules::increment().fingerprint(), adder.fingerprint());
+
+    let symbols: Vec<char> = "_1011+11_".chars().collect();
+    let tape: Tape = symbols.iter().copied().collect();
+    assert_eq!(tape.fingerprint(), symbols.fingerprint());
+    assert_ne!(symbols[1..].fingerprint(), symbols.fingerprint());
+}
+
+#[test]
+fn changed_rules_miss_the_cache() {
+    let dir = scratch("rules");
+    let pairs = dir.join("pairs.csv");
+    fs::write(&pairs, "1,1\n").unwrap();
+    let cache = dir.join("cache");
+    fs::create_dir_all(&cache).unwrap();
+    let tape = build_tape("1", "1", LeadingZeros::Strip).unwrap().fingerprint();
+    // An entry is a file named after the rules' and the tape's fingerprints.
+    let entry = |rules: &RuleTable| cache.join(format!("{:016x}-{:016x}", rules.fingerprint(), tape));
+
+    let mut changed = rules::adder();
+    changed.insert((State::Carry, '1'), ('O', Direction::Left, State::Carry));
+    fs::write(entry(&changed), "11 5\n").unwrap();
+    assert_eq!(batch(&pairs, &cache).0, "a,b,sum,steps,cached\n1,1,10,12,no\n");
+    fs::write(entry(&rules::adder()), "11 5\n").unwrap();
+    assert_eq!(batch(&pairs, &cache).0, "a,b,sum,steps,cached\n1,1,11,5,yes\n");
+    fs::remove_dir_all(dir).unwrap();
+}
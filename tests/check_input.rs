@@ -0,0 +1,80 @@
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::tape::input::{diagnose, render_diagnostics, validate_input, Diagnostic, ParseError};
+
+fn at(column: usize, problem: ParseError) -> Diagnostic {
+    Diagnostic { column, problem }
+}
+
+// Checks that `input` has exactly the `expected` diagnostics, the first of them being what
+// `parse_input` fails with.
+fn check(input: &str, expected: &[Diagnostic]) {
+    assert_eq!(diagnose(input), expected, "{:?}", input);
+    let first = parse_input(input, LeadingZeros::Strip).err();
+    assert_eq!(first.as_ref(), expected.first().map(|diagnostic| &diagnostic.problem), "{:?}", input);
+}
+
+#[test]
+fn a_well_formed_input_has_no_problems() {
+    check("1011+11", &[]);
+}
+
+#[test]
+fn single_problems() {
+    check("", &[at(1, ParseError::EmptyInput)]);
+    check("10a1+11", &[at(3, ParseError::InvalidSymbol('a'))]);
+    check("101", &[at(4, ParseError::MissingOperator)]);
+    check("+11", &[at(1, ParseError::MissingOperand { second: false })]);
+    check("11+", &[at(4, ParseError::MissingOperand { second: true })]);
+    check("1+1+1", &[at(4, ParseError::ExtraOperator)]);
+}
+
+#[test]
+fn every_problem_at_once() {
+    check("1++", &[at(3, ParseError::ExtraOperator), at(4, ParseError::MissingOperand { second: true })]);
+    check(
+        "+I 1+O",
+        &[
+            at(1, ParseError::MissingOperand { second: false }),
+            at(2, ParseError::ReservedMarker('I')),
+            at(3, ParseError::InvalidSymbol(' ')),
+            at(5, ParseError::ExtraOperator),
+            at(6, ParseError::ReservedMarker('O')),
+        ],
+    );
+}
+
+#[test]
+fn carets_point_at_each_problem() {
+    let input = "10a1+1O";
+    let rendered = render_diagnostics(input, &diagnose(input));
+    let expected = "  10a1+1O\n    ^   ^\ncolumn 3: 'a' is not a binary digit\ncolumn 7: ";
+    assert!(rendered.starts_with(expected), "{}", rendered);
+}
+
+#[test]
+fn inputs_without_an_operator_are_rejected_up_front() {
+    assert_eq!(validate_input(""), Err(ParseError::EmptyInput));
+    assert_eq!(validate_input("1010"), Err(ParseError::MissingOperator));
+    assert_eq!(parse_input("1010", LeadingZeros::Strip), Err(ParseError::MissingOperator));
+    for input in ["", "1010"] {
+        // Turned down before the machine is built, so no step limit is ever reached.
+        let output = Command::cargo_bin("tm").unwrap().args(["add", input, "--max-steps", "1"]).output().unwrap();
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert_eq!(output.status.code(), Some(2), "{:?}", input);
+        assert!(stderr.starts_with("error: invalid input\n"), "{:?}: {}", input, stderr);
+        assert!(!stderr.contains("steps"), "{:?}: {}", input, stderr);
+    }
+}
+
+#[test]
+fn operands_holding_the_adders_markers_are_rejected() {
+    check("1O1+11", &[at(2, ParseError::ReservedMarker('O'))]);
+    check("1+I", &[at(3, ParseError::ReservedMarker('I'))]);
+    assert_eq!(build_tape("1O1", "11", LeadingZeros::Strip), Err(ParseError::ReservedMarker('O')));
+    let output = Command::cargo_bin("tm").unwrap().args(["add", "1O1+11"]).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty(), "the sum was printed anyway");
+    assert!(stderr.contains("column 2: 'O' is not a binary digit, and is reserved"), "{}", stderr);
+}
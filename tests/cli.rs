@@ -0,0 +1,278 @@
+use assert_cmd::Command;
+use turing::registry;
+
+fn tm() -> Command {
+    Command::cargo_bin("tm").unwrap()
+}
+
+// Runs `tm` with `args`, expecting it to fail with `stderr` containing `message`.
+fn fails_with(args: &[&str], message: &str) {
+    let output = tm().args(args).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success(), "tm {:?} succeeded", args);
+    assert!(stderr.contains(message), "tm {:?}: {}", args, stderr);
+}
+
+#[test]
+fn add_checks_both_operand_forms_alike() {
+    for args in [&["add", "", ""][..], &["add", "+"]] {
+        fails_with(args, "column 1: the first operand before the '+' is missing");
+        fails_with(args, "column 2: the second operand after the '+' is missing");
+    }
+    fails_with(&["add", "", "1"], "column 1: the first operand before the '+' is missing");
+    fails_with(&["add", "+1"], "column 1: the first operand before the '+' is missing");
+    fails_with(&["add", "1", "1a"], "column 4: 'a' is not a binary digit");
+    fails_with(&["add", "--decimal", "", "1"], "the first operand before the '+' is missing");
+}
+
+#[test]
+fn check_input_lists_every_problem() {
+    let assert = tm().args(["check-input", "10a1+1O"]).assert().code(1);
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("column 3: 'a' is not a binary digit"), "{}", stdout);
+    assert!(stdout.contains("column 7: "), "{}", stdout);
+    tm().args(["check-input", "1011+11"]).assert().success();
+}
+
+// Runs `tm` with `args`, expecting it to succeed, and returns what it printed.
+fn succeeds(args: &[&str]) -> String {
+    let output = tm().args(args).output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "tm {:?} failed: {}", args, stderr);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// A path in the temporary directory for `name`, unique to this test process.
+fn scratch(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("tm-cli-{}-{}", std::process::id(), name));
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn add() {
+    assert_eq!(succeeds(&["add", "1011", "11"]), "1110\n");
+    assert_eq!(succeeds(&["add", "1011+11"]), "1110\n");
+    assert_eq!(succeeds(&["add", "--decimal", "11", "3"]), "14\n");
+    fails_with(&["add", "1011", "11", "1"], "Usage: tm add");
+}
+
+#[test]
+fn old_positional_style_still_adds() {
+    let output = tm().arg("1011+11").output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "1110\n");
+    assert!(String::from_utf8(output.stderr).unwrap().contains("use `tm add 1011+11`"));
+    fails_with(&["1011", "11"], "unknown command '1011'");
+}
+
+#[test]
+fn trace() {
+    let stdout = succeeds(&["trace", "1+1"]);
+    assert_eq!(stdout.lines().filter(|line| line.starts_with("Tape: ")).count(), 13);
+    assert!(stdout.contains("Result: 1 + 1 = 10"), "{}", stdout);
+    fails_with(&["trace"], "Usage: tm trace A+B");
+    fails_with(&["trace", "1+x"], "invalid input");
+}
+
+#[test]
+fn verify_trace() {
+    let path = scratch("trace.json");
+    succeeds(&["trace", "1+1", "--json", &path]);
+    assert!(succeeds(&["verify-trace", &path, "--input", "1+1"]).contains("12 steps, consistent"));
+    fails_with(&["verify-trace", &path, "--input", "1+0"], "the cell holds '0' but the event read '1'");
+    fails_with(&["verify-trace", "/nonexistent/trace.json", "--input", "1+1"], "could not read");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn run() {
+    let stdout = succeeds(&["run", "--machine", "add", "--input", "_1011+11_"]);
+    assert!(stdout.contains("Result: 1110"), "{}", stdout);
+    let rules = concat!(env!("CARGO_MANIFEST_DIR"), "/machines/increment.tm");
+    assert!(succeeds(&["run", "--rules", rules, "--input", "_1011_"]).contains("Result: 1100"));
+    fails_with(&["run", "--machine", "nope", "--input", "_1_"], "unknown machine 'nope', see `tm list`");
+}
+
+#[test]
+fn rules() {
+    assert!(succeeds(&["rules"]).contains("AddDigitOne 1 O L Carry"));
+    assert!(succeeds(&["rules", "dot"]).starts_with("digraph turing {"));
+    fails_with(&["rules", "nope"], "unknown rule format 'nope'");
+}
+
+#[test]
+fn list() {
+    let stdout = succeeds(&["list"]);
+    for name in ["add", "increment", "double"] {
+        assert!(stdout.lines().any(|line| line.split_whitespace().next() == Some(name)), "{}", stdout);
+    }
+    fails_with(&["list", "add"], "Usage: tm list");
+}
+
+#[test]
+fn export() {
+    assert!(succeeds(&["export", "add"]).starts_with("name = \"add\"\n"));
+    fails_with(&["export", "adder"], "unknown machine 'adder', did you mean 'add'?");
+}
+
+#[test]
+fn batch() {
+    let path = scratch("pairs.csv");
+    std::fs::write(&path, "1,1\n10,11\n").unwrap();
+    let stdout = succeeds(&["batch", &path]);
+    assert!(stdout.starts_with("a,b,sum,steps,cached\n1,1,10,12,no\n10,11,101,28,no\n"), "{}", stdout);
+    std::fs::remove_file(&path).unwrap();
+    fails_with(&["batch", &path], "could not read");
+}
+
+#[test]
+fn complexity() {
+    assert!(succeeds(&["complexity"]).lines().any(|line| line.split_whitespace().eq(["3", "50"])));
+    fails_with(&["complexity", "--bogus"], "unknown option '--bogus'");
+}
+
+#[test]
+fn mutate() {
+    assert!(succeeds(&["mutate"]).contains("killed 100 of 100 mutants"));
+    fails_with(&["mutate", "--seeds", "x"], "unknown option '--seeds'");
+}
+
+#[test]
+fn check() {
+    assert!(succeeds(&["check", "--property", "no-write-left"]).contains("no-write-left holds up to depth 10000"));
+    fails_with(&["check", "--property", "no-write:+"], "no-write:+ is broken at step 4");
+    fails_with(&["check", "--property", "nope"], "'nope' is not a property");
+}
+
+#[test]
+fn gen_vectors_and_verify_vectors() {
+    let path = scratch("vectors.json");
+    std::fs::write(&path, succeeds(&["gen-vectors", "--count", "3"])).unwrap();
+    assert_eq!(succeeds(&["verify-vectors", &path]), "3 of 3 vectors passed\n");
+    std::fs::remove_file(&path).unwrap();
+    fails_with(&["gen-vectors", "--count", "x"], "--count expects a number, got 'x'");
+    fails_with(&["verify-vectors", &path], "could not read");
+}
+
+#[test]
+fn compare_live() {
+    let stdout = succeeds(&["compare-live", "--machines", "add,add", "--input", "1+1"]);
+    assert!(stdout.ends_with("the answers never differed\n"), "{}", stdout);
+    fails_with(&["compare-live", "--machines", "add", "--input", "1+1"], "--machines expects two machines");
+}
+
+#[test]
+fn demo() {
+    assert!(succeeds(&["demo"]).contains("1111 + 1 = 10000"));
+    assert_eq!(succeeds(&[]), succeeds(&["demo"]));
+    fails_with(&["demo", "nope"], "unknown demo 'nope'");
+}
+
+#[test]
+fn completions() {
+    assert!(succeeds(&["completions", "bash"]).contains("complete -o default -F _tm tm"));
+    fails_with(&["completions", "tcsh"], "Usage: tm completions bash|zsh|fish");
+}
+
+#[test]
+fn run_reads_the_tape_from_stdin_as_from_an_argument() {
+    let from_argument = succeeds(&["run", "--machine", "add", "--input", "_1011+11_"]);
+    for piped in ["_1011+11_", "_1011+11_\n", "_1011+11_\r\n\n"] {
+        let assert = tm().args(["run", "--machine", "add", "--input-file", "-"]).write_stdin(piped).assert().success();
+        assert_eq!(String::from_utf8(assert.get_output().stdout.clone()).unwrap(), from_argument);
+    }
+    let path = scratch("tape.txt");
+    std::fs::write(&path, "_1011+11_\n").unwrap();
+    assert_eq!(succeeds(&["run", "--machine", "add", "--input-file", &path]), from_argument);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn run_rejects_an_empty_or_broken_tape_on_stdin() {
+    for (piped, message) in [("", "stdin holds no tape"), ("_1+\n1_\n", "the tape has a line break after cell 3")] {
+        let assert = tm().args(["run", "--machine", "add", "--input-file", "-"]).write_stdin(piped).assert().code(2);
+        let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+        assert!(stderr.contains(message), "{:?}: {}", piped, stderr);
+    }
+}
+
+// The commands `tm --help` lists, which the completion scripts must all offer.
+fn commands() -> Vec<String> {
+    let usage = succeeds(&["--help"]);
+    let listing = usage.split("Commands:\n").nth(1).unwrap().split("\n\n").next().unwrap();
+    listing.lines().map(|line| line.split_whitespace().next().unwrap().to_string()).collect()
+}
+
+#[test]
+fn bash_completions_offer_every_command() {
+    let output = tm().args(["completions", "bash"]).output().unwrap();
+    assert!(output.status.success() && output.stderr.is_empty());
+    let script = String::from_utf8(output.stdout).unwrap();
+    let commands = commands();
+    assert!(commands.len() >= 17, "{:?}", commands);
+    let first_words = script.lines().find(|line| line.contains("compgen -W \"add ")).unwrap();
+    for command in &commands {
+        assert!(first_words.split(['"', ' ']).any(|word| word == command), "{} is not offered", command);
+        assert!(script.contains(&format!("        {}) COMPREPLY=", command)), "{} has no case", command);
+    }
+}
+
+#[test]
+fn zsh_and_fish_completions_offer_every_command() {
+    for shell in ["zsh", "fish"] {
+        let script = succeeds(&["completions", shell]);
+        for command in commands() {
+            assert!(script.contains(&command), "{} doesn't offer {}", shell, command);
+        }
+    }
+}
+
+#[test]
+fn every_shell_completes_machine_with_every_registered_machine() {
+    // Where each script offers the names after `--machine`.
+    let machine_words = |shell: &str, script: &str| -> Vec<String> {
+        let line = match shell {
+            "bash" => script.lines().skip_while(|line| !line.contains("--machine ]")).nth(1),
+            "zsh" => script.lines().skip_while(|line| !line.contains("--machine ]]")).nth(1),
+            _ => script.lines().find(|line| line.starts_with("complete -c tm -l machine ")),
+        };
+        let line = line.unwrap_or_else(|| panic!("{} doesn't complete --machine", shell));
+        line.split(['"', '\'', ' ', '(', ')']).map(String::from).collect()
+    };
+    assert!(registry::names().count() >= 4);
+    for shell in ["bash", "zsh", "fish"] {
+        let script = succeeds(&["completions", shell]);
+        let words = machine_words(shell, &script);
+        for name in registry::names() {
+            assert!(words.iter().any(|word| word == name), "{} doesn't offer {} after --machine", shell, name);
+        }
+    }
+}
+
+#[test]
+fn batm_max_steps_limits_a_run() {
+    let output = tm().env("BATM_MAX_STEPS", "10").args(["add", "1011", "11"]).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("the machine did not halt within 10 steps"), "{}", stderr);
+
+    tm().env("BATM_MAX_STEPS", "100").args(["add", "1011", "11"]).assert().success().stdout("1110\n");
+}
+
+#[test]
+fn max_steps_overrides_batm_max_steps() {
+    let assert = tm().env("BATM_MAX_STEPS", "10").args(["add", "--max-steps", "100", "1011", "11"]).assert();
+    assert.success().stdout("1110\n");
+
+    let output = tm().env("BATM_MAX_STEPS", "100").args(["add", "--max-steps", "10", "1011", "11"]).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("the machine did not halt within 10 steps"), "{}", stderr);
+}
+
+#[test]
+fn batm_max_steps_must_be_a_number() {
+    let output = tm().env("BATM_MAX_STEPS", "lots").args(["add", "1011", "11"]).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("BATM_MAX_STEPS expects a number, got 'lots'"), "{}", stderr);
+}
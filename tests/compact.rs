@@ -0,0 +1,78 @@
+use assert_cmd::Command;
+use turing::error::ConfigParseError;
+use turing::prelude::*;
+use turing::rules::parse_rules;
+use turing::tape::parse_configuration;
+
+// Every configuration of the adder's run on `a+b`, from the first to the halted one.
+fn configurations(a: &str, b: &str) -> Vec<Snapshot> {
+    let mut machine = TuringMachine::new(build_tape(a, b, LeadingZeros::Strip).unwrap(), rules::adder());
+    let mut snapshots = vec![machine.snapshot()];
+    while machine.halt_reason().is_none() {
+        machine.step();
+        snapshots.push(machine.snapshot());
+    }
+    snapshots
+}
+
+#[test]
+fn configurations_round_trip_through_both_syntaxes() {
+    for (a, b) in [("1011", "11"), ("1", "1"), ("111", "1"), ("0", "0")] {
+        let mut machine = TuringMachine::new(build_tape(a, b, LeadingZeros::Strip).unwrap(), rules::adder());
+        for expected in configurations(a, b) {
+            let configuration = machine.configuration();
+            let bracketed = configuration.to_compact_string();
+            assert_eq!(bracketed, expected.to_compact_string());
+            assert_eq!(parse_configuration(&bracketed, &rules::adder()), Ok(expected.clone()), "{}", bracketed);
+            let caret = configuration.to_caret_string();
+            assert_eq!(parse_configuration(&caret, &rules::adder()), Ok(expected.clone()), "{}", caret);
+            machine.step();
+        }
+    }
+}
+
+#[test]
+fn both_syntaxes_read_the_same_configuration() {
+    let expected = Snapshot {
+        tape: "10101+11".chars().collect(),
+        head: 4,
+        state: State::GetLast,
+        partial_value: None,
+    };
+    assert_eq!(expected.to_compact_string(), "GetLast 1010[1]+11");
+    assert_eq!(parse_configuration("GetLast 1010[1]+11", &rules::adder()), Ok(expected.clone()));
+    assert_eq!(parse_configuration("GetLast:10101+11\n            ^", &rules::adder()), Ok(expected));
+}
+
+#[test]
+fn numbered_states_of_loaded_machines_are_known() {
+    let rules = parse_rules("0 _ _ R 3\n3 1 1 R 3\n3 _ _ L Halt\n").unwrap();
+    let snapshot = parse_configuration("3 _1[1]_", &rules).unwrap();
+    assert_eq!((snapshot.head, snapshot.state), (2, State::Named("3".to_string())));
+    assert_eq!(parse_configuration("4 _1[1]_", &rules), Err(ConfigParseError::UnknownState("4".to_string())));
+}
+
+#[test]
+fn malformed_configurations_are_rejected() {
+    let parse = |text: &str| parse_configuration(text, &rules::adder());
+    assert_eq!(parse("GetLast 10101+11"), Err(ConfigParseError::HeadMarkers(0)));
+    assert_eq!(parse("GetLast [1]0101+1[1]"), Err(ConfigParseError::HeadMarkers(2)));
+    assert_eq!(parse("GetLast 1[01"), Err(ConfigParseError::UnclosedBracket));
+    assert_eq!(parse("[1]0101+11"), Err(ConfigParseError::MissingState));
+    assert_eq!(parse("Nope 1[0]"), Err(ConfigParseError::UnknownState("Nope".to_string())));
+    assert_eq!(parse("GetLast:10\n              ^"), Err(ConfigParseError::CaretOffTape(14)));
+    assert_eq!(parse("GetLast:10\n        x"), Err(ConfigParseError::CaretLine('x')));
+}
+
+// Runs `tm run --machine add --resume-config <config>`.
+fn resume(config: &str) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("tm").unwrap().args(["run", "--machine", "add", "--resume-config", config]).assert()
+}
+
+#[test]
+fn tm_run_resumes_a_pasted_configuration() {
+    let stdout = String::from_utf8(resume("GetLast _1010[1]+11_").success().get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("Result: 11000\n"), "{}", stdout);
+    let stderr = String::from_utf8(resume("GetLast _10101+11_").failure().get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("--resume-config: the configuration has no head marker"), "{}", stderr);
+}
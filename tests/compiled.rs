@@ -0,0 +1,147 @@
+use turing::error::CompileError;
+use turing::prelude::*;
+use turing::testing::SplitMix64;
+use turing::testing::{adder_vectors, random_input};
+use turing::{OnSentinel, Sentinels};
+
+// The interpreted adder and the compiled one, with and without scanning, run to the end
+// on `a+b`, returning each one's result, steps and tape.
+fn all_three(a: &str, b: &str) -> [(String, usize, String); 3] {
+    let tape = build_tape(a, b, LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape.clone(), rules::adder());
+    machine.run();
+    let mut dense = TuringMachine::new(tape.clone(), rules::adder()).compile().unwrap();
+    dense.set_scanning(false);
+    dense.run();
+    let mut scanning = TuringMachine::new(tape, rules::adder()).compile().unwrap();
+    scanning.run();
+    [
+        (machine.result(), machine.steps(), machine.tape().iter().collect()),
+        (dense.result(), dense.steps(), dense.tape().iter().collect()),
+        (scanning.result(), scanning.steps(), scanning.tape().iter().collect()),
+    ]
+}
+
+#[test]
+fn compiled_machines_match_the_interpreter_on_every_vector() {
+    for (a, b, sum) in adder_vectors() {
+        let [interpreted, dense, scanning] = all_three(&a, &b);
+        assert_eq!(interpreted.0, sum, "{}+{}", a, b);
+        assert_eq!(dense, interpreted, "{}+{}: without scanning", a, b);
+        assert_eq!(scanning, interpreted, "{}+{}: scanning", a, b);
+    }
+}
+
+#[test]
+fn compiled_machines_match_the_interpreter_on_random_inputs() {
+    let mut rng = SplitMix64::new(103);
+    for _ in 0..100 {
+        let input = random_input(&mut rng, 24);
+        let (a, b) = input.split_once('+').unwrap();
+        let [interpreted, dense, scanning] = all_three(a, b);
+        assert_eq!(dense, interpreted, "{}", input);
+        assert_eq!(scanning, interpreted, "{}", input);
+    }
+}
+
+#[test]
+fn large_addition_matches_the_interpreter() {
+    // The benchmark's operands, shortened to keep the interpreter quick under debug.
+    let a: String = (0..256).map(|i| if i % 3 == 0 { '0' } else { '1' }).collect();
+    let b: String = (0..256).map(|i| if i % 5 == 0 { '0' } else { '1' }).collect();
+    let [interpreted, dense, scanning] = all_three(&a, &b);
+    assert_eq!(dense, interpreted);
+    assert_eq!(scanning, interpreted);
+}
+
+#[test]
+fn step_limit_counts_scanned_rules() {
+    let tape = build_tape("1011", "11", LeadingZeros::Strip).unwrap();
+    let mut scanning = TuringMachine::new(tape.clone(), rules::adder()).compile().unwrap();
+    assert_eq!(scanning.run_bounded(20), Err(RunError::StepLimitExceeded(20)));
+    assert_eq!(scanning.steps(), 20);
+    // Like the interpreter, finding no rule to apply after the last one takes the budget of a step.
+    let mut machine = TuringMachine::new(tape.clone(), rules::adder());
+    let mut scanning = TuringMachine::new(tape, rules::adder()).compile().unwrap();
+    assert_eq!(scanning.run_bounded(62), machine.run_bounded(62));
+    assert_eq!(scanning.run_bounded(63), Ok(62));
+}
+
+#[test]
+fn traced_compiled_runs_match_the_interpreters_trace() {
+    for (a, b) in [("1011", "11"), ("0", "0"), ("111111", "1")] {
+        let tape = build_tape(a, b, LeadingZeros::Strip).unwrap();
+        let mut machine = TuringMachine::new(tape.clone(), rules::adder());
+        machine.set_tracing(true);
+        machine.run();
+        for scanning in [false, true] {
+            let mut compiled = TuringMachine::new(tape.clone(), rules::adder()).compile().unwrap();
+            compiled.set_scanning(scanning);
+            compiled.set_tracing(true);
+            compiled.run();
+            assert_eq!(compiled.trace(), machine.trace(), "{}+{}, scanning {}", a, b, scanning);
+            assert_eq!(compiled.trace().len(), compiled.steps() + 2, "{}+{}, scanning {}", a, b, scanning);
+        }
+    }
+}
+
+#[test]
+fn compiled_runs_record_nothing_unless_traced() {
+    let tape = build_tape("1011", "11", LeadingZeros::Strip).unwrap();
+    let mut compiled = TuringMachine::new(tape.clone(), rules::adder()).compile().unwrap();
+    compiled.run();
+    assert!(compiled.trace().is_empty());
+    compiled.set_tracing(true);
+    compiled.reset_with_input(tape);
+    compiled.run();
+    assert_eq!(compiled.trace().len(), 64);
+}
+
+#[test]
+fn settings_that_change_how_the_machine_steps_refuse_to_compile() {
+    let adder = || TuringMachine::new(build_tape("1011", "11", LeadingZeros::Strip).unwrap(), rules::adder());
+    let mut fixed = adder();
+    fixed.set_tape_mode(TapeMode::Fixed);
+    assert_eq!(fixed.compile().err(), Some(CompileError::TapeMode(TapeMode::Fixed)));
+    let mut limited = adder();
+    limited.set_max_tape_len(Some(100)).unwrap();
+    assert_eq!(limited.compile().err(), Some(CompileError::TapeLimit(100)));
+    let mut bounded = adder();
+    bounded.set_sentinels(Some(Sentinels { left: '<', right: '>', on_contact: OnSentinel::Halt }));
+    assert_eq!(bounded.compile().err(), Some(CompileError::Sentinels));
+    let mut consulting = adder();
+    consulting.set_oracle(State::Carry, |_, _| {});
+    let error = consulting.compile().err().unwrap();
+    assert_eq!(error, CompileError::Oracle(State::Carry));
+    assert_eq!(error.to_string(), "the machine has an oracle on Carry, which a compiled machine doesn't support");
+}
+
+#[test]
+fn compiled_machines_keep_the_result_strategy_and_final_states() {
+    let tape = build_tape("1011", "11", LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape.clone(), rules::adder());
+    machine.set_result_strategy(ResultStrategy::AcceptStateOnly { accept: vec![State::GetLast] });
+    let mut compiled = machine.compile().unwrap();
+    compiled.run();
+    assert_eq!(compiled.halted_in(), Some(&State::GetLast));
+    assert!(compiled.finished_successfully());
+    assert_eq!(compiled.result(), "accept");
+
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    machine.set_final_states(vec![State::Halt]);
+    machine.set_result_strategy(ResultStrategy::WholeTapeTrimmed);
+    let mut compiled = machine.compile().unwrap();
+    compiled.run();
+    assert!(!compiled.finished_successfully());
+    assert_eq!(compiled.result(), "+++IIIO");
+}
+
+#[test]
+#[ignore = "the benchmark's 4096-bit operands; run with `cargo test --release -- --ignored`"]
+fn benchmark_addition_matches_the_interpreter() {
+    let a: String = (0..4096).map(|i| if i % 3 == 0 { '0' } else { '1' }).collect();
+    let b: String = (0..4096).map(|i| if i % 5 == 0 { '0' } else { '1' }).collect();
+    let [interpreted, dense, scanning] = all_three(&a, &b);
+    assert_eq!(dense, interpreted);
+    assert_eq!(scanning, interpreted);
+}
@@ -0,0 +1,49 @@
+use assert_cmd::Command;
+use turing::machine::complexity::{adder_step_bound, fitted_exponent, measure_adder, to_csv, Sample};
+use turing::prelude::*;
+use turing::testing::adder_vectors;
+
+// The steps all-ones 8-bit operands take with the current rules. A change to the rules
+// that alters it changes the adder's complexity, and has to update this on purpose.
+const ALL_ONES_8_BIT_STEPS: usize = 250;
+
+#[test]
+fn all_ones_8_bit_operands_take_a_known_number_of_steps() {
+    let tape = build_tape("11111111", "11111111", LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    assert_eq!(machine.run_bounded(10_000), Ok(ALL_ONES_8_BIT_STEPS));
+    assert!(measure_adder(64, 8).contains(&Sample { bits: 8, steps: ALL_ONES_8_BIT_STEPS }));
+}
+
+#[test]
+fn steps_grow_about_quadratically() {
+    let samples = measure_adder(64, 20);
+    assert_eq!(samples.len(), 20);
+    assert!(samples.windows(2).all(|pair| pair[0].bits < pair[1].bits && pair[0].steps < pair[1].steps));
+    let exponent = fitted_exponent(&samples).unwrap();
+    assert!((1.8..2.2).contains(&exponent), "{}", exponent);
+    assert_eq!(fitted_exponent(&samples[..1]), None);
+}
+
+#[test]
+fn step_bound_is_never_exceeded() {
+    for (a, b, _) in adder_vectors() {
+        let tape = build_tape(&a, &b, LeadingZeros::Strip).unwrap();
+        let bound = adder_step_bound(&tape).unwrap();
+        let steps = TuringMachine::new(tape, rules::adder()).run_bounded(bound).unwrap();
+        assert!(steps <= bound, "{}+{}: {} steps over the bound {}", a, b, steps, bound);
+    }
+    assert_eq!(adder_step_bound(&['_', '1', '0', '_']), None);
+}
+
+#[test]
+fn samples_export_as_csv() {
+    let samples = [Sample { bits: 1, steps: 12 }, Sample { bits: 8, steps: 250 }];
+    assert_eq!(to_csv(&samples), "bits,steps\n1,12\n8,250\n");
+    let path = std::env::temp_dir().join(format!("tm-complexity-{}.csv", std::process::id()));
+    let path = path.to_string_lossy().into_owned();
+    let args = ["complexity", "--max-bits", "16", "--samples", "2", "--csv", &path];
+    Command::cargo_bin("tm").unwrap().args(args).assert().success();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), to_csv(&measure_adder(16, 2)));
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,99 @@
+use turing::prelude::*;
+use turing::rules::compose::{Compose, ComposeError};
+
+fn named(name: &str) -> State {
+    State::Named(String::from(name))
+}
+
+// Rewrites the adder's final tape, e.g. `_+++IIIO_`, as a plain number and stops in
+// `Unmarked` on its last digit, as in examples/add_then_increment.rs.
+fn unmark() -> RuleTable {
+    let mut rules = RuleTable::new();
+    for symbol in ['0', '1', '+', 'I', 'O'] {
+        rules.insert((named("Rewind"), symbol), (symbol, Direction::Left, named("Rewind")));
+    }
+    rules.insert((named("Rewind"), '_'), ('_', Direction::Right, named("Unmark")));
+    for (read, write) in [('0', '0'), ('1', '1'), ('+', '_'), ('I', '1'), ('O', '0')] {
+        rules.insert((named("Unmark"), read), (write, Direction::Right, named("Unmark")));
+    }
+    rules.insert((named("Unmark"), '_'), ('_', Direction::Left, named("Unmarked")));
+    rules
+}
+
+// The adder, `unmark` and the incrementer merged into one machine.
+fn add_then_increment() -> RuleTable {
+    let increment = rules::increment().namespaced("inc.");
+    rules::adder()
+        .namespaced("add.")
+        .merge(&unmark(), &[(named("add.GetLast"), named("Rewind"))])
+        .and_then(|rules| rules.merge(&increment, &[(named("Unmarked"), named("inc.Increment"))]))
+        .unwrap()
+}
+
+// Runs the merged machine on a + b and returns its result as a number.
+fn run(rules: &RuleTable, a: u64, b: u64) -> u64 {
+    let tape = build_tape(&format!("{:b}", a), &format!("{:b}", b), LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape, rules.clone());
+    machine.set_state(named("add.FindPlus"));
+    machine.set_final_states(vec![named("inc.Done")]);
+    machine.set_result_strategy(ResultStrategy::WholeTapeTrimmed);
+    machine.run_bounded(1_000_000).unwrap();
+    assert!(matches!(machine.halt_reason(), Some(HaltReason::Finished(_))), "{:?}", machine.halt_reason());
+    u64::from_str_radix(&machine.result(), 2).unwrap()
+}
+
+#[test]
+fn add_then_increment_computes_the_sum_plus_one() {
+    let rules = add_then_increment();
+    for a in 0..64 {
+        for b in 0..64 {
+            assert_eq!(run(&rules, a, b), a + b + 1, "{}+{}+1", a, b);
+        }
+    }
+    let long = u64::from(u32::MAX);
+    for (a, b) in [(long, 1), (1, long), (long, long)] {
+        assert_eq!(run(&rules, a, b), a + b + 1, "{}+{}+1", a, b);
+    }
+}
+
+#[test]
+fn namespacing_renames_every_state() {
+    let rules = rules::adder().namespaced("add.");
+    assert_eq!(rules.len(), rules::adder().len());
+    for ((state, _), (_, _, next)) in rules.iter() {
+        assert!(state.to_string().starts_with("add."), "{}", state);
+        assert!(next.to_string().starts_with("add."), "{}", next);
+    }
+    assert!(rules.contains_key(&(named("add.FindPlus"), '+')));
+}
+
+#[test]
+fn merging_tables_with_the_same_states_fails() {
+    let error = rules::adder().merge(&rules::adder(), &[]).unwrap_err();
+    assert!(matches!(error, ComposeError::Collision(_)), "{:?}", error);
+    assert!(error.to_string().ends_with("namespace one of them first"), "{}", error);
+    let mut other = RuleTable::new();
+    other.insert((named("Unmark"), '1'), ('1', Direction::Right, named("Elsewhere")));
+    assert_eq!(unmark().merge(&other, &[]), Err(ComposeError::Collision(named("Unmark"))));
+}
+
+#[test]
+fn merging_with_unknown_states_fails() {
+    let adder = rules::adder().namespaced("add.");
+    let increment = rules::increment().namespaced("inc.");
+    let missing_from = adder.merge(&increment, &[(named("add.Nowhere"), named("inc.Increment"))]);
+    assert_eq!(missing_from, Err(ComposeError::UnknownState(named("add.Nowhere"))));
+    let missing_to = adder.merge(&increment, &[(named("add.GetLast"), named("inc.Nowhere"))]);
+    assert_eq!(missing_to, Err(ComposeError::UnknownState(named("inc.Nowhere"))));
+    assert_eq!(missing_to.unwrap_err().to_string(), "there is no state inc.Nowhere to wire");
+}
+
+#[test]
+fn a_shared_halt_state_may_appear_in_both_tables() {
+    let (mut first, mut second) = (RuleTable::new(), RuleTable::new());
+    first.insert((named("First"), '1'), ('0', Direction::Right, named("Done")));
+    second.insert((named("Second"), '0'), ('1', Direction::Left, named("Done")));
+    let merged = first.merge(&second, &[]).unwrap();
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged.get(&(named("Second"), '0')), Some(&('1', Direction::Left, named("Done"))));
+}
@@ -0,0 +1,55 @@
+use std::time::{Duration, Instant};
+
+use assert_cmd::Command;
+use num_bigint::BigUint;
+use turing::prelude::*;
+use turing::tape::decimal::{binary_to_decimal, decimal_to_binary};
+use turing::testing::SplitMix64;
+
+// A decimal number of exactly `digits` digits drawn from `rng`.
+fn random_decimal(rng: &mut SplitMix64, digits: usize) -> String {
+    let first = char::from(b'1' + rng.below(9) as u8);
+    let rest = (1..digits).map(|_| char::from(b'0' + rng.below(10) as u8));
+    std::iter::once(first).chain(rest).collect()
+}
+
+#[test]
+fn adds_600_digit_decimal_operands() {
+    let mut rng = SplitMix64::new(115);
+    let (a, b) = (random_decimal(&mut rng, 600), random_decimal(&mut rng, 600));
+    let started = Instant::now();
+    let (a_bits, b_bits) = (decimal_to_binary(&a).unwrap(), decimal_to_binary(&b).unwrap());
+    let tape = build_tape(&a_bits, &b_bits, LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    let steps = machine.run_bounded(50_000_000).unwrap();
+    let sum = binary_to_decimal(&machine.result()).unwrap();
+
+    let expected = a.parse::<BigUint>().unwrap() + b.parse::<BigUint>().unwrap();
+    assert_eq!(sum, expected.to_string());
+    assert!(steps > 1_000_000, "{} steps", steps);
+    assert!(started.elapsed() < Duration::from_secs(120), "took {:?}", started.elapsed());
+}
+
+#[test]
+fn conversions_round_trip_past_u128() {
+    let mut rng = SplitMix64::new(1);
+    for digits in [1, 2, 9, 10, 19, 38, 39, 40, 100, 250] {
+        let decimal = random_decimal(&mut rng, digits);
+        let binary = decimal_to_binary(&decimal).unwrap();
+        assert_eq!(binary, decimal.parse::<BigUint>().unwrap().to_str_radix(2), "{}", decimal);
+        assert_eq!(binary_to_decimal(&binary).unwrap(), decimal);
+    }
+    assert_eq!(decimal_to_binary("0").unwrap(), "0");
+    assert_eq!(decimal_to_binary("000123").unwrap(), "1111011");
+    assert_eq!(binary_to_decimal("0000").unwrap(), "0");
+    assert!(decimal_to_binary("12a").is_err());
+    assert!(binary_to_decimal("102").is_err());
+}
+
+#[test]
+fn tm_add_decimal_goes_past_u128() {
+    let max = u128::MAX.to_string();
+    let assert = Command::cargo_bin("tm").unwrap().args(["add", "--decimal", &max, &max]).assert().success();
+    let expected = (BigUint::from(u128::MAX) * 2u32).to_string();
+    assert_eq!(String::from_utf8(assert.get_output().stdout.clone()).unwrap(), format!("{}\n", expected));
+}
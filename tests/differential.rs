@@ -0,0 +1,68 @@
+use turing::prelude::*;
+use turing::testing::ReferenceMachine;
+use turing::testing::SplitMix64;
+use turing::testing::{adder_vectors, assert_same_traces, random_rules};
+
+#[test]
+fn adder_agrees_with_the_reference_on_every_vector() {
+    let add = registry::get("add").unwrap();
+    for (a, b, _) in adder_vectors() {
+        let tape: Vec<char> = format!("_{}+{}_", a, b).chars().collect();
+        assert_same_traces(&add.rules, &tape, &add.start, 100_000);
+    }
+}
+
+#[test]
+fn built_in_machines_agree_with_the_reference_on_their_examples() {
+    for entry in registry::MACHINES {
+        let definition = (entry.build)();
+        for (input, _) in entry.examples {
+            let tape: Vec<char> = input.chars().collect();
+            assert_same_traces(&definition.rules, &tape, &definition.start, 100_000);
+        }
+    }
+    let increment = registry::get("increment").unwrap();
+    for input in ["_", "0", "1", "_111_", "1011", "_1001_"] {
+        let tape: Vec<char> = input.chars().collect();
+        assert_same_traces(&increment.rules, &tape, &increment.start, 1_000);
+    }
+}
+
+#[test]
+fn random_tables_agree_with_the_reference() {
+    let mut rng = SplitMix64::new(1);
+    let symbols = ['_', '0', '1'];
+    for _ in 0..2_000 {
+        let states = 1 + rng.below(4);
+        let rules = random_rules(&mut rng, states, &symbols);
+        let len = 1 + rng.below(6);
+        let tape: Vec<char> = (0..len).map(|_| symbols[rng.below(symbols.len())]).collect();
+        assert_same_traces(&rules, &tape, &State::Named(String::from("S0")), 200);
+    }
+}
+
+#[test]
+fn reference_grows_the_tape_at_both_ends() {
+    // Writes a 1 and moves left off the tape, then walks right off its other end.
+    let state = |name: &str| State::Named(name.to_string());
+    let mut rules = RuleTable::new();
+    rules.insert((state("Left"), '_'), ('1', Direction::Left, state("Right")));
+    rules.insert((state("Right"), '_'), ('_', Direction::Right, state("Right")));
+    rules.insert((state("Right"), '1'), ('1', Direction::Right, state("Right")));
+    let mut reference = ReferenceMachine::new(&['_'], 0, state("Left"), &rules);
+    reference.step();
+    assert_eq!((reference.tape(), reference.head()), ("_1", 0));
+    for _ in 0..3 {
+        reference.step();
+    }
+    assert_eq!((reference.tape(), reference.head(), reference.steps()), ("_1__", 3, 4));
+    assert_eq!(assert_same_traces(&rules, &['_'], &state("Left"), 10), 10);
+}
+
+#[test]
+fn reference_halts_when_no_rule_applies() {
+    let mut reference = ReferenceMachine::new(&['x'], 0, State::FindPlus, &rules::adder());
+    reference.step();
+    assert_eq!((reference.state(), reference.steps()), (&State::Halt, 0));
+    assert_eq!(assert_same_traces(&rules::adder(), &['x'], &State::FindPlus, 10), 0);
+}
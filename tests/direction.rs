@@ -0,0 +1,47 @@
+use turing::prelude::*;
+use turing::DirectionParseError;
+
+#[test]
+fn every_alias_parses() {
+    let aliases = [
+        (Direction::Left, ["L", "l", "Left", "left", "LEFT"]),
+        (Direction::Right, ["R", "r", "Right", "right", "RIGHT"]),
+        (Direction::Stay, ["S", "s", "Stay", "stay", "N"]),
+    ];
+    for (direction, spellings) in aliases {
+        for spelling in spellings {
+            assert_eq!(spelling.parse::<Direction>(), Ok(direction.clone()), "{}", spelling);
+        }
+    }
+    assert_eq!("n".parse::<Direction>(), Ok(Direction::Stay));
+}
+
+#[test]
+fn garbage_is_rejected() {
+    for garbage in ["", " L", "Lf", "up", "<", "Links", "←"] {
+        assert_eq!(garbage.parse::<Direction>(), Err(DirectionParseError(String::from(garbage))), "{:?}", garbage);
+    }
+    let error = "up".parse::<Direction>().unwrap_err();
+    assert_eq!(error.to_string(), "'up' is not a move, use L, Left, R, Right, S, Stay or N (in any case)");
+}
+
+#[test]
+fn every_direction_round_trips() {
+    for direction in [Direction::Left, Direction::Right, Direction::Stay] {
+        let text = direction.to_string();
+        assert_eq!(text.len(), 1);
+        assert_eq!(text.parse::<Direction>(), Ok(direction));
+    }
+}
+
+#[test]
+fn rule_files_accept_the_aliases() {
+    let rules = rules::parse_rules("A 1 1 right B\nB 1 0 left A\nA 0 0 n Halt\nB 0 1 S Halt").unwrap();
+    assert_eq!(rules.get(&(State::Named(String::from("A")), '1')).unwrap().1, Direction::Right);
+    assert_eq!(rules.get(&(State::Named(String::from("B")), '1')).unwrap().1, Direction::Left);
+    assert_eq!(rules.get(&(State::Named(String::from("A")), '0')).unwrap().1, Direction::Stay);
+    assert_eq!(rules::parse_rules(&rules::to_text(&rules)), Ok(rules));
+    let error = rules::parse_rules("A 1 1 up B").unwrap_err();
+    assert_eq!(error.line, 1);
+    assert!(error.to_string().contains("'up' is not a move"), "{}", error);
+}
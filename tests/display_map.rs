@@ -0,0 +1,76 @@
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::registry::bundle::{self, Bundle};
+use turing::tape::display::DisplayMap;
+
+// The adder run to the end on 1011+11, showing symbols through `display`.
+fn run(display: DisplayMap) -> TuringMachine {
+    let mut machine = TuringMachine::new(build_tape("1011", "11", LeadingZeros::Strip).unwrap(), rules::adder());
+    machine.set_display_map(display);
+    machine.run_bounded(10_000).unwrap();
+    machine
+}
+
+// The line of `tm trace 1011+11 <extra>` that shows the final tape.
+fn final_tape(extra: &[&str]) -> String {
+    let assert = Command::cargo_bin("tm").unwrap().args(["trace", "1011+11"]).args(extra).assert();
+    let stdout = String::from_utf8(assert.success().get_output().stdout.clone()).unwrap();
+    stdout.lines().find(|line| line.starts_with("Final Tape")).unwrap().to_string()
+}
+
+#[test]
+fn mapping_leaves_the_tape_rules_and_answer_alone() {
+    let (raw, mapped) = (run(DisplayMap::new()), run(DisplayMap::adder()));
+    assert_eq!(mapped.tape().iter().collect::<String>(), "_+++IIIO_");
+    assert_eq!(mapped.tape(), raw.tape());
+    assert_eq!((mapped.steps(), mapped.result()), (raw.steps(), raw.result()));
+    assert_eq!(mapped.rules(), raw.rules());
+}
+
+#[test]
+fn rendered_tape_shows_the_mapped_forms() {
+    let mapped = run(DisplayMap::adder());
+    let display = DisplayMap::adder();
+    assert_eq!(display.show_tape(mapped.tape()), "_+++1\u{332}1\u{332}1\u{332}0\u{332}_");
+    assert_eq!(display.show('1'), "1");
+    let boxed = mapped.render_boxed();
+    assert!(boxed.contains("│1\u{332}│0\u{332}│"), "{}", boxed);
+    let window = trace::render_window(mapped.tape().iter(), 6, 3, &display);
+    assert_eq!(window, "[5] … 1\u{332} [1\u{332}] 0\u{332} … [8]");
+}
+
+#[test]
+fn empty_map_shows_symbols_as_they_are() {
+    let raw = run(DisplayMap::new());
+    assert_eq!(raw.render_boxed(), trace::render_boxed(raw.tape().iter(), raw.head(), &DisplayMap::new()));
+    assert!(raw.render_boxed().contains("│I│O│"));
+    assert!(DisplayMap::new().is_empty());
+}
+
+#[test]
+fn any_symbol_can_be_shown_as_anything() {
+    let mut bold = DisplayMap::new();
+    bold.insert('I', "\x1b[1m1\x1b[0m");
+    assert_eq!(bold.show_tape(&['_', 'I', 'O']), "_\x1b[1m1\x1b[0mO");
+    assert_eq!(DisplayMap::markers(&[('I', '1'), ('O', '0')]), DisplayMap::adder());
+}
+
+#[test]
+fn bundles_carry_their_markers() {
+    let exported = bundle::export(&Bundle::from_entry(registry::entry("add").unwrap()));
+    assert!(exported.contains("markers = \"I1 O0\""), "{}", exported);
+    let loaded = bundle::parse(&exported).unwrap();
+    assert_eq!(loaded.markers, [('I', '1'), ('O', '0')]);
+    let tape = build_tape("1011", "11", LeadingZeros::Strip).unwrap();
+    assert_eq!(loaded.machine(tape).display_map(), &DisplayMap::adder());
+    let increment = bundle::parse(&bundle::export(&Bundle::from_entry(registry::entry("increment").unwrap())));
+    assert!(increment.unwrap().machine(vec!['_']).display_map().is_empty());
+}
+
+#[test]
+fn tm_trace_maps_markers_unless_raw_symbols_is_given() {
+    let mapped = "['_', '+', '+', '+', '1\u{332}', '1\u{332}', '1\u{332}', '0\u{332}', '_']";
+    assert_eq!(final_tape(&[]), format!("Final Tape: {}, Head: 0, State: Halt", mapped));
+    let raw = "Final Tape: ['_', '+', '+', '+', 'I', 'I', 'I', 'O', '_'], Head: 0, State: Halt";
+    assert_eq!(final_tape(&["--raw-symbols"]), raw);
+}
@@ -0,0 +1,68 @@
+use std::fs;
+
+use assert_cmd::Command;
+
+fn tm() -> Command {
+    Command::cargo_bin("tm").unwrap()
+}
+
+// The adder as `tm export add` prints it, with `edit` applied to its rule lines.
+fn adder_bundle(name: &str, edit: impl Fn(&str) -> String) -> String {
+    let exported = String::from_utf8(tm().args(["export", "add"]).output().unwrap().stdout).unwrap();
+    let text: String = exported.lines().map(|line| edit(line) + "\n").collect();
+    let path = std::env::temp_dir().join(format!("tm-dry-run-{}-{}.toml", std::process::id(), name));
+    fs::write(&path, text).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+// Dry-runs the bundle at `path` on 1011+11, returning the exit code, stdout and stderr.
+fn dry_run(path: &str) -> (Option<i32>, String, String) {
+    let output = tm().args(["run", "--bundle", path, "--input", "1011+11", "--dry-run"]).output().unwrap();
+    fs::remove_file(path).unwrap();
+    let text = |bytes: Vec<u8>| String::from_utf8(bytes).unwrap();
+    (output.status.code(), text(output.stdout), text(output.stderr))
+}
+
+#[test]
+fn the_adder_passes_its_checks_without_running() {
+    let output = tm().args(["run", "--machine", "add", "--input", "1011+11", "--dry-run"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let expected = "\
+machine:  built-in machine add
+rules:    31 rules over 8 states, starting in FindPlus
+tape:     7 cells, head on cell 0, symbols +01_
+estimate: at most 91 steps, on a tape that stays 7 cells
+checks:   ok
+";
+    assert_eq!(stdout, expected);
+}
+
+#[test]
+fn a_rule_that_can_never_fire_fails_the_checks() {
+    let path = adder_bundle("never-fires", |line| match line {
+        "start FindPlus" => String::from("start FindPlus\nFindPlus x x R FindPlus"),
+        _ => String::from(line),
+    });
+    let (code, stdout, _) = dry_run(&path);
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("checks:   1 failed\n"), "{}", stdout);
+    let problem = "[unreadable-symbol] the rule for state FindPlus reading 'x' can never fire: \
+                   nothing puts 'x' on the tape";
+    assert!(stdout.contains(problem), "{}", stdout);
+    assert!(!stdout.contains("Result"), "{}", stdout);
+}
+
+#[test]
+fn a_malformed_rule_is_reported_by_line() {
+    let path = adder_bundle("malformed", |line| {
+        if line.starts_with("GetLast 1 ") { String::from("GetLast 1 + X AddOne") } else { String::from(line) }
+    });
+    let text = fs::read_to_string(&path).unwrap();
+    let line = text.lines().position(|line| line.starts_with("GetLast 1 ")).unwrap() + 1;
+    let (code, stdout, stderr) = dry_run(&path);
+    assert_eq!(code, Some(2));
+    assert!(stdout.is_empty(), "{}", stdout);
+    let message = format!("line {}: 'X' is not a move, use L, Left, R, Right, S, Stay or N (in any case)", line);
+    assert!(stderr.contains(&message), "{}", stderr);
+}
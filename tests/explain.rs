@@ -0,0 +1,36 @@
+use assert_cmd::Command;
+
+// What `tm run` prints for the rules in tests/fixtures/described.tm on `_11_` with --explain.
+fn explain() -> Vec<String> {
+    let rules = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/described.tm");
+    let args = ["run", "--rules", rules, "--input", "_11_", "--explain"];
+    let output = Command::cargo_bin("tm").unwrap().args(args).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap().lines().map(String::from).collect()
+}
+
+#[test]
+fn non_ascii_descriptions_are_printed_as_written() {
+    let lines = explain();
+    assert_eq!(
+        lines[0],
+        "In state Start, reading '_' at cell 0: über das Leerzeichen ➜ zur Zahl (write '_', move right, state Seek)."
+    );
+    assert_eq!(lines[1], "In state Seek, reading '1' at cell 1: 右へ進む (write '1', move right, state Seek).");
+    assert_eq!(
+        lines[4],
+        "In state Increment, reading '1' at cell 2: 1 → 0, Übertrag 🡐 (write '0', move left, state Increment)."
+    );
+    assert!(lines.iter().any(|line| line.ends_with("ein neues Bit «1» (write '1', move left, state Done).")));
+    assert!(lines.contains(&String::from("Result: 100")));
+}
+
+#[test]
+fn rules_without_a_description_fall_back_to_their_fields() {
+    let lines = explain();
+    assert_eq!(
+        lines[3],
+        "In state Seek, reading '_' at cell 3: write '_', move left and go to state Increment."
+    );
+    assert_eq!(lines[7], "In state Done, reading '_' at cell 0: no rule applies, so the machine halts.");
+}
@@ -0,0 +1,85 @@
+use turing::machine::fault::{Fault, FaultInjector};
+use turing::prelude::*;
+use turing::{OnSentinel, Sentinels};
+
+// Runs the adder on `1011011+110101` with faults at `rate` from `seed`.
+fn faulty_run(rate: f64, seed: u64) -> (Vec<Fault>, Vec<char>, Result<usize, RunError>) {
+    let tape = build_tape("1011011", "110101", LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    let mut injector = FaultInjector::new(rate, seed);
+    let outcome = machine.run_with_faults(&mut injector, 100_000);
+    (injector.log().to_vec(), machine.tape().iter().copied().collect(), outcome)
+}
+
+#[test]
+fn a_seed_reproduces_its_faults_and_tape() {
+    let (log, tape, outcome) = faulty_run(0.01, 7);
+    assert!(!log.is_empty());
+    assert_eq!((log, tape, outcome), faulty_run(0.01, 7));
+    assert_ne!(faulty_run(0.01, 7).0, faulty_run(0.01, 8).0);
+}
+
+#[test]
+fn no_faults_at_rate_zero() {
+    let (log, tape, outcome) = faulty_run(0.0, 7);
+    assert!(log.is_empty());
+    let mut clean = TuringMachine::new(build_tape("1011011", "110101", LeadingZeros::Strip).unwrap(), rules::adder());
+    assert_eq!(outcome, clean.run_bounded(100_000));
+    assert_eq!(tape, clean.tape().iter().copied().collect::<Vec<_>>());
+}
+
+// A machine in state `S` that writes `0` over a `1` and moves right.
+fn zero_and_right(tape: Vec<char>, head: usize) -> TuringMachine {
+    let start = State::Named(String::from("S"));
+    let mut rules = RuleTable::new();
+    rules.insert((start.clone(), '1'), ('0', Direction::Right, State::Named(String::from("T"))));
+    TuringMachine::builder(tape, rules).with_head(head).with_state(start).build().unwrap()
+}
+
+#[test]
+fn flips_the_write_of_a_step_that_overflows() {
+    let mut machine = zero_and_right(vec!['1'], 0);
+    machine.set_tape_mode(TapeMode::Fixed);
+    let mut injector = FaultInjector::new(1.0, 0);
+    assert_eq!(machine.run_with_faults(&mut injector, 10), Ok(1));
+    assert_eq!(machine.halt_reason(), Some(&HaltReason::Overflow(Direction::Right)));
+    let fault = Fault { step: 1, cell: 0, before: '0', after: '1' };
+    assert_eq!((injector.log(), machine.tape()[0]), (&[fault][..], '1'));
+}
+
+#[test]
+fn flips_the_written_cell_when_a_sentinel_reflects() {
+    let mut machine = zero_and_right(vec!['<', '1', '>'], 1);
+    machine.set_sentinels(Some(Sentinels { left: '<', right: '>', on_contact: OnSentinel::Reflect }));
+    let mut injector = FaultInjector::new(1.0, 0);
+    machine.run_with_faults(&mut injector, 10).unwrap();
+    let fault = Fault { step: 1, cell: 1, before: '0', after: '1' };
+    assert_eq!(injector.log(), [fault]);
+    assert_eq!(machine.tape(), &['<', '1', '>']);
+}
+
+#[test]
+fn flips_the_written_cell_when_the_tape_grows_left() {
+    let mut rules = RuleTable::new();
+    rules.insert((State::Named(String::from("S")), '1'), ('0', Direction::Left, State::Halt));
+    let mut machine = TuringMachine::new(vec!['1'], rules);
+    machine.set_state(State::Named(String::from("S")));
+    let mut injector = FaultInjector::new(1.0, 0);
+    machine.run_with_faults(&mut injector, 10).unwrap();
+    assert_eq!(injector.log(), [Fault { step: 1, cell: 1, before: '0', after: '1' }]);
+    assert_eq!(machine.tape(), &['_', '1']);
+}
+
+#[test]
+fn faulty_runs_stop_at_the_step_limit_and_off_a_strict_tape() {
+    let mut machine = TuringMachine::new(build_tape("1011", "11", LeadingZeros::Strip).unwrap(), rules::adder());
+    let mut injector = FaultInjector::new(0.0, 0);
+    assert_eq!(machine.run_with_faults(&mut injector, 10), Err(RunError::StepLimitExceeded(10)));
+    assert_eq!(machine.steps(), 10);
+
+    // Started on an empty tape, the head is off it from the first step.
+    let mut machine = TuringMachine::new(Vec::new(), rules::adder());
+    machine.set_tape_mode(TapeMode::Strict);
+    let outcome = machine.run_with_faults(&mut FaultInjector::new(1.0, 0), 10);
+    assert_eq!(outcome, Err(RunError::HeadOutOfBounds { head: 0, len: 0 }));
+}
@@ -0,0 +1,9 @@
+start Start
+final Done
+result whole_tape_trimmed
+
+Start _ _ R Seek # über das Leerzeichen ➜ zur Zahl
+Seek 1 1 R Seek # 右へ進む
+Seek _ _ L Increment
+Increment 1 0 L Increment # 1 → 0, Übertrag 🡐
+Increment _ 1 L Done # ein neues Bit «1»
@@ -0,0 +1,42 @@
+# The adder with a junk rule on 'x', which nothing writes, leading to a state that spins forever.
+start FindPlus
+result right_of_separator + I=1 O=0
+
+AddDigitOne + I L BackToStart # the second number has run out, write a done 1 (I)
+AddDigitOne 0 I L BackToStart # 0 plus one is 1: write a done 1 (I)
+AddDigitOne 1 O L Carry # 1 plus one is 10: write a done 0 (O) and carry one to the left
+AddDigitZero + O L BackToStart # the second number has run out, write a done 0 (O)
+AddDigitZero 0 O L BackToStart # add zero to this 0, marking it done as O
+AddDigitZero 1 I L BackToStart # add zero to this 1, marking it done as I
+AddOne + + R AddOne # move right over the second number
+AddOne 0 0 R AddOne # move right over the second number
+AddOne 1 1 R AddOne # move right over the second number
+AddOne I I L AddDigitOne # stop at the digits already added and turn to the next one
+AddOne O O L AddDigitOne # stop at the digits already added and turn to the next one
+AddOne _ _ L AddDigitOne # stop at the end of the tape and turn to the last digit
+AddZero + + R AddZero # move right over the second number
+AddZero 0 0 R AddZero # move right over the second number
+AddZero 1 1 R AddZero # move right over the second number
+AddZero I I L AddDigitZero # stop at the digits already added and turn to the next one
+AddZero O O L AddDigitZero # stop at the digits already added and turn to the next one
+AddZero _ _ L AddDigitZero # stop at the end of the tape and turn to the last digit
+BackToStart + + L BackToStart # move back to the start of the tape
+BackToStart 0 0 L BackToStart # move back to the start of the tape
+BackToStart 1 1 L BackToStart # move back to the start of the tape
+BackToStart _ _ R FindPlus # back at the start, look for the plus again
+Carry + 1 L BackToStart # carry into a new digit in place of the plus
+Carry 0 1 L BackToStart # absorb the carry, turning 0 into 1
+Carry 1 0 L Carry # 1 plus the carry is 10: write 0 and keep carrying
+FindPlus + + L GetLast # found the plus, turn back to the last digit of the first number
+FindPlus 0 0 R FindPlus # keep moving right until we find the plus
+FindPlus 1 1 R FindPlus # keep moving right until we find the plus
+FindPlus _ _ R FindPlus # skip the blank before the first number
+GetLast 0 + R AddZero # erase it and remember we must add zero
+GetLast 1 + R AddOne # erase it and remember we must add one
+FindPlus x x R Spin # junk: no input has an x
+Spin _ _ R Spin # never halts
+Spin 0 0 R Spin # never halts
+Spin 1 1 R Spin # never halts
+Spin + + R Spin # never halts
+Spin I I R Spin # never halts
+Spin O O R Spin # never halts
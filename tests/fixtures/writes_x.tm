@@ -0,0 +1,6 @@
+# Walks right over a run of ones and marks the blank after it with an X.
+start Start
+
+Start _ _ R Walk
+Walk 1 1 R Walk
+Walk _ X R Done
@@ -0,0 +1,91 @@
+use std::fs;
+
+use assert_cmd::Command;
+use turing::error::{FormatError, RuleJsonError, TraceError, VectorsError};
+use turing::prelude::*;
+use turing::registry::vectors;
+use turing::trace::formats::{self, Format};
+use turing::trace::json::Json;
+use turing::trace::replay::Trace;
+use turing::trace::stats::AggregateStats;
+
+// A trace from a future version, checked in as a consumer would find it.
+const FUTURE_TRACE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/trace_v3.json");
+
+// The error for a version 3 file of `format`, one past the current version.
+fn future(format: Format) -> FormatError {
+    FormatError::UnsupportedVersion {
+        format: format.name(),
+        version: 3,
+        current: 2,
+    }
+}
+
+// The artifact's payload on its own, as a version from before the envelope wrote it.
+fn payload(json: &Json) -> String {
+    json.get("payload").expect("artifacts are enveloped").to_string()
+}
+
+#[test]
+fn future_trace_is_turned_down() {
+    let text = fs::read_to_string(FUTURE_TRACE).unwrap();
+    assert_eq!(Trace::from_json(&text), Err(TraceError::Format(future(Format::Trace))));
+    let assert = Command::cargo_bin("tm").unwrap().args(["verify-trace", FUTURE_TRACE, "--input", "1011+11"]).assert();
+    let stderr = String::from_utf8(assert.failure().get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("this is version 3 of the tm-trace format, this version of tm reads versions 1 and 2"));
+}
+
+#[test]
+fn traces_round_trip() {
+    let mut machine = TuringMachine::new("_1011+11_".chars().collect(), rules::adder());
+    let trace = Trace::record(&mut machine, 1_000).unwrap();
+    let json = trace.to_json();
+    assert_eq!(json.get("format").and_then(Json::as_str), Some("tm-trace"));
+    assert_eq!(Trace::from_json(&json.to_string()), Ok(trace.clone()));
+    assert_eq!(Trace::from_json(&payload(&json)), Ok(trace));
+}
+
+#[test]
+fn machines_round_trip() {
+    for entry in registry::MACHINES {
+        let definition = registry::get(entry.name).unwrap();
+        let json = rules::to_json(&definition);
+        assert_eq!(rules::from_json(&format!("{:#}", json)), Ok(definition.clone()), "{}", entry.name);
+        assert_eq!(rules::from_json(&payload(&json)), Ok(definition), "{}", entry.name);
+        let json = json.to_string().replace("\"version\":2", "\"version\":3");
+        assert_eq!(rules::from_json(&json), Err(RuleJsonError::Format(future(Format::Machine))));
+    }
+}
+
+#[test]
+fn vectors_round_trip() {
+    let generated = vectors::generate(40, 7);
+    let text = vectors::export(&generated, 7);
+    assert_eq!(vectors::parse(&text), Ok(generated));
+    let future_text = text.replace("\"version\": 2", "\"version\": 3");
+    assert_eq!(vectors::parse(&future_text), Err(VectorsError::Format(future(Format::Vectors))));
+}
+
+#[test]
+fn other_kinds_of_artifact_are_turned_down() {
+    let text = vectors::export(&vectors::generate(4, 7), 7);
+    let wrong = FormatError::WrongFormat {
+        expected: Format::Machine.name(),
+        found: String::from(Format::Vectors.name()),
+    };
+    assert_eq!(rules::from_json(&text), Err(RuleJsonError::Format(wrong)));
+}
+
+#[test]
+fn batch_summaries_round_trip() {
+    let mut stats = AggregateStats::new();
+    stats.record_success(62, Some(9));
+    stats.record_failure("step limit");
+    let text = formats::envelope(Format::Stats, stats.to_json()).to_string();
+    let json: Json = text.parse().unwrap();
+    assert_eq!(formats::open(Format::Stats, &json), Ok(&stats.to_json()));
+    assert_eq!(
+        formats::open(Format::Trace, &json),
+        Err(FormatError::WrongFormat { expected: "tm-trace", found: "tm-stats".to_string() })
+    );
+}
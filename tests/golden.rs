@@ -0,0 +1,84 @@
+use std::fs;
+
+use assert_cmd::Command;
+
+use turing::prelude::*;
+use turing::rules::SortedRules;
+use turing::tape::input::DEMO_TAPE;
+
+// Compares `actual` with the golden file `name` under tests/golden/, pointing at the
+// first line that differs. A change meant to alter the output updates the golden files
+// along with it: run `cargo run --example golden -- --bless` and commit the result.
+fn compare(name: &str, actual: &str) {
+    let path = format!("{}/tests/golden/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let golden = fs::read_to_string(&path).unwrap();
+    if let Some((line, (expected, found))) = golden.lines().zip(actual.lines()).enumerate().find(|(_, (e, f))| e != f) {
+        panic!("{} differs at line {}:\nexpected: {}\nfound:    {}", name, line + 1, expected, found);
+    }
+    assert_eq!(golden.lines().count(), actual.lines().count(), "{} has a different number of lines", name);
+    assert_eq!(golden, actual, "{} differs in its line endings", name);
+}
+
+#[test]
+fn demo_trace_matches_the_golden_file() {
+    let mut machine = TuringMachine::new(DEMO_TAPE.chars().collect(), rules::adder());
+    machine.set_tracing(true);
+    machine.run();
+    compare("demo_trace.txt", &trace::trace_to_text(machine.trace()));
+}
+
+#[test]
+fn adder_smtlib_dump_matches_the_golden_file() {
+    compare("adder.smt2", &rules::to_smtlib(&rules::adder()));
+}
+
+#[test]
+fn adder_rules_in_canonical_order_match_the_golden_file() {
+    compare("adder_rules.txt", &rules::to_text(&rules::adder()));
+}
+
+#[test]
+fn canonical_order_ignores_insertion_order() {
+    let mut entries: Vec<_> = rules::adder().into_iter().collect();
+    entries.sort_by_key(|((state, read), _)| (state.to_string(), *read));
+    let reversed: RuleTable = entries.into_iter().rev().collect();
+    let keys = |rules: &RuleTable| -> Vec<(String, char)> {
+        rules.iter_sorted().map(|(state, read, ..)| (state.to_string(), read)).collect()
+    };
+    let sorted = keys(&reversed);
+    assert_eq!(sorted, keys(&rules::adder()));
+    assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", sorted);
+    assert_eq!(sorted[0], ("AddDigitOne".to_string(), '+'));
+}
+
+#[test]
+fn plain_tape_diff_matches_the_golden_file() {
+    let before = build_tape("1011", "11", LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(before.clone(), rules::adder());
+    machine.preserve_operands();
+    machine.run();
+    let after: Vec<char> = machine.tape().iter().copied().collect();
+    let diff = trace::render_tape_diff(&before, &after, machine.grown_left(), false);
+    compare("adder_diff.txt", &format!("{}\n{}\n", diff, machine.equation().unwrap()));
+}
+
+#[test]
+fn tm_add_diff_prints_the_golden_diff() {
+    let assert = Command::cargo_bin("tm").unwrap().args(["add", "1011+11", "--diff"]).env("NO_COLOR", "1").assert();
+    compare("adder_diff.txt", &String::from_utf8(assert.success().get_output().stdout.clone()).unwrap());
+}
+
+#[test]
+fn tape_diff_aligns_growth_and_highlights_changes() {
+    // The tape grew by two cells on the left; the before row is padded to match.
+    let diff = trace::render_tape_diff(&['_', '1', '_'], &['1', '0', '_', '1', '1'], 2, false);
+    assert_eq!(diff, "before:   _1_\nafter:  10_11\n        ^^  ^");
+    let highlighted = trace::render_tape_diff(&['_', '1', '_'], &['1', '0', '_', '1', '1'], 2, true);
+    assert_eq!(highlighted.lines().nth(1), Some("after:  \x1b[7m1\x1b[0m\x1b[7m0\x1b[0m_1\x1b[7m1\x1b[0m"));
+}
+
+#[test]
+fn tm_trace_explain_matches_the_golden_file() {
+    let assert = Command::cargo_bin("tm").unwrap().args(["trace", "1+1", "--explain"]).assert();
+    compare("adder_explain.txt", &String::from_utf8(assert.success().get_output().stdout.clone()).unwrap());
+}
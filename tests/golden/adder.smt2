@@ -0,0 +1,38 @@
+; The transition function of a Turing machine.
+(declare-datatype State ((AddDigitOne) (AddDigitZero) (AddOne) (AddZero) (BackToStart) (Carry) (FindPlus) (GetLast)))
+(declare-datatype Symbol ((|'+'|) (|'0'|) (|'1'|) (|'I'|) (|'O'|) (|'_'|)))
+(declare-datatype Move ((L) (R) (S)))
+(declare-datatype Action ((action (write Symbol) (move Move) (next State)) (stuck)))
+(define-fun delta ((state State) (read Symbol)) Action
+  (ite (and (= state AddDigitOne) (= read |'+'|)) (action |'I'| L BackToStart)
+  (ite (and (= state AddDigitOne) (= read |'0'|)) (action |'I'| L BackToStart)
+  (ite (and (= state AddDigitOne) (= read |'1'|)) (action |'O'| L Carry)
+  (ite (and (= state AddDigitZero) (= read |'+'|)) (action |'O'| L BackToStart)
+  (ite (and (= state AddDigitZero) (= read |'0'|)) (action |'O'| L BackToStart)
+  (ite (and (= state AddDigitZero) (= read |'1'|)) (action |'I'| L BackToStart)
+  (ite (and (= state AddOne) (= read |'+'|)) (action |'+'| R AddOne)
+  (ite (and (= state AddOne) (= read |'0'|)) (action |'0'| R AddOne)
+  (ite (and (= state AddOne) (= read |'1'|)) (action |'1'| R AddOne)
+  (ite (and (= state AddOne) (= read |'I'|)) (action |'I'| L AddDigitOne)
+  (ite (and (= state AddOne) (= read |'O'|)) (action |'O'| L AddDigitOne)
+  (ite (and (= state AddOne) (= read |'_'|)) (action |'_'| L AddDigitOne)
+  (ite (and (= state AddZero) (= read |'+'|)) (action |'+'| R AddZero)
+  (ite (and (= state AddZero) (= read |'0'|)) (action |'0'| R AddZero)
+  (ite (and (= state AddZero) (= read |'1'|)) (action |'1'| R AddZero)
+  (ite (and (= state AddZero) (= read |'I'|)) (action |'I'| L AddDigitZero)
+  (ite (and (= state AddZero) (= read |'O'|)) (action |'O'| L AddDigitZero)
+  (ite (and (= state AddZero) (= read |'_'|)) (action |'_'| L AddDigitZero)
+  (ite (and (= state BackToStart) (= read |'+'|)) (action |'+'| L BackToStart)
+  (ite (and (= state BackToStart) (= read |'0'|)) (action |'0'| L BackToStart)
+  (ite (and (= state BackToStart) (= read |'1'|)) (action |'1'| L BackToStart)
+  (ite (and (= state BackToStart) (= read |'_'|)) (action |'_'| R FindPlus)
+  (ite (and (= state Carry) (= read |'+'|)) (action |'1'| L BackToStart)
+  (ite (and (= state Carry) (= read |'0'|)) (action |'1'| L BackToStart)
+  (ite (and (= state Carry) (= read |'1'|)) (action |'0'| L Carry)
+  (ite (and (= state FindPlus) (= read |'+'|)) (action |'+'| L GetLast)
+  (ite (and (= state FindPlus) (= read |'0'|)) (action |'0'| R FindPlus)
+  (ite (and (= state FindPlus) (= read |'1'|)) (action |'1'| R FindPlus)
+  (ite (and (= state FindPlus) (= read |'_'|)) (action |'_'| R FindPlus)
+  (ite (and (= state GetLast) (= read |'0'|)) (action |'+'| R AddZero)
+  (ite (and (= state GetLast) (= read |'1'|)) (action |'+'| R AddOne)
+  stuck))))))))))))))))))))))))))))))))
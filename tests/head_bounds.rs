@@ -0,0 +1,90 @@
+use turing::error::RunError;
+use turing::machine::builder::BuildError;
+use turing::prelude::*;
+
+// A five-cell tape with the head recorded far past its end, as a tampered snapshot might have it.
+fn tampered() -> Snapshot {
+    Snapshot {
+        tape: "_1+1_".chars().collect(),
+        head: 9999,
+        state: State::FindPlus,
+        partial_value: None,
+    }
+}
+
+#[test]
+fn snapshot_with_the_head_off_the_tape_is_refused() {
+    let error = TuringMachine::from_snapshot(&tampered(), rules::adder()).unwrap_err();
+    assert_eq!(error, BuildError::HeadOutOfRange { head: 9999, len: 5 });
+    assert_eq!(error.to_string(), "the head starts at cell 9999 but the tape has only 5 cells");
+}
+
+#[test]
+fn snapshot_with_the_head_on_the_tape_resumes() {
+    let snapshot = Snapshot { head: 1, ..tampered() };
+    let mut machine = TuringMachine::from_snapshot(&snapshot, rules::adder()).unwrap();
+    machine.run_bounded(10_000).unwrap();
+    assert_eq!(machine.result(), "10");
+}
+
+#[test]
+fn empty_tape_grows_by_default() {
+    let mut machine = TuringMachine::new(Vec::new(), rules::adder());
+    machine.step();
+    assert_eq!(machine.steps(), 1);
+    assert_eq!(*machine.state(), State::FindPlus);
+    assert_eq!(machine.tape().len(), 2);
+}
+
+#[test]
+fn empty_tape_fails_in_the_strict_modes() {
+    for mode in [TapeMode::Strict, TapeMode::Fixed] {
+        let mut machine = TuringMachine::new(Vec::new(), rules::adder());
+        machine.set_tape_mode(mode);
+        assert_eq!(machine.run_bounded(10_000), Err(RunError::HeadOutOfBounds { head: 0, len: 0 }), "{:?}", mode);
+        assert_eq!(machine.steps(), 0);
+        assert!(machine.tape().is_empty());
+    }
+}
+
+#[test]
+fn step_halts_instead_of_panicking() {
+    let mut machine = TuringMachine::new(Vec::new(), rules::adder());
+    machine.set_tape_mode(TapeMode::Strict);
+    machine.step();
+    assert_eq!(*machine.state(), State::Halt);
+    assert_eq!(machine.halt_reason(), Some(&HaltReason::HeadOutOfBounds));
+}
+
+// The adder on a tape of `cells` with no blanks around it, in `TapeMode::Fixed`.
+fn fixed(cells: &str) -> TuringMachine {
+    let mut machine = TuringMachine::new(cells.chars().collect(), rules::adder());
+    machine.set_tape_mode(TapeMode::Fixed);
+    machine
+}
+
+#[test]
+fn running_off_the_right_of_a_fixed_tape_overflows() {
+    let mut machine = fixed("1+1");
+    assert_eq!(machine.run_bounded(100), Ok(5));
+    assert_eq!(machine.halt_reason(), Some(&HaltReason::Overflow(Direction::Right)));
+    assert_eq!(machine.tape().iter().collect::<String>(), "++1");
+    assert_eq!(machine.head(), 2);
+}
+
+#[test]
+fn running_off_the_left_of_a_fixed_tape_overflows() {
+    let mut machine = fixed("+1");
+    assert_eq!(machine.run_bounded(100), Ok(1));
+    assert_eq!(machine.halt_reason(), Some(&HaltReason::Overflow(Direction::Left)));
+    assert_eq!(machine.tape().len(), 2);
+    assert_eq!(machine.head(), 0);
+}
+
+#[test]
+fn the_same_run_grows_a_growing_tape() {
+    let mut machine = TuringMachine::new("1+1".chars().collect(), rules::adder());
+    machine.run_bounded(100).unwrap();
+    assert_eq!(machine.result(), "10");
+    assert!(machine.tape().len() > 3);
+}
@@ -0,0 +1,83 @@
+use std::fs;
+
+use assert_cmd::Command;
+use turing::machine::stream::HeadTrace;
+use turing::prelude::*;
+
+// The rows of a head trace, checking its header.
+fn rows(csv: &str) -> Vec<(usize, isize, String)> {
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("step,logical_head,state"));
+    lines
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            assert_eq!(fields.len(), 3, "{}", line);
+            (fields[0].parse().unwrap(), fields[1].parse().unwrap(), String::from(fields[2]))
+        })
+        .collect()
+}
+
+// Runs `tm run` on 1011+11 with `extra` arguments, writing the head trace to a scratch
+// file, and returns its rows.
+fn run_cli(name: &str, extra: &[&str]) -> Vec<(usize, isize, String)> {
+    let path = std::env::temp_dir().join(format!("tm-head-trace-{}-{}.csv", std::process::id(), name));
+    let path = path.to_string_lossy().into_owned();
+    let output = Command::cargo_bin("tm")
+        .unwrap()
+        .args(["run", "--machine", "add", "--input", "_1011+11_", "--head-trace", &path])
+        .args(extra)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Result: 1110"));
+    let csv = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    rows(&csv)
+}
+
+// Checks that the rows start at step 0 and end at the adder's halt on 1011+11, with
+// step numbers that only go up.
+fn check_ends(rows: &[(usize, isize, String)]) {
+    assert_eq!(rows.first(), Some(&(0, 0, String::from("FindPlus"))));
+    assert_eq!(rows.last(), Some(&(62, 0, String::from("GetLast"))));
+    assert!(rows.windows(2).all(|pair| pair[0].0 < pair[1].0), "{:?}", rows);
+}
+
+#[test]
+fn run_writes_every_step() {
+    let rows = run_cli("every-step", &[]);
+    check_ends(&rows);
+    assert_eq!(rows.len(), 63);
+    assert_eq!(rows[1], (1, 1, String::from("FindPlus")));
+}
+
+#[test]
+fn run_downsamples_and_keeps_the_final_step() {
+    let rows = run_cli("every-10", &["--head-trace-every", "10"]);
+    check_ends(&rows);
+    let steps: Vec<usize> = rows.iter().map(|row| row.0).collect();
+    assert_eq!(steps, [0, 10, 20, 30, 40, 50, 60, 62]);
+}
+
+#[test]
+fn trace_keeps_every_nth_step_and_the_last() {
+    for (every, expected) in [(1, 63), (7, 10), (31, 3), (62, 2), (100, 2)] {
+        let mut machine = TuringMachine::new("_1011+11_".chars().collect(), rules::adder());
+        let mut trace = HeadTrace::new(Vec::new(), every).unwrap();
+        machine.run_observed(|configuration| trace.observe(configuration)).unwrap();
+        let rows = rows(&String::from_utf8(trace.finish().unwrap()).unwrap());
+        check_ends(&rows);
+        assert_eq!(rows.len(), expected, "every {}", every);
+        assert!(rows[..rows.len() - 1].iter().all(|row| row.0 % every == 0), "every {}", every);
+    }
+}
+
+#[test]
+fn the_head_is_logical_when_the_tape_grows_left() {
+    let mut machine = TuringMachine::new("1+1".chars().collect(), rules::adder());
+    let mut trace = HeadTrace::new(Vec::new(), 1).unwrap();
+    machine.run_observed(|configuration| trace.observe(configuration)).unwrap();
+    let rows = rows(&String::from_utf8(trace.finish().unwrap()).unwrap());
+    assert!(rows.iter().any(|row| row.1 < 0), "{:?}", rows);
+    assert_eq!(rows.len(), machine.steps() + 1);
+}
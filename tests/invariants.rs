@@ -0,0 +1,155 @@
+use turing::machine::invariant::{ExactlyOnePlus, Invariant, MarkersAreSuffix};
+use turing::prelude::*;
+use turing::tape::input::build_tape_with_layout;
+use turing::testing::SplitMix64;
+use turing::testing::{adder_vectors, random_input};
+use turing::Configuration;
+
+// The adder on `a+b` with the tape's layout, ready to run.
+fn adder(a: &str, b: &str, rules: RuleTable) -> TuringMachine {
+    let (tape, layout) = build_tape_with_layout(a, b, LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape, rules);
+    machine.set_layout(layout);
+    machine.set_tape_mode(TapeMode::Strict);
+    machine
+}
+
+// The adder with a carry that writes `+` over the 1 it passes instead of a 0.
+fn corrupted_carry() -> RuleTable {
+    let mut rules = rules::adder();
+    rules.insert((State::Carry, '1'), ('+', Direction::Left, State::Carry));
+    rules
+}
+
+#[test]
+fn exactly_one_plus_holds_on_every_vector() {
+    let invariants: Vec<Box<dyn Invariant>> = vec![Box::new(ExactlyOnePlus)];
+    for (a, b, sum) in adder_vectors() {
+        let mut machine = adder(&a, &b, rules::adder());
+        machine.run_with_invariants(&invariants).unwrap_or_else(|violation| panic!("{}+{}: {}", a, b, violation));
+        assert_eq!(machine.result(), sum, "{}+{}", a, b);
+    }
+}
+
+#[test]
+fn corrupted_rule_breaks_exactly_one_plus_at_a_known_step() {
+    let invariants: Vec<Box<dyn Invariant>> = vec![Box::new(ExactlyOnePlus)];
+    let violation = adder("1011", "11", corrupted_carry()).run_with_invariants(&invariants).unwrap_err();
+    assert_eq!(violation.step, 14);
+    assert_eq!(violation.message, "the tape has 2 separate runs of '+'");
+    assert_eq!(violation.snapshot.tape.iter().collect::<String>(), "_101+1+O_");
+    assert_eq!((violation.snapshot.head, &violation.snapshot.state), (4, &State::BackToStart));
+    // The corrupted carry fires at step 13, its `+` joining the separator's run, and the
+    // carry's 1 splits that run at step 14; up to then the run was the adder's own.
+    let mut clean = adder("1011", "11", rules::adder());
+    clean.run_bounded(12).unwrap_err();
+    let mut corrupted = adder("1011", "11", corrupted_carry());
+    corrupted.run_bounded(12).unwrap_err();
+    assert_eq!(corrupted.tape(), clean.tape());
+    corrupted.step();
+    assert_eq!(corrupted.tape().iter().collect::<String>(), "_101+++O_");
+}
+
+// Runs the adder on `a+b` under both invariants and the strict final-tape check, and
+// checks the sum and whether it took the separator's cell.
+fn run_strictly(a: &str, b: &str, sum: &str) {
+    let invariants: Vec<Box<dyn Invariant>> = vec![Box::new(ExactlyOnePlus), Box::new(MarkersAreSuffix)];
+    let mut machine = adder(a, b, rules::adder());
+    machine.run_with_invariants(&invariants).unwrap_or_else(|violation| panic!("{}+{}: {}", a, b, violation));
+    machine.verify_final_tape().unwrap_or_else(|problem| panic!("{}+{}: {}", a, b, problem));
+    assert_eq!(machine.result(), sum, "{}+{}", a, b);
+    // The sum takes the separator's cell exactly when it is wider than `b`.
+    let wider = sum.len() > b.trim_start_matches('0').len().max(1);
+    assert_eq!(machine.separator_consumed(), wider, "{}+{}: separator consumed", a, b);
+}
+
+#[test]
+fn markers_stay_a_suffix_on_every_vector() {
+    for (a, b, sum) in adder_vectors() {
+        run_strictly(&a, &b, &sum);
+    }
+}
+
+#[test]
+fn markers_stay_a_suffix_on_random_inputs() {
+    let mut rng = SplitMix64::new(7);
+    for _ in 0..200 {
+        let input = random_input(&mut rng, 16);
+        let (a, b) = input.split_once('+').unwrap();
+        let sum = u64::from_str_radix(a, 2).unwrap() + u64::from_str_radix(b, 2).unwrap();
+        run_strictly(a, b, &format!("{:b}", sum));
+    }
+}
+
+#[test]
+fn lopsided_operands_pass_the_strict_verifier() {
+    run_strictly("1", "11111111", "100000000");
+    run_strictly("11111111", "1", "100000000");
+}
+
+#[test]
+fn digit_after_a_marker_breaks_the_suffix() {
+    let tape: Tape = "_1+I1O_".chars().collect();
+    let configuration = Configuration {
+        tape: &tape,
+        head: 3,
+        logical_head: 3,
+        state: &State::AddOne,
+        steps: 9,
+    };
+    let error = MarkersAreSuffix.check(&configuration).unwrap_err();
+    assert_eq!(error, "cell 4 holds '1' after the marker in cell 3");
+    let tape: Tape = "_1+1IO_".chars().collect();
+    let configuration = Configuration { tape: &tape, ..configuration };
+    assert_eq!(MarkersAreSuffix.check(&configuration), Ok(()));
+}
+
+#[test]
+fn ones_cost_no_more_than_zeros_without_a_carry() {
+    let steps = |a: &str, b: &str| {
+        let mut machine = TuringMachine::new(build_tape(a, b, LeadingZeros::Preserve).unwrap(), rules::adder());
+        machine.run_bounded(100_000).unwrap();
+        machine.steps()
+    };
+    let mut pairs = 0;
+    for width in 1..=6 {
+        let zeros = "0".repeat(width);
+        for a in 0..1u32 << width {
+            for b in (0..1u32 << width).filter(|b| a & b == 0) {
+                let (a, b) = (format!("{:0w$b}", a, w = width), format!("{:0w$b}", b, w = width));
+                assert_eq!(steps(&a, &b), steps(&zeros, &b), "{}+{}", a, b);
+                pairs += 1;
+            }
+        }
+    }
+    // Every pair of widths 1 to 6 with no bit in common: the sum of 3^width.
+    assert_eq!(pairs, 3 + 9 + 27 + 81 + 243 + 729);
+}
+
+// The sweep ends on the leading blank, the cell `FindPlus` starts a run on, and then
+// steps right to the first cell after it, where the run's first `FindPlus` step also
+// lands; so each round starts where the first did, whatever carries did to the tape.
+#[test]
+fn every_sweep_hands_over_on_the_leading_blank() {
+    let mut sweeps = 0;
+    let tapes = [("1011", "11"), ("1", "1"), ("111", "1"), ("1", "111"), ("0011", "01"), ("11111111", "1")];
+    for (a, b) in tapes {
+        let mut machine = TuringMachine::new(build_tape(a, b, LeadingZeros::Preserve).unwrap(), rules::adder());
+        machine.set_tape_mode(TapeMode::Strict);
+        machine.step();
+        let start = machine.head();
+        while machine.halt_reason().is_none() {
+            let (state, head) = (machine.state().clone(), machine.head());
+            machine.step();
+            if state != State::BackToStart || *machine.state() == State::BackToStart {
+                continue;
+            }
+            let leading = machine.tape().iter().position(|&c| c != '_').map_or(0, |first| first.saturating_sub(1));
+            assert_eq!(head, leading, "{}+{}: the sweep ended off the leading blank", a, b);
+            assert_eq!(*machine.state(), State::FindPlus, "{}+{}: the sweep handed over to {}", a, b, machine.state());
+            assert_eq!(machine.head(), start, "{}+{}: FindPlus starts on cell {}", a, b, machine.head());
+            sweeps += 1;
+        }
+    }
+    assert_eq!(sweeps, 21);
+}
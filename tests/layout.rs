@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use turing::prelude::*;
+use turing::tape::input::build_tape_with_layout;
+use turing::tape::layout::TapeLayout;
+
+// The adder on `a+b` with the tape's layout, ready to run.
+fn adder(a: &str, b: &str) -> TuringMachine {
+    let (tape, layout) = build_tape_with_layout(a, b, LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    machine.set_layout(layout);
+    machine
+}
+
+#[test]
+fn builder_records_the_adder_regions() {
+    let machine = adder("1011", "11");
+    assert_eq!(machine.layout(), Some(&TapeLayout::adder(4, 2)));
+    assert_eq!(machine.region("operand_a"), Some(1..5));
+    assert_eq!(machine.region("separator"), Some(5..6));
+    assert_eq!(machine.region("operand_b"), Some(6..8));
+    assert_eq!(machine.slice("operand_a").as_deref(), Some("1011"));
+    assert_eq!(machine.slice("operand_b").as_deref(), Some("11"));
+    assert_eq!(machine.region("nope"), None);
+}
+
+#[test]
+fn regions_follow_the_run() {
+    let mut machine = adder("1011", "11");
+    machine.run_bounded(10_000).unwrap();
+    assert_eq!(machine.region("operand_a"), Some(1..1));
+    assert_eq!(machine.region("separator"), Some(1..4));
+    assert_eq!(machine.region("operand_b"), Some(4..8));
+    assert_eq!(machine.slice("separator").as_deref(), Some("+++"));
+    assert_eq!(machine.slice("operand_b").as_deref(), Some("IIIO"));
+    assert_eq!(machine.verify_final_tape(), Ok(()));
+    assert_eq!(machine.result(), "1110");
+}
+
+#[test]
+fn carry_grows_the_second_operand_leftward() {
+    // Every digit carries, out past the second operand and over the separator's cell.
+    let mut machine = adder("111", "1");
+    assert_eq!(machine.region("operand_b"), Some(5..6));
+    machine.run_bounded(1_000).unwrap();
+    assert_eq!(machine.tape().iter().collect::<String>(), "_+1OOO_");
+    assert_eq!(machine.region("separator"), Some(1..2));
+    assert_eq!(machine.region("operand_b"), Some(2..6));
+    assert_eq!(machine.slice("operand_b").as_deref(), Some("1OOO"));
+    assert!(machine.separator_consumed());
+    assert_eq!(machine.verify_final_tape(), Ok(()));
+    assert_eq!(machine.result(), "1000");
+}
+
+#[test]
+fn growth_on_the_left_keeps_the_regions() {
+    // One step left off the leading blank grows the tape by a cell on the left.
+    let rules = RuleTable::from(HashMap::from([((State::FindPlus, '_'), ('_', Direction::Left, State::Halt))]));
+    let (tape, layout) = build_tape_with_layout("10", "1", LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape, rules);
+    machine.set_layout(layout);
+    machine.step();
+    assert_eq!(machine.tape().iter().collect::<String>(), "__10+1_");
+    assert_eq!(machine.region("operand_a"), Some(1..3));
+    assert_eq!(machine.layout().unwrap().cells("operand_a"), Some(2..4));
+    assert_eq!(machine.slice("operand_a").as_deref(), Some("10"));
+    assert_eq!(machine.slice("separator").as_deref(), Some("+"));
+    assert_eq!(machine.slice("operand_b").as_deref(), Some("1"));
+}
+
+#[test]
+fn final_tape_problems_are_explained() {
+    let machine = adder("1011", "11");
+    assert_eq!(machine.verify_final_tape(), Err("the first operand still holds 1011".to_string()));
+    let mut machine = TuringMachine::new(build_tape("1", "1", LeadingZeros::Strip).unwrap(), rules::adder());
+    machine.run_bounded(1_000).unwrap();
+    assert_eq!(machine.verify_final_tape(), Err("the machine has no tape layout".to_string()));
+}
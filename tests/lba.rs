@@ -0,0 +1,91 @@
+use turing::prelude::*;
+use turing::tape::bounded::{self, BoundedError};
+
+// A state of the palindrome checker.
+fn state(name: &str) -> State {
+    State::Named(name.to_string())
+}
+
+// Runs the palindrome checker on `word` between the end markers, checking that the tape
+// kept its length, and whether it accepted.
+fn decide(word: &str) -> bool {
+    let mut machine = bounded::machine(word, rules::palindrome(), state("Pick"), '<', '>').unwrap();
+    machine.set_final_states(vec![state("Accept"), state("Reject")]);
+    machine.run_bounded(10_000).unwrap_or_else(|error| panic!("{}: {}", word, error));
+    assert_eq!(machine.tape().len(), word.len() + 2, "{}: the tape grew", word);
+    match machine.halt_reason() {
+        Some(HaltReason::Finished(halted)) if *halted == state("Accept") => true,
+        Some(HaltReason::Finished(halted)) if *halted == state("Reject") => false,
+        reason => panic!("{}: halted with {:?}", word, reason),
+    }
+}
+
+// Runs the palindrome checker, as changed by `rules`, on `ab`.
+fn run_on_ab(rules: RuleTable) -> Result<usize, RunError> {
+    let mut machine = bounded::machine("ab", rules, state("Pick"), '<', '>').unwrap();
+    machine.run_bounded(10_000)
+}
+
+#[test]
+fn palindromes_are_accepted() {
+    for word in ["", "a", "b", "aa", "aba", "abba", "babbab", "aabbbaa"] {
+        assert!(decide(word), "{}", word);
+    }
+}
+
+#[test]
+fn other_words_are_rejected() {
+    for word in ["ab", "ba", "abb", "aab", "abab", "abbbab"] {
+        assert!(!decide(word), "{}", word);
+    }
+}
+
+#[test]
+fn exactly_the_palindromes_up_to_eight_letters_are_accepted() {
+    let mut words = vec![String::new()];
+    for length in 1..=8 {
+        for bits in 0..1u32 << length {
+            words.push((0..length).map(|i| if bits >> i & 1 == 1 { 'b' } else { 'a' }).collect());
+        }
+    }
+    assert_eq!(words.len(), 511);
+    for word in &words {
+        let palindrome = word.chars().eq(word.chars().rev());
+        assert_eq!(decide(word), palindrome, "{}", word);
+    }
+}
+
+#[test]
+fn moving_past_a_marker_is_an_error() {
+    // Walking right off the word instead of turning back at `>` would grow the tape.
+    let mut past = rules::palindrome();
+    past.insert((state("HaveA"), '>'), ('>', Direction::Right, state("CheckA")));
+    let error = run_on_ab(past).unwrap_err();
+    assert_eq!(error, RunError::PastMarker { step: 4, marker: '>' });
+    assert_eq!(error.to_string(), "step 4 would have moved the head past the end marker '>'");
+}
+
+#[test]
+fn a_marker_no_rule_reads_is_an_error() {
+    let mut stuck = rules::palindrome();
+    stuck.remove(&(state("HaveA"), '>'));
+    let error = run_on_ab(stuck).unwrap_err();
+    assert!(matches!(error, RunError::StuckOnMarker { marker: '>', .. }), "{}", error);
+}
+
+#[test]
+fn rules_and_tapes_that_break_the_bound_are_rejected() {
+    let mut overwrite = rules::palindrome();
+    overwrite.insert((state("Pick"), '<'), ('_', Direction::Right, state("Pick")));
+    let error = bounded::machine("ab", overwrite, state("Pick"), '<', '>').err();
+    assert!(matches!(error, Some(BoundedError::OverwritesMarker { marker: '<', write: '_', .. })), "{:?}", error);
+
+    let mut writes = rules::palindrome();
+    writes.insert((state("Pick"), 'x'), ('>', Direction::Right, state("Pick")));
+    let error = bounded::check_rules(&writes, '<', '>');
+    assert!(matches!(error, Err(BoundedError::WritesMarker { read: 'x', marker: '>', .. })), "{:?}", error);
+
+    assert_eq!(bounded::tape("a>b", '<', '>'), Err(BoundedError::StrayMarker { cell: 2, marker: '>' }));
+    assert_eq!(bounded::check_tape(&['a', '>'], '<', '>'), Err(BoundedError::MissingMarker('<')));
+    assert_eq!(bounded::check_tape(&['<', 'a'], '<', '>'), Err(BoundedError::MissingMarker('>')));
+}
@@ -0,0 +1,56 @@
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::testing::SplitMix64;
+use turing::trace::json::Json;
+use turing::trace::replay::Trace;
+
+// The adder's answer for `a` and `b`, and the one its JSON trace records.
+fn add(a: &str, b: &str, leading_zeros: LeadingZeros) -> (String, String) {
+    let mut machine = TuringMachine::new(build_tape(a, b, leading_zeros).unwrap(), rules::adder());
+    let trace = Trace::record(&mut machine, 1_000_000).unwrap();
+    let json = trace.to_json();
+    let recorded = json.get("payload").and_then(|payload| payload.get("result")).and_then(Json::as_str);
+    (machine.result(), recorded.unwrap().to_string())
+}
+
+// What `tm add` prints for `args`.
+fn tm_add(args: &[&str]) -> String {
+    let assert = Command::cargo_bin("tm").unwrap().arg("add").args(args).assert().success();
+    String::from_utf8(assert.get_output().stdout.clone()).unwrap()
+}
+
+#[test]
+fn leading_zeros_are_stripped_by_default() {
+    assert_eq!(add("0011", "01", LeadingZeros::Strip), ("100".to_string(), "100".to_string()));
+    assert_eq!(add("000", "0", LeadingZeros::Strip), ("0".to_string(), "0".to_string()));
+    let tape = parse_input("0011+01", LeadingZeros::Strip).unwrap();
+    assert_eq!(tape, build_tape("11", "1", LeadingZeros::Strip).unwrap());
+    assert_eq!(tm_add(&["0011", "01"]), "100\n");
+    assert_eq!(tm_add(&["0011+01"]), "100\n");
+}
+
+#[test]
+fn preserved_zeros_give_a_result_as_wide_as_the_operands() {
+    assert_eq!(add("0011", "0001", LeadingZeros::Preserve).0, "0100");
+    assert_eq!(add("0000", "0000", LeadingZeros::Preserve).0, "0000");
+    assert_eq!(tm_add(&["--preserve-leading-zeros", "0011", "0001"]), "0100\n");
+    let mut rng = SplitMix64::new(101);
+    for width in 1..=12 {
+        for _ in 0..8 {
+            let (a, b) = (rng.next_u64() % (1 << width), rng.next_u64() % (1 << width));
+            let (a_bits, b_bits) = (format!("{:0w$b}", a, w = width), format!("{:0w$b}", b, w = width));
+            let (result, recorded) = add(&a_bits, &b_bits, LeadingZeros::Preserve);
+            assert_eq!(result, recorded);
+            // The width of the operands, and one more digit only for a carry out of the top.
+            let sum = a + b;
+            let expected = if sum >> width == 0 { format!("{:0w$b}", sum, w = width) } else { format!("{:b}", sum) };
+            assert_eq!(result, expected, "{}+{}", a_bits, b_bits);
+        }
+    }
+}
+
+#[test]
+fn preserved_zeros_of_different_widths_widen_to_the_wider_operand() {
+    assert_eq!(add("0011", "01", LeadingZeros::Preserve).0, "0100");
+    assert_eq!(add("01", "0011", LeadingZeros::Preserve).0, "0100");
+}
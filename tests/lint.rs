@@ -0,0 +1,55 @@
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::rules::lint::{lint, LintWarning};
+
+// The input alphabet of the adder, blank and markers included.
+const ADDER_ALPHABET: [char; 6] = ['_', '0', '1', '+', 'I', 'O'];
+
+// The adder with a rule `FindPlus` fires on 'x' into a state that spins forever, as
+// tests/fixtures/junk_rule.tm has it.
+fn with_junk_rule() -> RuleTable {
+    let spin = State::Named("Spin".to_string());
+    let mut rules = rules::adder();
+    rules.insert((State::FindPlus, 'x'), ('x', Direction::Right, spin.clone()));
+    for symbol in ADDER_ALPHABET {
+        rules.insert((spin.clone(), symbol), (symbol, Direction::Right, spin.clone()));
+    }
+    rules
+}
+
+#[test]
+fn adder_is_clean() {
+    assert_eq!(lint(&rules::adder(), &ADDER_ALPHABET), []);
+    assert_eq!(lint(&rules::increment(), &['_', '0', '1']), []);
+}
+
+#[test]
+fn junk_rule_and_spinning_state_are_reported() {
+    let warnings = lint(&with_junk_rule(), &ADDER_ALPHABET);
+    let spin = State::Named("Spin".to_string());
+    assert_eq!(
+        warnings,
+        [LintWarning::UnreadableSymbol { state: State::FindPlus, symbol: 'x' }, LintWarning::HaltUnreachable(spin)]
+    );
+    let codes: Vec<&str> = warnings.iter().map(LintWarning::code).collect();
+    assert_eq!(codes, ["unreadable-symbol", "halt-unreachable"]);
+}
+
+#[test]
+fn a_symbol_in_the_alphabet_is_readable() {
+    // Spin then halts too, on an 'x' it has no rule for.
+    let mut alphabet = ADDER_ALPHABET.to_vec();
+    alphabet.push('x');
+    assert_eq!(lint(&with_junk_rule(), &alphabet), []);
+}
+
+#[test]
+fn tm_rules_lint_reports_the_fixture() {
+    Command::cargo_bin("tm").unwrap().args(["rules", "lint"]).assert().success().stdout("");
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/junk_rule.tm");
+    let assert = Command::cargo_bin("tm").unwrap().args(["rules", "lint", "--rules", fixture]).assert().code(1);
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let warnings: Vec<String> = lint(&with_junk_rule(), &ADDER_ALPHABET).iter().map(|w| format!("{}\n", w)).collect();
+    assert_eq!(stdout, warnings.concat());
+    assert!(stdout.starts_with("[unreadable-symbol] the rule for state FindPlus reading 'x'"), "{}", stdout);
+}
@@ -0,0 +1,97 @@
+use assert_cmd::Command;
+use turing::machine::lockstep::Lockstep;
+use turing::prelude::*;
+
+fn adder() -> TuringMachine {
+    TuringMachine::new(build_tape("1011", "11", LeadingZeros::Strip).unwrap(), rules::adder())
+}
+
+#[test]
+fn the_same_machine_twice_never_parts() {
+    let steps = adder().run_bounded(10_000).unwrap();
+    let mut lockstep = Lockstep::new(adder(), adder());
+    let mut ticks = 0;
+    while let Some(tick) = lockstep.tick() {
+        ticks += 1;
+        assert_eq!(tick.tick, ticks);
+        assert_eq!(tick.sides[0], tick.sides[1], "tick {}", ticks);
+        assert!(!tick.diverged);
+    }
+    // The last tick is the one that finds no rule and halts, which takes no step.
+    assert_eq!(ticks, steps + 1);
+    assert_eq!(lockstep.first_divergence(), None);
+    assert!(lockstep.machines().iter().all(|machine| machine.steps() == steps));
+    assert!(lockstep.halted());
+    assert_eq!(lockstep.tick(), None);
+}
+
+#[test]
+fn a_faster_machine_waits_once_halted() {
+    let steps = adder().run_bounded(10_000).unwrap();
+    let mut lockstep = Lockstep::new(adder(), adder()).with_pace(1, 3);
+    let mut right_halted = false;
+    while let Some(tick) = lockstep.tick() {
+        let [left, right] = &tick.sides;
+        assert_eq!(left.stepped, 1);
+        assert_eq!(left.steps, tick.tick.min(steps));
+        assert_eq!(right.steps, (3 * tick.tick).min(steps));
+        if right_halted {
+            assert_eq!(right.stepped, 0, "tick {}: a halted machine stepped", tick.tick);
+        }
+        right_halted = right.halted;
+        assert_eq!(tick.diverged, left.result != right.result);
+    }
+    assert_eq!(lockstep.first_divergence(), Some(4));
+    let [left, right] = lockstep.machines();
+    assert_eq!(left.result(), right.result());
+}
+
+#[test]
+fn a_pace_of_zero_counts_as_one() {
+    let mut lockstep = Lockstep::new(adder(), adder()).with_pace(0, 0);
+    let tick = lockstep.tick().unwrap();
+    assert_eq!((tick.sides[0].stepped, tick.sides[1].stepped), (1, 1));
+}
+
+#[test]
+fn a_broken_rule_parts_the_answers_where_it_fires() {
+    let mut broken = rules::adder();
+    broken.insert((State::AddDigitOne, '0'), ('O', Direction::Left, State::BackToStart));
+    let tape = build_tape("1", "10", LeadingZeros::Strip).unwrap();
+    let right = TuringMachine::new(tape.clone(), rules::adder());
+    let mut lockstep = Lockstep::new(right, TuringMachine::new(tape, broken));
+    let mut apart = None;
+    while let Some(tick) = lockstep.tick() {
+        let writes = tick.sides[0].state == State::BackToStart && tick.sides[0].stepped == 1;
+        if apart.is_none() && tick.diverged {
+            assert!(writes, "tick {}: the answers parted before the broken rule fired", tick.tick);
+            apart = Some(tick.tick);
+        }
+    }
+    assert!(apart.is_some());
+    assert_eq!(lockstep.first_divergence(), apart);
+    let [right, wrong] = lockstep.machines();
+    assert_eq!((right.result().as_str(), wrong.result().as_str()), ("11", "10"));
+}
+
+#[test]
+fn compare_live_prints_paired_lines() {
+    let output = Command::cargo_bin("tm")
+        .unwrap()
+        .args(["compare-live", "--machines", "add,add", "--input", "1011+11", "--pace", "1,3"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1 + 64 + 1, "{}", stdout);
+    assert!(lines[1].starts_with("     0       0 FindPlus"), "{}", lines[1]);
+    assert!(lines[5].ends_with("<- answers differ"), "{}", lines[5]);
+    assert_eq!(lines[65], "the answers first differed after tick 4");
+
+    let args = ["compare-live", "--machines", "add", "--input", "1+1"];
+    let output = Command::cargo_bin("tm").unwrap().args(args).output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("--machines expects two machines"), "{}", stderr);
+}
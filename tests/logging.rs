@@ -0,0 +1,80 @@
+use std::sync::{Mutex, Once};
+use std::thread::{self, ThreadId};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use turing::prelude::*;
+use turing::Verbosity;
+
+// A logged record: the thread that logged it, its target, level and message.
+type Captured = (ThreadId, String, Level, String);
+
+// Keeps every record, so each test can look at the ones its own thread logged.
+struct Capture(Mutex<Vec<Captured>>);
+
+impl Log for Capture {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let captured = (thread::current().id(), record.target().to_string(), record.level(), record.args().to_string());
+        self.0.lock().unwrap().push(captured);
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+// Runs `f` and returns the records it logged, as `(target, level, message)`.
+fn logged(f: impl FnOnce()) -> Vec<(String, Level, String)> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&CAPTURE).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+    });
+    let before = CAPTURE.0.lock().unwrap().len();
+    f();
+    let id = thread::current().id();
+    let records = CAPTURE.0.lock().unwrap();
+    records[before..].iter().filter(|r| r.0 == id).map(|r| (r.1.clone(), r.2, r.3.clone())).collect()
+}
+
+#[test]
+fn warns_when_the_tape_lacks_the_leading_blank() {
+    let records = logged(|| {
+        TuringMachine::new("1+1_".chars().collect(), rules::adder());
+    });
+    let warning = "the tape does not start with a blank, so it will grow left if the head walks off it";
+    assert_eq!(records, [(String::from("turing"), Level::Warn, String::from(warning))]);
+    let records = logged(|| {
+        TuringMachine::new("_1+1_".chars().collect(), rules::adder());
+    });
+    assert!(records.is_empty(), "{:?}", records);
+}
+
+#[test]
+fn logs_every_step_at_trace_level() {
+    let records = logged(|| {
+        let mut machine = TuringMachine::new(build_tape("1", "1", LeadingZeros::Strip).unwrap(), rules::adder());
+        machine.run();
+    });
+    let steps: Vec<_> = records.iter().filter(|r| r.1 == Level::Trace).collect();
+    assert_eq!(steps.len(), 12);
+    assert_eq!(steps[0].2, "step 1: state FindPlus reads '_' at cell 0, writes '_', moves R, goes to FindPlus");
+}
+
+#[test]
+fn verbosity_logs_to_the_step_target_instead_of_printing() {
+    let records = logged(|| {
+        let mut machine = TuringMachine::new(build_tape("1", "1", LeadingZeros::Strip).unwrap(), rules::adder());
+        machine.set_verbosity(Verbosity::Steps);
+        machine.run();
+    });
+    let lines: Vec<_> = records.iter().filter(|r| r.0 == trace::STEP_TARGET).collect();
+    // One line before every step, the last finding no rule, and one for the final tape.
+    assert_eq!(lines.len(), 14);
+    assert!(lines.iter().all(|line| line.1 == Level::Info));
+    assert_eq!(lines[0].2, "Tape: ['_', '1', '+', '1', '_'], Head: 0, State: FindPlus");
+    assert!(lines[13].2.starts_with("Final Tape: "), "{}", lines[13].2);
+}
@@ -0,0 +1,56 @@
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::tape::input::doubling_tape;
+
+// Runs the built-in doubling machine on `2^n`, returning its answer, its steps and the
+// length its tape grew to.
+fn double(n: usize, max_steps: usize) -> Result<(String, usize, usize), RunError> {
+    let definition = registry::get("double").unwrap();
+    let mut machine = TuringMachine::new(doubling_tape(n), definition.rules);
+    machine.set_state(definition.start);
+    machine.set_result_strategy(definition.result);
+    machine.set_final_states(definition.final_states);
+    let steps = machine.run_bounded(max_steps)?;
+    Ok((machine.result(), steps, machine.tape().len()))
+}
+
+#[test]
+fn small_parameter_gives_the_exact_result_and_steps() {
+    assert_eq!(double(5, 10_000), Ok(("1".repeat(32), 853, 40)));
+    assert_eq!(double(8, 100_000), Ok(("1".repeat(256), 44_808, 267)));
+}
+
+#[test]
+fn compiled_machine_takes_the_same_steps() {
+    let definition = registry::get("double").unwrap();
+    let mut machine = TuringMachine::new(doubling_tape(8), definition.rules);
+    machine.set_state(definition.start);
+    machine.set_result_strategy(definition.result);
+    machine.set_final_states(definition.final_states);
+    let mut compiled = machine.compile().unwrap();
+    compiled.run();
+    assert_eq!((compiled.result(), compiled.steps()), ("1".repeat(256), 44_808));
+    assert!(compiled.finished_successfully());
+}
+
+#[test]
+fn step_limit_stops_the_small_parameter() {
+    assert_eq!(double(5, 852), Err(RunError::StepLimitExceeded(852)));
+    let assert = Command::cargo_bin("tm").unwrap().args(["demo", "long-run", "--n", "5", "--max-steps", "500"]).assert();
+    let stderr = String::from_utf8(assert.code(2).get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("the machine did not halt within 500 steps"), "{}", stderr);
+}
+
+#[test]
+fn tm_demo_long_run_prints_the_result_and_steps() {
+    let assert = Command::cargo_bin("tm").unwrap().args(["demo", "long-run", "--n", "5"]).assert().success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.starts_with("2^5 = 32 ones\n853 steps, 40 tape cells, "), "{}", stdout);
+}
+
+// The default parameter, millions of steps: `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn large_parameter_gives_the_exact_result_and_steps() {
+    assert_eq!(double(12, 20_000_000), Ok(("1".repeat(4096), 11_201_384, 4111)));
+}
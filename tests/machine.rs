@@ -0,0 +1,142 @@
+use turing::prelude::*;
+use turing::tape::input::ParseError;
+
+#[test]
+fn debug_is_one_line_without_the_rules() {
+    let mut machine = TuringMachine::new(build_tape("1011", "11", LeadingZeros::Strip).unwrap(), rules::adder());
+    let expected = r#"TuringMachine { state: FindPlus, head: 0, tape: "_1011+11_", steps: 0 }"#;
+    assert_eq!(format!("{:?}", machine), expected);
+    assert_eq!(format!("{:#?}", machine), expected);
+    machine.run();
+    assert_eq!(format!("{:?}", machine), r#"TuringMachine { state: Halt, head: 0, tape: "_+++IIIO_", steps: 62 }"#);
+}
+
+fn adder(a: &str, b: &str) -> TuringMachine {
+    TuringMachine::new(build_tape(a, b, LeadingZeros::Strip).unwrap(), rules::adder())
+}
+
+#[test]
+fn machines_in_the_same_configuration_are_equal() {
+    let (mut left, mut right) = (adder("1011", "11"), adder("1011", "11"));
+    assert_eq!(left, right);
+    left.run();
+    assert_ne!(left, right);
+    // Settings, the step limit and how the run went are left out.
+    right.set_tracing(true);
+    right.set_snapshot_capacity(4);
+    right.run_bounded(1_000).unwrap();
+    assert_eq!(left, right);
+}
+
+#[test]
+fn equivalent_runs_end_equal() {
+    let mut swapped = adder("11", "1011");
+    let mut machine = adder("1011", "11");
+    swapped.run();
+    machine.run();
+    assert_eq!(swapped.result(), machine.result());
+    assert_ne!(swapped, machine, "the tapes keep their own markers");
+    let mut again = adder("1011", "11");
+    again.run();
+    assert_eq!(again, machine);
+}
+
+#[test]
+fn different_rules_make_machines_differ() {
+    let mut rules = rules::adder();
+    rules.insert((State::Named(String::from("Unused")), '1'), ('1', Direction::Right, State::Halt));
+    let extra = TuringMachine::new(build_tape("1", "1", LeadingZeros::Strip).unwrap(), rules);
+    let plain = adder("1", "1");
+    assert_ne!(extra, plain);
+    assert!(extra.same_configuration(&plain));
+}
+
+#[test]
+fn grown_blanks_make_tapes_differ() {
+    let plain = adder("1", "1");
+    let padded = TuringMachine::new("_1+1__".chars().collect(), rules::adder());
+    assert_eq!(padded.result(), plain.result());
+    assert_ne!(padded, plain);
+    assert!(!padded.same_configuration(&plain));
+}
+
+#[test]
+fn run_until_stops_when_the_tape_matches() {
+    let mut machine = adder("1011", "11");
+    let marked = |tape: &[char]| tape.iter().any(|&symbol| symbol == 'I' || symbol == 'O');
+    assert_eq!(machine.run_until(marked, 1_000), Ok(true));
+    let steps = machine.steps();
+    assert!(steps > 0 && steps < 62, "{}", steps);
+    assert_ne!(machine.state(), &State::Halt);
+    // The predicate is checked before every step, so the step before didn't match.
+    assert_eq!(machine.tape().iter().filter(|&&symbol| symbol == 'I' || symbol == 'O').count(), 1);
+    machine.run();
+    assert_eq!((machine.result(), machine.steps()), (String::from("1110"), 62));
+
+    let mut matching = adder("1011", "11");
+    assert_eq!(matching.run_until(|tape| tape.contains(&'+'), 1_000), Ok(true));
+    assert_eq!(matching.steps(), 0);
+}
+
+#[test]
+fn run_until_runs_to_the_halt_when_the_tape_never_matches() {
+    let mut machine = adder("1011", "11");
+    assert_eq!(machine.run_until(|tape| tape.contains(&'x'), 1_000), Ok(false));
+    assert_eq!(machine.state(), &State::Halt);
+    assert_eq!((machine.result(), machine.steps()), (String::from("1110"), 62));
+
+    let mut limited = adder("1011", "11");
+    assert_eq!(limited.run_until(|tape| tape.contains(&'x'), 10), Err(RunError::StepLimitExceeded(10)));
+    assert_eq!(limited.steps(), 10);
+}
+
+#[test]
+fn pushing_the_second_operand_builds_the_same_tape() {
+    let mut pushed = TuringMachine::new(vec!['_', '1', '0', '1', '1', '_', '_'], rules::adder());
+    pushed.push_operand("11").unwrap();
+    assert_eq!(pushed, adder("1011", "11"));
+    assert_eq!(pushed.run_bounded(10_000), Ok(62));
+    assert_eq!(pushed.result(), "1110");
+}
+
+#[test]
+fn pushed_operands_keep_their_leading_zeros() {
+    let mut machine = adder("1011", "11");
+    machine.push_operand("0101").unwrap();
+    assert_eq!(machine.tape().iter().collect::<String>(), "_1011+11+0101_");
+}
+
+#[test]
+fn pushing_a_non_binary_operand_leaves_the_tape_alone() {
+    let mut machine = adder("1011", "11");
+    assert_eq!(machine.push_operand("12"), Err(ParseError::InvalidSymbol('2')));
+    assert_eq!(machine.push_operand("1I"), Err(ParseError::ReservedMarker('I')));
+    assert_eq!(machine, adder("1011", "11"));
+}
+
+// A machine put straight into the configuration `adder("1011", "11")` reaches after 6 steps.
+fn turned_back(tape: &str, head: usize, state: State) -> TuringMachine {
+    TuringMachine::builder(tape.chars().collect(), rules::adder())
+        .with_head(head)
+        .with_state(state)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn configurations_reached_differently_share_a_key() {
+    let mut stepped = adder("1011", "11");
+    for _ in 0..6 {
+        stepped.step();
+    }
+    assert_eq!(stepped.config_key(), turned_back("_1011+11_", 4, State::GetLast).config_key());
+    assert_eq!(stepped.config_key(), turned_back("_1011+11___", 4, State::GetLast).config_key());
+}
+
+#[test]
+fn a_different_head_or_state_changes_the_key() {
+    let key = turned_back("_1011+11_", 4, State::GetLast).config_key();
+    assert_ne!(key, turned_back("_1011+11_", 3, State::GetLast).config_key());
+    assert_ne!(key, turned_back("_1011+11_", 4, State::AddOne).config_key());
+    assert_ne!(key, turned_back("_1011+10_", 4, State::GetLast).config_key());
+}
@@ -0,0 +1,65 @@
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::rules::mutate::{mutate, mutation_test, Mutation};
+use turing::testing::SplitMix64;
+
+// The mutants of the adder that the test vectors miss with seed 42, in the order drawn.
+const SURVIVORS_OF_SEED_42: [&str; 10] = [
+    "Carry 0: move S instead of L",
+    "BackToStart _: move S instead of R",
+    "GetLast 0: move L instead of R",
+    "GetLast 1: move S instead of R",
+    "AddZero O: write 0 instead of O",
+    "BackToStart _: move L instead of R",
+    "Carry 0: move S instead of L",
+    "AddZero O: write 0 instead of O",
+    "Carry 0: move S instead of L",
+    "AddDigitZero +: write 1 instead of O",
+];
+
+#[test]
+fn fixed_seed_gives_a_fixed_outcome() {
+    let outcomes = mutation_test(&rules::adder(), 200, 42).unwrap();
+    assert_eq!(outcomes.len(), 200);
+    assert_eq!(outcomes.iter().filter(|outcome| outcome.killed).count(), 190);
+    let survivors: Vec<String> = outcomes.iter().filter(|o| !o.killed).map(|o| o.mutation.to_string()).collect();
+    assert_eq!(survivors, SURVIVORS_OF_SEED_42);
+    assert_eq!(mutation_test(&rules::adder(), 200, 42).unwrap(), outcomes);
+    assert_ne!(mutation_test(&rules::adder(), 200, 43).unwrap(), outcomes);
+}
+
+#[test]
+fn a_mutation_changes_exactly_one_rule() {
+    let adder = rules::adder();
+    let mut rng = SplitMix64::new(7);
+    for _ in 0..200 {
+        let (mutant, mutation) = mutate(&adder, &mut rng);
+        let changed = adder.iter().filter(|(key, value)| mutant.get(*key) != Some(*value)).count();
+        assert_eq!(changed, 1, "{}", mutation);
+        let (state, read) = match &mutation {
+            Mutation::Write { state, read, .. }
+            | Mutation::Move { state, read, .. }
+            | Mutation::NextState { state, read, .. }
+            | Mutation::Delete { state, read } => (state, read),
+        };
+        assert_ne!(mutant.get(&(state.clone(), *read)), adder.get(&(state.clone(), *read)), "{}", mutation);
+        assert_eq!(mutant.len() + usize::from(matches!(mutation, Mutation::Delete { .. })), adder.len());
+    }
+}
+
+#[test]
+fn a_table_failing_the_vectors_is_not_mutated() {
+    let mut broken = rules::adder();
+    broken.insert((State::Carry, '1'), ('1', Direction::Left, State::Carry));
+    assert_eq!(mutation_test(&broken, 10, 0), None);
+    assert_eq!(mutation_test(&RuleTable::new(), 10, 0), None);
+}
+
+#[test]
+fn tm_mutate_prints_the_kill_rate_and_survivors() {
+    let args = ["mutate", "--rules", "builtin:add", "--iterations", "200", "--seed", "42"];
+    let assert = Command::cargo_bin("tm").unwrap().args(args).assert().success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let survivors: String = SURVIVORS_OF_SEED_42.iter().map(|survivor| format!("  {}\n", survivor)).collect();
+    assert_eq!(stdout, format!("killed 190 of 200 mutants (95.0%)\nsurviving mutants:\n{}", survivors));
+}
@@ -0,0 +1,30 @@
+use std::path::Path;
+use std::process::Command;
+
+const TARGET: &str = "thumbv7em-none-eabihf";
+
+// Whether the standard library for `TARGET` is installed next to the compiler's own.
+fn target_installed() -> bool {
+    let output = Command::new("rustc").args(["--print", "sysroot"]).output().unwrap();
+    let sysroot = String::from_utf8(output.stdout).unwrap();
+    Path::new(sysroot.trim()).join("lib/rustlib").join(TARGET).is_dir()
+}
+
+#[test]
+fn the_core_machine_builds_without_std() {
+    if !target_installed() {
+        eprintln!("skipped: `rustup target add {}` to check the no_std build", TARGET);
+        return;
+    }
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // A target directory of its own, so the check doesn't wait on the one running the tests.
+    let output = Command::new(env!("CARGO"))
+        .args(["check", "--lib", "--no-default-features", "--target", TARGET])
+        .arg("--manifest-path")
+        .arg(manifest.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(manifest.join("target/no_std"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
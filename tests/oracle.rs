@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use turing::prelude::*;
+use turing::Oracle;
+
+// A state of the primality machine.
+fn state(name: &str) -> State {
+    State::Named(name.to_string())
+}
+
+// Walks right to the '#', steps onto the cell after it to ask the oracle, then moves into
+// Accept or Reject on its answer.
+fn primality_machine(number: &str) -> TuringMachine {
+    let rules = vec![
+        (state("Scan"), '_', '_', Direction::Right, state("Scan")),
+        (state("Scan"), '0', '0', Direction::Right, state("Scan")),
+        (state("Scan"), '1', '1', Direction::Right, state("Scan")),
+        (state("Scan"), '#', '#', Direction::Right, state("Query")),
+        (state("Query"), 'Y', 'Y', Direction::Right, state("Accept")),
+        (state("Query"), 'N', 'N', Direction::Right, state("Reject")),
+    ];
+    let tape = format!("_{}#_", number).chars().collect();
+    let mut machine = TuringMachine::with_rules(tape, rules).unwrap();
+    machine.set_state(state("Scan"));
+    machine
+}
+
+// Answers whether the binary number left of the '#' just behind the head is prime,
+// writing 'Y' or 'N' under the head.
+fn primality_oracle(tape: &mut Tape, head: isize) {
+    let head = head as usize;
+    let digits: String = tape.iter().take(head - 1).filter(|c| **c == '0' || **c == '1').collect();
+    let n = u64::from_str_radix(&digits, 2).unwrap_or(0);
+    let prime = n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d));
+    tape[head] = if prime { 'Y' } else { 'N' };
+}
+
+// Runs `machine` to the end and returns the last state a rule moved it into.
+fn verdict(machine: &mut TuringMachine) -> Option<State> {
+    let mut last = None;
+    machine.run_observing(|_, _, _, _, to| last = Some(to.clone()));
+    last
+}
+
+#[test]
+fn oracle_decides_primality() {
+    const PRIMES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    for n in 0..40u64 {
+        let number = format!("{:b}", n);
+        let mut machine = primality_machine(&number);
+        machine.set_oracle(state("Query"), primality_oracle);
+        let expected = if PRIMES.contains(&n) { "Accept" } else { "Reject" };
+        assert_eq!(verdict(&mut machine), Some(state(expected)), "{}", n);
+    }
+}
+
+#[test]
+fn oracle_calls_are_counted_apart_from_steps() {
+    let mut machine = primality_machine("1011");
+    machine.set_oracle(state("Query"), primality_oracle);
+    assert_eq!(verdict(&mut machine), Some(state("Accept")));
+    // The leading blank, four digits and the '#', then the answer.
+    assert_eq!(machine.steps(), 7);
+    assert_eq!(machine.oracle_calls(), 1);
+    assert_eq!(machine.tape().iter().collect::<String>(), "_1011#Y_");
+}
+
+#[test]
+fn oracle_is_only_asked_on_entering_its_state() {
+    let asked = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&asked);
+    let mut machine = primality_machine("1");
+    machine.set_oracle(state("Elsewhere"), move |_, _| {
+        counter.fetch_add(1, Ordering::Relaxed);
+    });
+    assert_eq!(verdict(&mut machine), Some(state("Query")));
+    assert_eq!((asked.load(Ordering::Relaxed), machine.oracle_calls()), (0, 0));
+}
+
+#[test]
+fn oracle_cannot_leave_the_head_off_the_tape() {
+    // An oracle that wipes the tape: the machine grows it back under the head and halts,
+    // finding no rule for the blank there.
+    let mut machine = primality_machine("11");
+    machine.set_oracle(state("Query"), |tape, _| tape.clear());
+    assert_eq!(verdict(&mut machine), Some(state("Query")));
+    assert_eq!(machine.oracle_calls(), 1);
+    assert!(machine.head() < machine.tape().len());
+    assert!(machine.tape().iter().all(|&c| c == '_'));
+}
+
+fn assert_send<T: Send>() {}
+
+#[test]
+fn oracles_can_be_sent_to_another_thread() {
+    assert_send::<Oracle>();
+}
@@ -0,0 +1,71 @@
+use turing::decode_result;
+use turing::machine::pipeline::{Pipeline, PipelineError};
+use turing::prelude::*;
+use turing::tape::input::chain_tape;
+
+// Rewrites a number on the tape (a plain `_n_` input or an adder's final tape) as `_n+1_`.
+fn increment_input(tape: &[char]) -> Vec<char> {
+    chain_tape(tape, "1", LeadingZeros::Strip).expect("adder output is binary")
+}
+
+// Adds 3 by incrementing three times, each stage being the adder with a `+1` appended.
+fn add_three(max_steps: usize) -> Pipeline {
+    Pipeline::new(max_steps)
+        .stage(rules::adder(), increment_input)
+        .stage(rules::adder(), increment_input)
+        .stage(rules::adder(), increment_input)
+}
+
+#[test]
+fn three_increments_add_three() {
+    for n in 0..64u32 {
+        let input: Vec<char> = format!("_{:b}_", n).chars().collect();
+        let output = add_three(10_000).run(&input).unwrap();
+        assert_eq!(decode_result(&output.tape), format!("{:b}", n + 3), "{}", n);
+        assert_eq!(output.stages.len(), 3);
+        assert!(output.stages.iter().all(|stage| stage.steps > 0 && stage.trace.is_empty()));
+    }
+}
+
+#[test]
+fn stages_report_their_own_steps_and_tapes() {
+    let output = add_three(10_000).run(&['_', '1', '0', '1', '1', '_']).unwrap();
+    let mut tape = vec!['_', '1', '0', '1', '1', '_'];
+    for stage in &output.stages {
+        let mut machine = TuringMachine::new(increment_input(&tape), rules::adder());
+        let steps = machine.run_bounded(10_000).unwrap();
+        assert_eq!((stage.steps, stage.tape_len), (steps, machine.tape().len()));
+        tape = machine.into_tape();
+    }
+    assert_eq!(output.tape, tape);
+    assert_eq!(decode_result(&output.tape), "1110");
+}
+
+#[test]
+fn tracing_records_every_stage() {
+    let output = add_three(10_000).tracing(true).run(&['_', '1', '_']).unwrap();
+    let mut tape = vec!['_', '1', '_'];
+    for stage in &output.stages {
+        let mut machine = TuringMachine::new(increment_input(&tape), rules::adder());
+        machine.set_tracing(true);
+        machine.run_bounded(10_000).unwrap();
+        assert_eq!(stage.trace, machine.trace());
+        assert_eq!(stage.trace.last().unwrap().state, State::Halt);
+        tape = machine.into_tape();
+    }
+}
+
+#[test]
+fn a_failing_stage_is_named_by_its_index() {
+    // 1+1 takes 12 steps, 10+1 takes 24.
+    let error = add_three(20).run(&['_', '1', '_']).unwrap_err();
+    assert_eq!(error, PipelineError { stage: 1, error: RunError::StepLimitExceeded(20) });
+    assert_eq!(error.to_string(), "stage 1: the machine did not halt within 20 steps");
+}
+
+#[test]
+fn an_empty_pipeline_hands_back_its_input() {
+    let output = Pipeline::new(10).run(&['_', '1', '_']).unwrap();
+    assert_eq!(output.tape, ['_', '1', '_']);
+    assert!(output.stages.is_empty());
+}
@@ -0,0 +1,131 @@
+use std::sync::mpsc;
+use std::thread;
+
+use turing::machine::stream::Backpressure;
+use turing::prelude::*;
+use turing::rules::Fingerprint;
+
+// The number of states of the counter, `C0` to `C99`.
+const STATES: usize = 100;
+
+// Counts the ones on the tape modulo 100 in its state, then writes the count's last digit
+// on the blank after them: the next state is the current one's number plus one.
+fn counter(state: &State, read: char) -> Option<(char, Direction, State)> {
+    let State::Named(name) = state else {
+        return None;
+    };
+    let count: usize = name.strip_prefix('C')?.parse().ok()?;
+    match read {
+        '1' => Some(('1', Direction::Right, State::Named(format!("C{}", (count + 1) % STATES)))),
+        '_' => Some((char::from_digit((count % 10) as u32, 10)?, Direction::Stay, State::Halt)),
+        _ => None,
+    }
+}
+
+fn states() -> Vec<State> {
+    (0..STATES).map(|count| State::Named(format!("C{}", count))).collect()
+}
+
+// `ones` ones and a blank, the head on the first one; the machine never moves left.
+fn tape(ones: usize) -> Vec<char> {
+    format!("{}_", "1".repeat(ones)).chars().collect()
+}
+
+fn procedural(ones: usize) -> TuringMachine {
+    let mut machine = TuringMachine::with_transition_fn(tape(ones), counter);
+    machine.set_state(State::Named(String::from("C0")));
+    machine
+}
+
+// The counter as an explicit table, enumerated from the procedural machine.
+fn table() -> RuleTable {
+    procedural(0).enumerate_rules(&states(), &['1', '_'])
+}
+
+fn explicit(ones: usize) -> TuringMachine {
+    let mut machine = TuringMachine::new(tape(ones), table());
+    machine.set_state(State::Named(String::from("C0")));
+    machine
+}
+
+#[test]
+fn enumerates_every_rule() {
+    assert_eq!(table().len(), 2 * STATES);
+}
+
+#[test]
+fn steps_and_traces_as_its_table_does() {
+    for ones in [0, 1, 9, 99, 100, 101, 250] {
+        let (mut a, mut b) = (procedural(ones), explicit(ones));
+        let (tape_a, trace_a) = a.run_collecting_trace();
+        let (tape_b, trace_b) = b.run_collecting_trace();
+        assert_eq!(trace_a, trace_b, "{} ones", ones);
+        assert_eq!((tape_a, a.steps()), (tape_b, b.steps()), "{} ones", ones);
+        let digit = char::from_digit((ones % STATES % 10) as u32, 10).unwrap();
+        assert_eq!(a.tape()[ones], digit, "{} ones", ones);
+    }
+}
+
+#[test]
+fn observes_as_its_table_does() {
+    for ones in [0, 1, 99, 250] {
+        let mut seen = (Vec::new(), Vec::new());
+        let (mut a, mut b) = (procedural(ones), explicit(ones));
+        a.run_observing(|from, read, _, _, to| seen.0.push((from, read, to.clone())));
+        b.run_observing(|from, read, _, _, to| seen.1.push((from, read, to.clone())));
+        assert_eq!(seen.0, seen.1, "{} ones", ones);
+    }
+}
+
+#[test]
+fn streams_as_its_table_does() {
+    let stream = |mut machine: TuringMachine| {
+        let (sender, receiver) = mpsc::sync_channel(1000);
+        let steps = machine.run_streaming(sender, Backpressure::Block).unwrap();
+        (steps, receiver.iter().collect::<Vec<_>>(), machine.halt_reason().cloned())
+    };
+    let (steps, events, halt) = stream(procedural(150));
+    assert_eq!((steps, events.len()), (151, 151));
+    assert_eq!((steps, events, halt), stream(explicit(150)));
+}
+
+#[test]
+fn stops_at_the_step_limit_as_its_table_does() {
+    let (mut a, mut b) = (procedural(250), explicit(250));
+    assert_eq!(a.run_bounded(100), Err(RunError::StepLimitExceeded(100)));
+    assert_eq!(b.run_bounded(100), Err(RunError::StepLimitExceeded(100)));
+    assert_eq!(a.snapshot(), b.snapshot());
+}
+
+#[test]
+fn exporters_need_a_finite_table() {
+    assert!(procedural(1).finite_rules().is_err());
+    assert!(procedural(1).compile().is_err());
+    assert_eq!(explicit(1).finite_rules().unwrap().fingerprint(), table().fingerprint());
+    let mut compiled = explicit(12).compile().unwrap();
+    compiled.run();
+    assert_eq!((compiled.steps(), compiled.tape()[12]), (13, '2'));
+}
+
+fn assert_send<T: Send>() {}
+
+#[test]
+fn machines_can_be_sent_to_another_thread() {
+    assert_send::<TuringMachine>();
+    let mut procedural = procedural(12);
+    let mut with_oracle = TuringMachine::new(vec!['_', '1', '_'], rules::increment());
+    with_oracle.set_state(State::Named(String::from("Start")));
+    with_oracle.set_oracle(State::Named(String::from("Done")), |_, _| {});
+    procedural = thread::spawn(move || {
+        procedural.run_bounded(1_000).unwrap();
+        procedural
+    })
+    .join()
+    .unwrap();
+    assert_eq!(procedural.tape().iter().collect::<String>(), "1111111111112");
+    let with_oracle = thread::spawn(move || {
+        with_oracle.run_bounded(1_000).unwrap();
+        with_oracle
+    });
+    assert_eq!(with_oracle.join().unwrap().oracle_calls(), 1);
+}
@@ -0,0 +1,122 @@
+use turing::machine::progress;
+use turing::prelude::*;
+use turing::testing::SplitMix64;
+use turing::testing;
+
+// Checks `estimated_remaining_steps` against the steps the adder actually has left, at
+// every step of the addition of `a` and `b`, with and without leading zeros. Returns the
+// number of configurations checked.
+fn check_estimates(a: &str, b: &str) -> usize {
+    let mut checked = 0;
+    for leading_zeros in [LeadingZeros::Strip, LeadingZeros::Preserve] {
+        let tape = build_tape(a, b, leading_zeros).unwrap();
+        let total = TuringMachine::new(tape.clone(), rules::adder()).run_bounded(1_000_000).unwrap();
+        let mut machine = TuringMachine::new(tape, rules::adder());
+        loop {
+            let expected = total - machine.steps();
+            let estimate = machine.estimated_remaining_steps();
+            assert_eq!(estimate, Some(expected), "{}+{} at step {}, {}", a, b, machine.steps(), machine.state());
+            checked += 1;
+            if machine.halt_reason().is_some() {
+                break;
+            }
+            machine.step();
+        }
+    }
+    checked
+}
+
+#[test]
+fn estimates_are_exact_for_small_operands() {
+    let mut checked = 0;
+    for a in 0..32u32 {
+        for b in 0..32u32 {
+            checked += check_estimates(&format!("{:b}", a), &format!("{:b}", b));
+        }
+    }
+    assert!(checked > 100_000, "{}", checked);
+}
+
+#[test]
+fn estimates_are_exact_for_wide_operands() {
+    let mut rng = SplitMix64::new(3);
+    for _ in 0..20 {
+        let input = testing::random_input(&mut rng, 24);
+        let (a, b) = input.split_once('+').unwrap();
+        check_estimates(a, b);
+    }
+    check_estimates("0011", "000001");
+    check_estimates("11111111", "1");
+}
+
+#[test]
+fn other_configurations_have_no_estimate() {
+    let mut increment = TuringMachine::new(vec!['_', '1', '_'], rules::increment());
+    increment.set_state(State::Named(String::from("Start")));
+    assert_eq!(increment.estimated_remaining_steps(), None);
+    let no_plus = TuringMachine::new(vec!['_', '1', '1', '_'], rules::adder());
+    assert_eq!(no_plus.estimated_remaining_steps(), None);
+}
+
+#[test]
+fn progress_comes_every_so_many_steps() {
+    let mut machine = TuringMachine::new(build_tape("1011", "11", LeadingZeros::Strip).unwrap(), rules::adder());
+    let mut reported = Vec::new();
+    let steps = machine.run_bounded_with_progress(10_000, 10, |machine| reported.push(machine.steps())).unwrap();
+    let expected: Vec<usize> = (1..).map(|n| n * 10).take_while(|&n| n < steps).collect();
+    assert_eq!(reported, expected);
+}
+
+#[test]
+fn cycles_are_caught_across_progress_reports() {
+    // Walks right and left between two cells forever, a cycle of period 2.
+    let mut rules = RuleTable::new();
+    let (there, back) = (State::Named(String::from("There")), State::Named(String::from("Back")));
+    rules.insert((there.clone(), '_'), ('_', Direction::Right, back.clone()));
+    rules.insert((back.clone(), '_'), ('_', Direction::Left, there.clone()));
+    let mut machine = TuringMachine::new(vec!['_', '_'], rules);
+    machine.set_state(there);
+    machine.set_tape_mode(TapeMode::Fixed);
+    let outcome = machine.run_bounded_with_progress(1_000, 1, |_| {});
+    assert_eq!(outcome, Err(RunError::Cycle { step: 2, period: 2 }));
+}
+
+// Every state the adder has.
+const ADDER_STATES: [State; 9] = [
+    State::FindPlus,
+    State::GetLast,
+    State::AddZero,
+    State::AddOne,
+    State::AddDigitZero,
+    State::AddDigitOne,
+    State::Carry,
+    State::BackToStart,
+    State::Halt,
+];
+
+#[test]
+fn heads_outside_the_operands_have_no_estimate() {
+    let tape: Tape = "__1+1_".chars().collect();
+    assert_eq!(progress::adder_remaining_steps(&tape, 0, &State::BackToStart), None);
+    assert_eq!(progress::adder_remaining_steps(&tape, 0, &State::AddDigitOne), None);
+    assert_eq!(progress::adder_remaining_steps(&tape, 5, &State::FindPlus), None);
+    // Every head in every state, on and off the tape, gives an estimate or none, never a panic.
+    for input in ["__1+1_", "_1011+11_", "_+++IIIO_", "1+1", "_10+_"] {
+        let tape: Tape = input.chars().collect();
+        for head in 0..tape.len() + 3 {
+            for state in &ADDER_STATES {
+                progress::adder_remaining_steps(&tape, head, state);
+            }
+        }
+    }
+}
+
+#[test]
+fn other_tapes_have_no_estimate() {
+    for input in ["_abc_", "_11_", "+11_", "1+1", ""] {
+        let tape: Tape = input.chars().collect();
+        for state in &ADDER_STATES[..8] {
+            assert_eq!(progress::adder_remaining_steps(&tape, 0, state), None, "{:?} in {}", input, state);
+        }
+    }
+}
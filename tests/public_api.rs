@@ -0,0 +1,161 @@
+use std::env;
+use std::fs;
+
+// The crate's source and the checked-in listing of its public items.
+const SRC_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+const LISTING: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/public_api.txt");
+
+// Lists every public item of the library, module by module and method under the `impl`
+// it belongs to, and compares the listing with tests/public_api.txt, so that an item made
+// public, changed or removed by accident fails the tests. A change that means to alter
+// the public surface updates the listing along with it: run
+// `BLESS=1 cargo test --test public_api` and commit the result.
+#[test]
+fn public_api_matches_the_listing() {
+    let listing = listing();
+    if env::var_os("BLESS").is_some() {
+        fs::write(LISTING, &listing).expect("the listing should be writable");
+        return;
+    }
+    let golden = fs::read_to_string(LISTING).expect("the listing should be readable");
+    let added: Vec<&str> = listing.lines().filter(|line| !golden.lines().any(|g| g == *line)).collect();
+    let removed: Vec<&str> = golden.lines().filter(|line| !listing.lines().any(|l| l == *line)).collect();
+    let diff: Vec<String> = removed.iter().map(|line| format!("- {}", line))
+        .chain(added.iter().map(|line| format!("+ {}", line)))
+        .collect();
+    assert!(diff.is_empty(), "the public API differs from {}:\n{}", LISTING, diff.join("\n"));
+}
+
+#[test]
+fn internal_modules_are_hidden() {
+    let listing = listing();
+    for module in ["cache", "reference", "rng", "testing"] {
+        assert!(!listing.lines().any(|line| line.starts_with(&format!("{}: ", module))), "{}", module);
+    }
+    for module in ["prelude", "error", "machine::compiled", "rules", "tape::input", "trace", "registry"] {
+        assert!(listing.lines().any(|line| line.starts_with(&format!("{}: ", module))), "{}", module);
+    }
+}
+
+// The listing of every module of the library, `lib` for the root itself, leaving out the
+// modules declared `#[doc(hidden)]`, which are there for the crate's own tests and tools.
+// Private modules count: their methods and re-exported items are public all the same.
+fn listing() -> String {
+    let mut modules = vec![(String::from("lib"), format!("{}/lib.rs", SRC_DIR))];
+    let mut listed = Vec::new();
+    while let Some((module, path)) = modules.pop() {
+        let source = fs::read_to_string(&path).expect("the source file should be readable");
+        // A module's children live in its directory, or beside lib.rs for the root.
+        let dir = match module.as_str() {
+            "lib" => SRC_DIR.to_string(),
+            module => format!("{}/{}", SRC_DIR, module.replace("::", "/")),
+        };
+        for child in modules_declared(&source) {
+            let nested = format!("{}/{}/mod.rs", dir, child);
+            let path = if fs::metadata(&nested).is_ok() { nested } else { format!("{}/{}.rs", dir, child) };
+            let name = if module == "lib" { child } else { format!("{}::{}", module, child) };
+            modules.push((name, path));
+        }
+        listed.push((module, source));
+    }
+    listed.sort();
+    let mut listing = String::new();
+    for (module, source) in listed {
+        for item in public_items(&source) {
+            listing.push_str(&format!("{}: {}\n", module, item));
+        }
+    }
+    listing
+}
+
+// The modules a source file declares, except those under `#[doc(hidden)]`.
+fn modules_declared(source: &str) -> Vec<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i == 0 || lines[i - 1].trim() != "#[doc(hidden)]")
+        .filter_map(|(_, line)| line.strip_prefix("pub mod ").or_else(|| line.strip_prefix("mod ")))
+        .filter_map(|name| Some(name.strip_suffix(';')?.to_string()))
+        .collect()
+}
+
+// The public items of one source file, each on one line without its body or value:
+// signatures, types, constants, fields, variants, trait methods and re-exports. Methods,
+// fields and variants are prefixed with the `impl` or type they belong to. Items under
+// `pub(crate)` don't count.
+fn public_items(source: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut owner = String::new();
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        let code = strip_comment(line);
+        if !code.starts_with(' ') && !code.is_empty() {
+            owner = String::new();
+            if ["impl", "pub struct", "pub enum", "pub trait"].iter().any(|start| code.starts_with(start)) {
+                owner = signature(code, &mut lines);
+                items.push(owner.clone());
+                continue;
+            }
+        }
+        let trimmed = code.trim_start();
+        let nested = code.starts_with("    ") && !code.starts_with("     ");
+        let variant = nested && owner.starts_with("pub enum") && trimmed.starts_with(char::is_uppercase);
+        let method = nested && owner.starts_with("pub trait") && trimmed.starts_with("fn ");
+        if !(trimmed.starts_with("pub ") || variant || method) {
+            continue;
+        }
+        let item = signature(trimmed, &mut lines);
+        if code.starts_with(' ') {
+            items.push(format!("{} :: {}", owner, item));
+        } else {
+            items.push(item);
+        }
+    }
+    items
+}
+
+// `first` and the lines after it up to the start of the body or the end of the item,
+// joined into one.
+fn signature<'a>(first: &str, lines: &mut impl Iterator<Item = &'a str>) -> String {
+    let mut joined = first.trim().to_string();
+    while !ends_item(&joined) {
+        match lines.next() {
+            Some(line) => {
+                let next = strip_comment(line).trim();
+                if !(joined.ends_with('(') || joined.ends_with('<') || next.starts_with(')') || next.starts_with('>')) {
+                    joined.push(' ');
+                }
+                joined.push_str(next);
+            }
+            None => break,
+        }
+    }
+    let joined = joined.split(" = ").next().unwrap().trim_end_matches(['{', ';', ',']).trim_end();
+    joined.trim_end_matches(',').replace(",)", ")").replace(",>", ">")
+}
+
+// Whether a signature has reached its body, its end, or the end of a field: a `{`, `;` or
+// `,` outside any brackets.
+fn ends_item(text: &str) -> bool {
+    let depth = text.replace("->", "").chars().fold(0i32, |depth, c| match c {
+        '(' | '[' | '<' => depth + 1,
+        ')' | ']' | '>' => depth - 1,
+        _ => depth,
+    });
+    depth == 0 && (text.ends_with('{') || text.ends_with(';') || text.ends_with(','))
+}
+
+// `line` without a trailing `//` comment, unless the `//` is inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let bytes = line.as_bytes();
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'"' if i == 0 || bytes[i - 1] != b'\\' => in_string = !in_string,
+            b'/' if !in_string && bytes.get(i + 1) == Some(&b'/') => return line[..i].trim_end(),
+            _ => {}
+        }
+    }
+    line.trim_end()
+}
@@ -0,0 +1,80 @@
+use std::fs;
+
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::registry::bundle::Bundle;
+
+// A function that builds a rule table from nothing.
+type Constructor = fn() -> RuleTable;
+
+// Every rule table constructor in the `rules` module and the built-in machine it backs.
+const CONSTRUCTORS: &[(&str, Constructor)] = &[
+    ("adder", rules::adder),
+    ("increment", rules::increment),
+    ("palindrome", rules::palindrome),
+    ("doubling", rules::doubling),
+];
+
+// The names of the functions in src/rules/mod.rs that build a `RuleTable` from nothing.
+fn constructors_in_source() -> Vec<String> {
+    let source = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/src/rules/mod.rs")).unwrap();
+    let mut names: Vec<String> = source
+        .lines()
+        .filter_map(|line| line.strip_prefix("pub fn ")?.strip_suffix("() -> RuleTable {"))
+        .map(String::from)
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn every_rules_constructor_is_registered() {
+    let mut listed: Vec<String> = CONSTRUCTORS.iter().map(|(name, _)| name.to_string()).collect();
+    listed.sort();
+    assert_eq!(constructors_in_source(), listed, "a rules constructor is missing from this test");
+    for (name, build) in CONSTRUCTORS {
+        assert!(registry::identify(&build()).is_some(), "rules::{} is not in the registry", name);
+    }
+    assert_eq!(registry::MACHINES.len(), CONSTRUCTORS.len());
+}
+
+#[test]
+fn machines_are_found_by_name() {
+    for entry in registry::MACHINES {
+        assert_eq!(registry::entry(entry.name).map(|found| found.name), Some(entry.name));
+        assert_eq!(registry::get(entry.name).unwrap().rules, (entry.build)().rules);
+    }
+    assert!(registry::get("nope").is_none());
+    let names: Vec<&str> = registry::names().collect();
+    assert_eq!(names, ["add", "increment", "double", "palindrome"]);
+}
+
+#[test]
+fn every_machine_passes_its_examples() {
+    for entry in registry::MACHINES {
+        assert!(!entry.examples.is_empty(), "{}", entry.name);
+        assert_eq!(Bundle::from_entry(entry).check_examples(100_000), [], "{}", entry.name);
+    }
+}
+
+#[test]
+fn unknown_names_suggest_close_ones() {
+    assert_eq!(registry::suggest("adder"), ["add"]);
+    assert_eq!(registry::suggest("incremnt"), ["increment"]);
+    assert_eq!(registry::suggest("pal"), ["palindrome"]);
+    assert!(registry::suggest("zzzzzzzz").is_empty());
+}
+
+// Runs `tm run --machine <name> --input <input>` and returns its stdout.
+fn run_machine(name: &str, input: &str) -> String {
+    let assert = Command::cargo_bin("tm").unwrap().args(["run", "--machine", name, "--input", input]).assert();
+    String::from_utf8(assert.success().get_output().stdout.clone()).unwrap()
+}
+
+#[test]
+fn tm_run_selects_a_machine_by_name() {
+    let stdout = run_machine("increment", "1011");
+    assert!(stdout.contains("Result: 1100\n"), "{}", stdout);
+    let stdout = run_machine("palindrome", "<abba>");
+    assert!(stdout.contains("Result: accept\n"), "{}", stdout);
+}
@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::trace::replay::{Trace, TraceError};
+
+// The recorded run of the adder on `a+b`, and the tape it started on.
+fn recorded(a: &str, b: &str) -> (Trace, Vec<char>) {
+    let tape = build_tape(a, b, LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape.clone(), rules::adder());
+    (Trace::record(&mut machine, 10_000).unwrap(), tape)
+}
+
+// A path in the temp directory for a file this test writes.
+fn scratch(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("tm-replay-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn recorded_traces_are_consistent() {
+    for (a, b) in [("1011", "11"), ("1", "1"), ("111", "1"), ("0", "0")] {
+        let (trace, tape) = recorded(a, b);
+        assert_eq!(trace.verify_consistency(&tape), Ok(()), "{}+{}", a, b);
+        assert_eq!(Trace::from_json(&trace.to_json().to_string()), Ok(trace), "{}+{}", a, b);
+    }
+}
+
+#[test]
+fn corrupted_event_is_found_at_its_index() {
+    let (trace, tape) = recorded("1011", "11");
+    assert_eq!(trace.events.len(), 62);
+    let mut corrupted = trace.clone();
+    corrupted.events[17].read = if trace.events[17].read == '1' { '0' } else { '1' };
+    let error = corrupted.verify_consistency(&tape).unwrap_err();
+    assert_eq!(error.event(), Some(17));
+    let (expected, found) = (trace.events[17].read, corrupted.events[17].read);
+    assert_eq!(error, TraceError::Read { event: 17, expected, found });
+
+    let mut corrupted = trace.clone();
+    corrupted.events[40].head += 1;
+    let expected = trace.events[40].head;
+    assert_eq!(
+        corrupted.verify_consistency(&tape),
+        Err(TraceError::Head { event: 40, expected, found: expected + 1 })
+    );
+
+    let mut corrupted = trace.clone();
+    corrupted.events[60].state = State::Carry;
+    assert_eq!(corrupted.verify_consistency(&tape).unwrap_err().event(), Some(60));
+}
+
+#[test]
+fn false_claims_are_caught() {
+    let (mut trace, tape) = recorded("1011", "11");
+    trace.result = Some("1111".to_string());
+    let error = trace.verify_consistency(&tape).unwrap_err();
+    assert_eq!(error, TraceError::Result { claimed: "1111".to_string(), actual: "1110".to_string() });
+    assert_eq!(error.event(), None);
+    trace.final_tape = Some("+++IIII".to_string());
+    assert!(matches!(trace.verify_consistency(&tape), Err(TraceError::FinalTape { .. })));
+}
+
+#[test]
+fn growth_on_the_left_is_reconstructed() {
+    // Writes `x` on the first cell, steps left off the tape and writes `y` on the new cell.
+    let state = |name: &str| State::Named(name.to_string());
+    let mut rules = RuleTable::new();
+    rules.insert((State::FindPlus, '_'), ('x', Direction::Left, state("Left")));
+    rules.insert((state("Left"), '_'), ('y', Direction::Right, State::Halt));
+    let mut machine = TuringMachine::new(vec!['_', '1'], rules);
+    let mut trace = Trace::record(&mut machine, 10).unwrap();
+    assert_eq!(trace.events.iter().map(|event| event.head).collect::<Vec<_>>(), [0, -1]);
+    assert_eq!(trace.final_tape.as_deref(), Some("yx1"));
+    trace.result = None;
+    assert_eq!(trace.verify_consistency(&['_', '1']), Ok(()));
+    trace.events[1].read = '1';
+    assert_eq!(trace.verify_consistency(&['_', '1']), Err(TraceError::Read { event: 1, expected: '_', found: '1' }));
+}
+
+#[test]
+fn tm_verify_trace_names_the_corrupted_event() {
+    let (mut trace, _) = recorded("1011", "11");
+    let path = scratch("trace.json");
+    std::fs::write(&path, trace.to_json().to_string()).unwrap();
+    let assert = Command::cargo_bin("tm").unwrap().arg("verify-trace").arg(&path).args(["--input", "1011+11"]).assert();
+    assert.success().stdout("62 steps, consistent\n");
+
+    trace.events[17].head += 1;
+    std::fs::write(&path, trace.to_json().to_string()).unwrap();
+    let assert = Command::cargo_bin("tm").unwrap().arg("verify-trace").arg(&path).args(["--input", "1011+11"]).assert();
+    let stderr = String::from_utf8(assert.failure().get_output().stderr.clone()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let expected = trace.events[17].head - 1;
+    let message = format!("event 17: the head is at cell {} but the event says {}", expected, expected + 1);
+    assert!(stderr.contains(&message), "{}", stderr);
+}
@@ -0,0 +1,88 @@
+use std::fs;
+
+use turing::prelude::*;
+use turing::rules::{parse_machine, MachineDefinition};
+use turing::Tape;
+
+// Reads one of the rule files under machines/.
+fn load(name: &str) -> MachineDefinition {
+    let path = format!("{}/machines/{}", env!("CARGO_MANIFEST_DIR"), name);
+    parse_machine(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+// Runs `definition` on `tape` from its start state and returns its answer.
+fn answer(definition: &MachineDefinition, tape: &str) -> String {
+    let mut machine = TuringMachine::new(tape.chars().collect(), definition.rules.clone());
+    machine.set_state(definition.start.clone());
+    machine.set_result_strategy(definition.result.clone());
+    machine.set_final_states(definition.final_states.clone());
+    machine.run_bounded(10_000).unwrap();
+    machine.result()
+}
+
+#[test]
+fn increment_reads_the_whole_tape() {
+    let increment = load("increment.tm");
+    assert_eq!(increment.result, ResultStrategy::WholeTapeTrimmed);
+    for n in 0..64u32 {
+        assert_eq!(answer(&increment, &format!("_{:b}_", n)), format!("{:b}", n + 1));
+    }
+}
+
+#[test]
+fn adder_reads_right_of_the_separator() {
+    let adder = load("adder.tm");
+    assert_eq!(adder.result, ResultStrategy::adder());
+    assert_eq!(adder.result.to_string(), "right_of_separator + I=1 O=0");
+    assert_eq!(answer(&adder, "_1011+11_"), "1110");
+    assert_eq!(answer(&adder, "_111+1_"), "1000");
+}
+
+#[test]
+fn strategies_read_a_final_tape() {
+    let tape: Tape = "__1+IOO__".chars().collect();
+    assert_eq!(ResultStrategy::adder().extract(&tape, None), "100");
+    assert_eq!(ResultStrategy::WholeTapeTrimmed.extract(&tape, None), "1+IOO");
+    let accept = ResultStrategy::AcceptStateOnly { accept: vec![State::Named("Yes".to_string())] };
+    assert_eq!(accept.extract(&tape, Some(&State::Named("Yes".to_string()))), "accept");
+    assert_eq!(accept.extract(&tape, Some(&State::Halt)), "reject");
+    assert_eq!(accept.extract(&tape, None), "reject");
+}
+
+#[test]
+fn strategies_survive_the_json_rule_format() {
+    for definition in [load("adder.tm"), load("increment.tm")] {
+        let json = rules::to_json(&definition).to_string();
+        assert_eq!(rules::from_json(&json).unwrap().result, definition.result);
+    }
+}
+
+// The adder run to the end on `a+b`, with leading zeros as `zeros` says.
+fn added(a: &str, b: &str, zeros: LeadingZeros) -> TuringMachine {
+    let mut machine = TuringMachine::new(build_tape(a, b, zeros).unwrap(), rules::adder());
+    machine.run();
+    machine
+}
+
+#[test]
+fn result_bit_length_counts_a_carry_out() {
+    let machine = added("1111", "1", LeadingZeros::Strip);
+    assert_eq!((machine.result(), machine.result_bit_length()), (String::from("10000"), 5));
+    let machine = added("1011", "11", LeadingZeros::Strip);
+    assert_eq!((machine.result(), machine.result_bit_length()), (String::from("1110"), 4));
+}
+
+#[test]
+fn result_bit_length_is_zero_for_a_zero_result() {
+    let machine = added("0", "0", LeadingZeros::Strip);
+    assert_eq!((machine.result(), machine.result_bit_length()), (String::from("0"), 0));
+    let machine = added("000", "00", LeadingZeros::Preserve);
+    assert_eq!(machine.result_bit_length(), 0, "{}", machine.result());
+}
+
+#[test]
+fn result_bit_length_leaves_out_leading_zeros() {
+    let machine = added("0011", "0001", LeadingZeros::Preserve);
+    assert_eq!((machine.result(), machine.result_bit_length()), (String::from("0100"), 3));
+    assert_eq!(added("0011", "0001", LeadingZeros::Strip).result_bit_length(), 3);
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+use std::thread;
+
+use turing::machine::compiled::{CompiledMachine, CompiledRules};
+use turing::prelude::*;
+
+fn tape(a: &str, b: &str) -> Vec<char> {
+    build_tape(a, b, LeadingZeros::Strip).unwrap()
+}
+
+// An adder recording everything a run could leave behind.
+fn fresh(input: Vec<char>) -> TuringMachine {
+    let mut machine = TuringMachine::new(input, rules::adder());
+    machine.set_tracing(true);
+    machine.set_snapshot_capacity(4);
+    machine.preserve_operands();
+    machine
+}
+
+#[test]
+fn a_reset_machine_runs_as_a_new_one() {
+    let good = tape("1011", "11");
+    let mut expected = fresh(good.clone());
+    expected.run_bounded(10_000).unwrap();
+
+    // A run that hits the step limit, and one that halts where it shouldn't, on a tape
+    // with a symbol the adder has no rule for.
+    let mut machine = fresh(tape("1111111111", "1"));
+    assert_eq!(machine.run_bounded(50), Err(RunError::StepLimitExceeded(50)));
+    for bad in [vec!['_', '1', 'x', '+', '1', '_'], good.clone()] {
+        machine.reset_with_input(bad);
+        machine.run_bounded(10_000).unwrap();
+    }
+    machine.reset_with_input(good);
+    machine.run_bounded(10_000).unwrap();
+
+    assert_eq!(machine, expected);
+    assert_eq!(machine.result(), "1110");
+    assert_eq!(machine.steps(), expected.steps());
+    assert_eq!(machine.head_travel(), expected.head_travel());
+    assert_eq!(machine.halt_reason(), Some(&HaltReason::Finished(State::GetLast)));
+    assert_eq!(machine.operands(), Some(("1011", "11")));
+    assert_eq!(machine.trace(), expected.trace());
+    assert_eq!(machine.last_snapshots(), expected.last_snapshots());
+}
+
+#[test]
+fn a_reset_machine_goes_back_to_its_own_start_state() {
+    let mut increment = TuringMachine::new(vec!['_', '1', '1', '_'], rules::increment());
+    let start = State::Named(String::from("Start"));
+    increment.set_state(start.clone());
+    increment.run_bounded(1_000).unwrap();
+    increment.reset_with_input(vec!['_', '1', '0', '_']);
+    assert_eq!(increment.state(), &start);
+}
+
+#[test]
+fn a_reset_machine_goes_back_to_the_state_it_was_built_with() {
+    let start = State::Named(String::from("Start"));
+    let mut built = TuringMachine::builder(vec!['_', '1', '1', '_'], rules::increment())
+        .with_state(start.clone())
+        .build()
+        .unwrap();
+    built.run_bounded(1_000).unwrap();
+    built.reset_with_input(vec!['_', '1', '0', '_']);
+    assert_eq!(built.state(), &start);
+    built.run_bounded(1_000).unwrap();
+    assert_eq!(built.result(), "11");
+
+    let snapshot = Snapshot { tape: tape("1", "1"), head: 2, state: State::GetLast, partial_value: None };
+    let mut resumed = TuringMachine::from_snapshot(&snapshot, rules::adder()).unwrap();
+    resumed.run_bounded(1_000).unwrap();
+    resumed.reset_with_input(tape("1", "1"));
+    assert_eq!(resumed.state(), &State::GetLast);
+}
+
+#[test]
+fn workers_share_one_rule_table() {
+    let shared = Arc::new(rules::adder());
+    let workers: Vec<_> = (0..4u64)
+        .map(|worker| {
+            let rules = Arc::clone(&shared);
+            thread::spawn(move || {
+                let mut machine = TuringMachine::with_shared_rules(vec!['_'], Arc::clone(&rules));
+                let mut sums = Vec::new();
+                for b in 0..16u64 {
+                    machine.reset_with_input(tape(&format!("{:b}", worker), &format!("{:b}", b)));
+                    machine.run_bounded(10_000).unwrap();
+                    sums.push(machine.result());
+                }
+                assert!(Arc::ptr_eq(&machine.shared_rules(), &rules));
+                sums
+            })
+        })
+        .collect();
+    for (a, worker) in workers.into_iter().enumerate() {
+        let sums = worker.join().unwrap();
+        assert!(sums.iter().enumerate().all(|(b, sum)| *sum == format!("{:b}", a + b)));
+    }
+    assert_eq!(Arc::strong_count(&shared), 1);
+}
+
+#[test]
+fn compiled_workers_share_one_dense_table() {
+    let shared = Arc::new(CompiledRules::new(&rules::adder(), &State::FindPlus));
+    let workers: Vec<_> = (0..4u64)
+        .map(|worker| {
+            let rules = Arc::clone(&shared);
+            thread::spawn(move || {
+                // A run stuck on a symbol the adder has no rule for, before the real ones.
+                let mut machine = CompiledMachine::with_shared_rules(vec!['_', '1', 'x', '+', '1', '_'], rules);
+                machine.run_bounded(10_000).unwrap();
+                let mut sums = Vec::new();
+                for b in 0..16u64 {
+                    let input = tape(&format!("{:b}", worker), &format!("{:b}", b));
+                    machine.reset_with_input(input.clone());
+                    let steps = machine.run_bounded(10_000).unwrap();
+                    assert_eq!(steps, TuringMachine::new(input, rules::adder()).run_bounded(10_000).unwrap());
+                    sums.push(machine.result());
+                }
+                sums
+            })
+        })
+        .collect();
+    for (a, worker) in workers.into_iter().enumerate() {
+        let sums = worker.join().unwrap();
+        assert!(sums.iter().enumerate().all(|(b, sum)| *sum == format!("{:b}", a + b)));
+    }
+    assert_eq!(Arc::strong_count(&shared), 1);
+}
+
+#[test]
+fn a_compiled_machine_hands_its_table_on() {
+    let compiled = TuringMachine::new(tape("1", "1"), rules::adder()).compile().unwrap();
+    let mut other = CompiledMachine::with_shared_rules(tape("111", "1"), compiled.shared_rules());
+    assert!(Arc::ptr_eq(&compiled.shared_rules(), &other.shared_rules()));
+    assert_eq!(other.state(), &State::FindPlus);
+    other.run();
+    assert_eq!(other.result(), "1000");
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use turing::prelude::*;
+use turing::rules::RuleError;
+
+// A state outside the adder's.
+fn named(name: &str) -> State {
+    State::Named(String::from(name))
+}
+
+#[test]
+fn pruning_drops_the_rules_of_unreachable_states() {
+    let mut rules = rules::adder();
+    // An orphan that only leads to itself and back into the adder: nothing enters it.
+    rules.insert((named("Orphan"), '1'), ('1', Direction::Right, named("Orphan")));
+    rules.insert((named("Orphan"), '_'), ('_', Direction::Left, State::Carry));
+
+    let reachable = rules::reachable_states(&rules, &State::FindPlus);
+    assert!(!reachable.contains(&named("Orphan")));
+    let adder_states: BTreeSet<State> = rules::adder().keys().map(|(state, _)| state.clone()).collect();
+    assert!(adder_states.is_subset(&reachable), "{:?}", reachable);
+
+    let pruned = rules::prune_rules(&rules, &State::FindPlus);
+    assert_eq!(pruned, rules::adder());
+    // Started in the orphan, its own rules and those of the states it leads to are kept.
+    assert!(rules::reachable_states(&rules, &named("Orphan")).contains(&State::Carry));
+    let from_orphan = rules::prune_rules(&rules, &named("Orphan"));
+    assert_eq!(from_orphan.get(&(named("Orphan"), '1')), rules.get(&(named("Orphan"), '1')));
+    assert_eq!(from_orphan.get(&(State::Carry, '1')), rules.get(&(State::Carry, '1')));
+}
+
+#[test]
+fn pruned_rules_run_the_same() {
+    let mut rules = rules::adder();
+    rules.insert((named("Orphan"), '1'), ('0', Direction::Left, named("Orphan")));
+    let pruned = rules::prune_rules(&rules, &State::FindPlus);
+    assert_eq!(pruned.len(), rules.len() - 1);
+    let run = |rules: RuleTable| {
+        let mut machine = TuringMachine::new(build_tape("1011", "11", LeadingZeros::Strip).unwrap(), rules);
+        machine.run();
+        (machine.result(), machine.steps())
+    };
+    assert_eq!(run(pruned), run(rules));
+}
+
+// A two-state flipper over blanks and bits with a rule for every pair but `Back` reading '1'.
+fn flipper() -> RuleTable {
+    let mut rules = RuleTable::new();
+    for symbol in ['_', '0', '1'] {
+        rules.insert((named("Flip"), symbol), (symbol, Direction::Right, named("Back")));
+    }
+    rules.insert((named("Back"), '_'), ('_', Direction::Stay, State::Halt));
+    rules.insert((named("Back"), '0'), ('1', Direction::Left, named("Flip")));
+    rules
+}
+
+#[test]
+fn a_table_with_every_rule_is_total() {
+    let mut rules = flipper();
+    rules.insert((named("Back"), '1'), ('0', Direction::Left, named("Flip")));
+    let states = [named("Flip"), named("Back"), State::Halt];
+    assert_eq!(rules::missing_rules(&rules, &['_', '0', '1'], &states), Vec::new());
+    assert!(rules::is_total(&rules, &['_', '0', '1'], &states));
+}
+
+#[test]
+fn a_missing_pair_is_reported_and_makes_the_table_partial() {
+    let states = [named("Flip"), named("Back"), State::Halt];
+    assert_eq!(rules::missing_rules(&flipper(), &['_', '0', '1'], &states), [(named("Back"), '1')]);
+    assert!(!rules::is_total(&flipper(), &['_', '0', '1'], &states));
+}
+
+#[test]
+fn the_adder_is_not_total() {
+    let states: Vec<State> = rules::adder().keys().map(|(state, _)| state.clone()).collect();
+    let missing = rules::missing_rules(&rules::adder(), &['_', '0', '1', '+', 'I', 'O'], &states);
+    assert!(missing.contains(&(State::GetLast, '+')), "{:?}", missing);
+    assert!(!rules::is_total(&rules::adder(), &['_', '0', '1', '+', 'I', 'O'], &states));
+}
+
+// Flat rules giving `Scan` two rules for '1', which disagree on the direction.
+fn conflicting() -> Vec<rules::Rule> {
+    vec![
+        (named("Scan"), '0', '0', Direction::Right, named("Scan")),
+        (named("Scan"), '1', '1', Direction::Right, named("Scan")),
+        (named("Scan"), '1', '1', Direction::Left, State::Halt),
+    ]
+}
+
+#[test]
+fn from_rules_refuses_two_rules_for_the_same_pair() {
+    let error = rules::from_rules(conflicting()).unwrap_err();
+    assert_eq!(error, RuleError::Duplicate(named("Scan"), '1'));
+    assert_eq!(error.to_string(), "there are two rules for state Scan reading '1'");
+}
+
+#[test]
+fn with_rules_refuses_two_rules_for_the_same_pair() {
+    let error = TuringMachine::with_rules(vec!['_', '1', '_'], conflicting()).unwrap_err();
+    assert_eq!(error, RuleError::Duplicate(named("Scan"), '1'));
+}
+
+#[test]
+fn identical_rules_still_count_as_two() {
+    let rule = (named("Scan"), '0', '0', Direction::Right, named("Scan"));
+    assert_eq!(rules::from_rules([rule.clone(), rule]), Err(RuleError::Duplicate(named("Scan"), '0')));
+}
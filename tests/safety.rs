@@ -0,0 +1,106 @@
+use assert_cmd::Command;
+use turing::machine::safety::{check_safety, Counterexample, Property, SafetyOutcome};
+use turing::prelude::*;
+use turing::rules::parse_machine;
+
+const WRITES_X: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/writes_x.tm");
+
+// The fixture's rules, which write an X on the blank after a run of ones.
+fn writes_x() -> RuleTable {
+    parse_machine(&std::fs::read_to_string(WRITES_X).unwrap()).unwrap().rules
+}
+
+// The tape `_` followed by `ones` ones.
+fn ones(ones: usize) -> Vec<char> {
+    format!("_{}", "1".repeat(ones)).chars().collect()
+}
+
+// The start state of the fixture.
+fn start() -> State {
+    State::Named("Start".to_string())
+}
+
+#[test]
+fn violation_is_found_at_a_known_step() {
+    let outcome = check_safety(&writes_x(), &start(), &[ones(3)], &Property::NoWrite('X'), 5000);
+    let SafetyOutcome::Violated(Counterexample { step, trace }) = outcome else {
+        panic!("{:?}", outcome);
+    };
+    // One step onto the ones, three over them, and the fifth writes the X on the blank.
+    assert_eq!(step, 5);
+    let configurations: Vec<String> = trace.iter().map(Snapshot::to_compact_string).collect();
+    assert_eq!(
+        configurations,
+        ["Start [_]111", "Walk _[1]11", "Walk _1[1]1", "Walk _11[1]", "Walk _111[_]"]
+    );
+}
+
+#[test]
+fn shortest_counterexample_wins_across_tapes() {
+    let outcome = check_safety(&writes_x(), &start(), &[ones(6), ones(1)], &Property::NoWrite('X'), 5000);
+    let SafetyOutcome::Violated(counterexample) = outcome else {
+        panic!("{:?}", outcome);
+    };
+    assert_eq!(counterexample.step, 3);
+    assert_eq!(counterexample.trace[0].tape, ones(1));
+}
+
+#[test]
+fn property_holds_below_the_violating_depth() {
+    let outcome = check_safety(&writes_x(), &start(), &[ones(3)], &Property::NoWrite('X'), 4);
+    assert_eq!(outcome, SafetyOutcome::Holds { depth: 4, configurations: 5, exhausted: false });
+    let outcome = check_safety(&writes_x(), &start(), &[ones(3)], &Property::NoWrite('Y'), 5000);
+    assert_eq!(outcome, SafetyOutcome::Holds { depth: 5000, configurations: 6, exhausted: true });
+}
+
+#[test]
+fn looping_machine_is_explored_once() {
+    // Steps back and forth between two cells forever, writing nothing new.
+    let state = |name: &str| State::Named(name.to_string());
+    let mut rules = RuleTable::new();
+    rules.insert((state("There"), '_'), ('_', Direction::Right, state("Back")));
+    rules.insert((state("Back"), '_'), ('_', Direction::Left, state("There")));
+    let outcome = check_safety(&rules, &state("There"), &[ones(0)], &Property::NoWrite('1'), 1_000_000);
+    assert_eq!(outcome, SafetyOutcome::Holds { depth: 1_000_000, configurations: 2, exhausted: true });
+}
+
+#[test]
+fn writing_left_of_the_start_is_caught() {
+    let state = |name: &str| State::Named(name.to_string());
+    let mut rules = RuleTable::new();
+    rules.insert((state("Go"), '_'), ('_', Direction::Left, state("Mark")));
+    rules.insert((state("Mark"), '_'), ('1', Direction::Right, State::Halt));
+    let outcome = check_safety(&rules, &state("Go"), &[ones(0)], &Property::NoWriteLeftOfStart, 10);
+    let SafetyOutcome::Violated(counterexample) = outcome else {
+        panic!("{:?}", outcome);
+    };
+    assert_eq!(counterexample.step, 2);
+    assert_eq!(counterexample.trace.last().unwrap().to_compact_string(), "Mark [_]");
+}
+
+#[test]
+fn properties_read_their_spelling() {
+    for property in [Property::NoWrite('X'), Property::NoWriteLeftOfStart] {
+        assert_eq!(property.to_string().parse(), Ok(property));
+    }
+    assert!("no-write:XY".parse::<Property>().is_err());
+}
+
+#[test]
+fn tm_check_prints_the_counterexample() {
+    let assert = Command::cargo_bin("tm")
+        .unwrap()
+        .args(["check", "--property", "no-write:X", "--depth", "5000", "--rules", WRITES_X, "_111"])
+        .assert();
+    let stderr = String::from_utf8(assert.code(1).get_output().stderr.clone()).unwrap();
+    assert_eq!(
+        stderr,
+        "no-write:X is broken at step 5:\n     0  Start [_]111\n     1  Walk _[1]11\n     2  Walk _1[1]1\n     \
+         3  Walk _11[1]\n     4  Walk _111[_]\n"
+    );
+    let assert = Command::cargo_bin("tm")
+        .unwrap()
+        .args(["check", "--property", "no-write:X", "--depth", "4", "--rules", WRITES_X, "_111"])
+        .assert();
+    assert.success().stdout("no-write:X holds up to depth 4 (5 configurations explored)\n");
+}
@@ -0,0 +1,43 @@
+use turing::prelude::*;
+
+// An adder on 1011+11 whose oracle drops a '#' at the end of the tape the first time the
+// machine turns back from the '+', after 6 steps.
+fn corrupted_adder() -> TuringMachine {
+    let tape = build_tape("1011", "11", LeadingZeros::Strip).unwrap();
+    let mut machine = TuringMachine::new(tape, rules::adder());
+    let mut corrupted = false;
+    machine.set_oracle(State::GetLast, move |tape, _| {
+        if !corrupted {
+            tape.push_back('#');
+            corrupted = true;
+        }
+    });
+    machine
+}
+
+#[test]
+fn corruption_is_caught_at_the_first_check_after_it() {
+    let mut machine = corrupted_adder();
+    machine.set_sanity_interval(Some(4));
+    assert_eq!(
+        machine.run_bounded(10_000),
+        Err(RunError::Corrupted { step: 8, cell: 9, symbol: '#' })
+    );
+}
+
+#[test]
+fn corruption_is_not_caught_before_the_interval() {
+    let mut machine = corrupted_adder();
+    machine.set_sanity_interval(Some(20));
+    assert_eq!(
+        machine.run_bounded(10_000),
+        Err(RunError::Corrupted { step: 20, cell: 9, symbol: '#' })
+    );
+}
+
+#[test]
+fn corruption_goes_unnoticed_without_checks() {
+    let mut machine = corrupted_adder();
+    assert_eq!(machine.run_bounded(10_000), Ok(62));
+    assert_eq!(machine.tape().back(), Some(&'#'));
+}
@@ -0,0 +1,45 @@
+use turing::prelude::*;
+use turing::{OnSentinel, Sentinels};
+
+// Walks over `<10>` from cell `head` in `direction`, marking each digit it passes, `1` as
+// 'X' and `0` as 'Y', so it is stuck if it reads a marked cell again.
+fn marker(head: usize, direction: Direction, on_contact: OnSentinel) -> TuringMachine {
+    let scan = State::Named(String::from("Scan"));
+    let rules = rules::from_rules([
+        (scan.clone(), '1', 'X', direction.clone(), scan.clone()),
+        (scan.clone(), '0', 'Y', direction, scan.clone()),
+    ])
+    .unwrap();
+    let mut machine =
+        TuringMachine::builder(vec!['<', '1', '0', '>'], rules).with_head(head).with_state(scan).build().unwrap();
+    machine.set_sentinels(Some(Sentinels { left: '<', right: '>', on_contact }));
+    machine
+}
+
+#[test]
+fn running_into_the_right_sentinel_halts_after_the_write() {
+    let mut machine = marker(1, Direction::Right, OnSentinel::Halt);
+    assert_eq!(machine.run_bounded(100), Ok(2));
+    assert_eq!(machine.halt_reason(), Some(&HaltReason::Sentinel('>')));
+    assert_eq!(machine.tape().iter().collect::<String>(), "<XY>");
+    assert_eq!(machine.head(), 2);
+}
+
+#[test]
+fn running_into_the_left_sentinel_halts_after_the_write() {
+    let mut machine = marker(2, Direction::Left, OnSentinel::Halt);
+    assert_eq!(machine.run_bounded(100), Ok(2));
+    assert_eq!(machine.halt_reason(), Some(&HaltReason::Sentinel('<')));
+    assert_eq!(machine.tape().iter().collect::<String>(), "<XY>");
+    assert_eq!(machine.head(), 1);
+}
+
+#[test]
+fn a_reflecting_sentinel_keeps_the_head_where_it_was() {
+    let mut machine = marker(1, Direction::Right, OnSentinel::Reflect);
+    assert_eq!(machine.run_bounded(100), Ok(2));
+    let scan = State::Named(String::from("Scan"));
+    assert_eq!(machine.halt_reason(), Some(&HaltReason::Stuck { state: scan, symbol: 'Y' }));
+    assert_eq!(machine.tape().iter().collect::<String>(), "<XY>");
+    assert_eq!(machine.head(), 2);
+}
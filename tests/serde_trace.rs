@@ -0,0 +1,81 @@
+use turing::prelude::*;
+use turing::trace::formats::{Envelope, Format, FormatError};
+use turing::trace::{trace_to_svg, trace_to_text};
+
+// The trace of 1011+11, with the partial values recorded.
+fn recorded() -> Vec<Snapshot> {
+    let mut machine = TuringMachine::new(build_tape("1011", "11", LeadingZeros::Strip).unwrap(), rules::adder());
+    machine.set_tracing(true);
+    machine.set_partial_values(true);
+    machine.run_bounded(10_000).unwrap();
+    machine.trace().to_vec()
+}
+
+#[test]
+fn trace_round_trips_in_its_envelope() {
+    let trace = recorded();
+    let saved = serde_json::to_string(&Envelope::new(Format::Snapshots, &trace)).unwrap();
+    assert!(saved.starts_with(r#"{"format":"tm-snapshots","version":2,"payload":[{"tape":"#), "{}", saved);
+    let loaded = serde_json::from_str::<Envelope<Vec<Snapshot>>>(&saved).unwrap().open(Format::Snapshots).unwrap();
+    assert_eq!(loaded, trace);
+    assert_eq!(trace_to_text(&loaded), trace_to_text(&trace));
+    assert_eq!(trace_to_svg(&loaded), trace_to_svg(&trace));
+}
+
+#[test]
+fn named_states_and_missing_partial_values_round_trip() {
+    let snapshot = Snapshot {
+        tape: vec!['_', '1', '_'],
+        head: 1,
+        state: State::Named("Seek".to_string()),
+        partial_value: None,
+    };
+    let saved = serde_json::to_string(&Envelope::new(Format::Snapshots, vec![snapshot.clone()])).unwrap();
+    let loaded: Envelope<Vec<Snapshot>> = serde_json::from_str(&saved).unwrap();
+    assert_eq!(loaded.open(Format::Snapshots).unwrap(), [snapshot]);
+}
+
+#[test]
+fn bare_trace_reads_as_version_one() {
+    let trace = recorded();
+    let loaded: Envelope<Vec<Snapshot>> = serde_json::from_str(&serde_json::to_string(&trace).unwrap()).unwrap();
+    assert_eq!(loaded.open(Format::Snapshots).unwrap(), trace);
+}
+
+#[test]
+fn other_formats_and_versions_are_turned_down() {
+    let open = |json: &str| serde_json::from_str::<Envelope<Vec<Snapshot>>>(json).unwrap().open(Format::Snapshots);
+    assert_eq!(
+        open(r#"{"format":"tm-trace","version":2,"payload":[]}"#),
+        Err(FormatError::WrongFormat { expected: "tm-snapshots", found: "tm-trace".to_string() })
+    );
+    let error = open(r#"{"format":"tm-snapshots","version":3,"payload":[]}"#).unwrap_err();
+    assert_eq!(error, FormatError::UnsupportedVersion { format: "tm-snapshots", version: 3, current: 2 });
+    assert_eq!(open(r#"{"format":"tm-snapshots","version":1,"payload":[]}"#), Ok(vec![]));
+}
+
+#[test]
+fn result_strategies_round_trip() {
+    let strategies = [
+        ResultStrategy::adder(),
+        ResultStrategy::WholeTapeTrimmed,
+        ResultStrategy::AcceptStateOnly { accept: vec![State::Named("Yes".to_string())] },
+    ];
+    let saved = serde_json::to_string(&strategies).unwrap();
+    let adder = r#"{"right_of_separator":{"separator":"+","map":[["I","1"],["O","0"]]}}"#;
+    assert!(saved.starts_with(&format!("[{},", adder)), "{}", saved);
+    assert_eq!(serde_json::from_str::<Vec<ResultStrategy>>(&saved).unwrap(), strategies);
+}
+
+#[test]
+fn snapshot_file_with_the_head_off_the_tape_is_refused() {
+    let path = std::env::temp_dir().join(format!("tm-head-9999-{}.json", std::process::id()));
+    let json = r#"{"format":"tm-snapshots","version":2,"payload":[
+        {"tape":["_","1","+","1","_"],"head":9999,"state":"FindPlus","partial_value":null}]}"#;
+    std::fs::write(&path, json).unwrap();
+    let saved: Envelope<Vec<Snapshot>> = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let snapshots = saved.open(Format::Snapshots).unwrap();
+    let error = TuringMachine::from_snapshot(&snapshots[0], rules::adder()).unwrap_err();
+    assert_eq!(error.to_string(), "the head starts at cell 9999 but the tape has only 5 cells");
+}
@@ -0,0 +1,43 @@
+use turing::prelude::*;
+use turing::tape::input::ParseError;
+use turing::{simulate, Error, Simulation};
+
+#[test]
+fn simulating_reports_the_operands_and_sum_as_numbers() {
+    let simulation = simulate("1011+11").unwrap();
+    assert_eq!(
+        simulation,
+        Simulation {
+            operands: (11, 3),
+            result: 14,
+            steps: 62,
+            max_tape: 9,
+            halted: HaltReason::Finished(State::GetLast),
+        }
+    );
+}
+
+#[test]
+fn simulating_strips_leading_zeros() {
+    let simulation = simulate("0011+0001").unwrap();
+    assert_eq!((simulation.operands, simulation.result), ((3, 1), 4));
+}
+
+#[test]
+fn the_largest_u64_operands_still_fit() {
+    let max = "1".repeat(64);
+    let simulation = simulate(&format!("{}+0", max)).unwrap();
+    assert_eq!(simulation.result, u64::MAX);
+}
+
+#[test]
+fn a_65_bit_operand_is_too_large() {
+    let input = format!("1{}+1", "0".repeat(64));
+    assert_eq!(simulate(&input), Err(Error::Parse(ParseError::TooLarge(65))));
+}
+
+#[test]
+fn a_65_bit_sum_is_too_large() {
+    let input = format!("{}+1", "1".repeat(64));
+    assert_eq!(simulate(&input), Err(Error::Parse(ParseError::TooLarge(65))));
+}
@@ -0,0 +1,142 @@
+use assert_cmd::Command;
+use turing::prelude::*;
+
+// An s-expression: an atom, with a quoted symbol kept with its bars, or a list.
+#[derive(Debug, Clone, PartialEq)]
+enum Sexpr {
+    Atom(String),
+    List(Vec<Sexpr>),
+}
+
+impl Sexpr {
+    fn list(&self) -> &[Sexpr] {
+        match self {
+            Sexpr::List(items) => items,
+            Sexpr::Atom(atom) => panic!("{} is not a list", atom),
+        }
+    }
+
+    fn atom(&self) -> &str {
+        match self {
+            Sexpr::Atom(atom) => atom,
+            Sexpr::List(_) => panic!("a list is not an atom"),
+        }
+    }
+}
+
+// Reads `text` as a sequence of s-expressions, with `;` comments and `|quoted|` symbols.
+fn read_sexprs(text: &str) -> Result<Vec<Sexpr>, String> {
+    let mut stack: Vec<Vec<Sexpr>> = vec![Vec::new()];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '(' => stack.push(Vec::new()),
+            ')' => {
+                let list = stack.pop().filter(|_| !stack.is_empty()).ok_or("a ')' closes nothing")?;
+                stack.last_mut().unwrap().push(Sexpr::List(list));
+            }
+            '|' => {
+                let mut closed = false;
+                let quoted: String = chars
+                    .by_ref()
+                    .take_while(|&c| {
+                        closed = c == '|';
+                        !closed
+                    })
+                    .collect();
+                if !closed {
+                    return Err(String::from("a quoted symbol is never closed"));
+                }
+                if quoted.contains('\\') {
+                    return Err(format!("a backslash in |{}|", quoted));
+                }
+                stack.last_mut().unwrap().push(Sexpr::Atom(format!("|{}|", quoted)));
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut atom = String::from(c);
+                while let Some(&c) = chars.peek().filter(|c| !c.is_whitespace() && !"();|".contains(**c)) {
+                    atom.push(c);
+                    chars.next();
+                }
+                stack.last_mut().unwrap().push(Sexpr::Atom(atom));
+            }
+        }
+    }
+    match stack.len() {
+        1 => Ok(stack.pop().unwrap()),
+        open => Err(format!("{} '(' are never closed", open - 1)),
+    }
+}
+
+#[test]
+fn reader_rejects_unbalanced_text() {
+    let atom = |text: &str| Sexpr::Atom(text.to_string());
+    let nested = Sexpr::List(vec![atom("a"), Sexpr::List(vec![atom("|b c|")])]);
+    assert_eq!(read_sexprs("; a comment\n(a (|b c|))"), Ok(vec![nested]));
+    assert!(read_sexprs("(a (b)").is_err());
+    assert!(read_sexprs("(a))").is_err());
+    assert!(read_sexprs("|a\\b|").is_err());
+    assert!(read_sexprs("(|a)").is_err());
+}
+
+#[test]
+fn adder_dump_reads_as_declarations_and_delta() {
+    let forms = read_sexprs(&rules::to_smtlib(&rules::adder())).unwrap();
+    let heads: Vec<&str> = forms.iter().map(|form| form.list()[0].atom()).collect();
+    let declare = "declare-datatype";
+    assert_eq!(heads, [declare, declare, declare, declare, "define-fun"]);
+    let constructors = |form: &Sexpr| -> Vec<String> {
+        form.list()[2].list().iter().map(|constructor| constructor.list()[0].atom().to_string()).collect()
+    };
+    assert_eq!(forms[0].list()[1].atom(), "State");
+    assert_eq!(constructors(&forms[0]).len(), 8);
+    assert_eq!(forms[1].list()[1].atom(), "Symbol");
+    assert_eq!(constructors(&forms[1]), ["|'+'|", "|'0'|", "|'1'|", "|'I'|", "|'O'|", "|'_'|"]);
+
+    // `delta` nests one `ite` per rule and ends in `stuck`.
+    let mut body = &forms[4].list()[4];
+    let mut rules = 0;
+    while let Sexpr::List(ite) = body {
+        assert_eq!(ite[0].atom(), "ite");
+        assert_eq!(ite[2].list()[0].atom(), "action");
+        body = &ite[3];
+        rules += 1;
+    }
+    assert_eq!(body.atom(), "stuck");
+    assert_eq!(rules, rules::adder().len());
+}
+
+#[test]
+fn awkward_names_and_symbols_are_escaped() {
+    let state = |name: &str| State::Named(name.to_string());
+    let mut rules = RuleTable::new();
+    rules.insert((state("ite"), '|'), ('\\', Direction::Right, state("carry|out")));
+    rules.insert((state("carry|out"), '%'), ('%', Direction::Left, state("2nd")));
+    let smt = rules::to_smtlib(&rules);
+    for quoted in ["|ite|", "|carry%7Cout|", "|2nd|", "|'%7C'|", "|'%5C'|", "|'%25'|"] {
+        assert!(smt.contains(quoted), "{} is missing from\n{}", quoted, smt);
+    }
+    let forms = read_sexprs(&smt).unwrap();
+    assert_eq!(forms.len(), 5);
+}
+
+#[test]
+fn dump_is_the_same_whatever_the_insertion_order() {
+    let mut reversed = RuleTable::new();
+    let mut entries: Vec<_> = rules::adder().iter().map(|(key, action)| (key.clone(), action.clone())).collect();
+    entries.sort_by_key(|((state, read), _)| (state.to_string(), *read));
+    for (key, action) in entries.into_iter().rev() {
+        reversed.insert(key, action);
+    }
+    assert_eq!(rules::to_smtlib(&reversed), rules::to_smtlib(&rules::adder()));
+}
+
+#[test]
+fn tm_rules_smtlib_prints_the_dump() {
+    let assert = Command::cargo_bin("tm").unwrap().args(["rules", "smtlib"]).assert();
+    assert.success().stdout(rules::to_smtlib(&rules::adder()));
+}
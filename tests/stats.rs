@@ -0,0 +1,65 @@
+use std::fs;
+
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::trace::json::Json;
+use turing::trace::stats::{AggregateStats, Distribution};
+
+// The fixed batch: every pair of operands from 0 to 9, 100 in all.
+fn pairs() -> Vec<(String, String)> {
+    (0..10u32).flat_map(|a| (0..10u32).map(move |b| (format!("{:b}", a), format!("{:b}", b)))).collect()
+}
+
+#[test]
+fn fixed_batch_has_a_known_median() {
+    let mut stats = AggregateStats::new();
+    for (a, b) in pairs() {
+        let mut machine = TuringMachine::new(build_tape(&a, &b, LeadingZeros::Strip).unwrap(), rules::adder());
+        let steps = machine.run_bounded(10_000).unwrap();
+        stats.record_success(steps, Some(machine.tape().len()));
+    }
+    assert_eq!(stats.successes(), 100);
+    assert_eq!(stats.steps(), Some(Distribution { min: 12, median: 44, p95: 70, max: 78 }));
+    assert_eq!(stats.tape_cells(), Some(Distribution { min: 5, median: 8, p95: 10, max: 11 }));
+}
+
+#[test]
+fn percentiles_are_nearest_rank() {
+    let distribution = Distribution::of(&(1..=100).rev().collect::<Vec<_>>()).unwrap();
+    assert_eq!(distribution, Distribution { min: 1, median: 50, p95: 95, max: 100 });
+    assert_eq!(Distribution::of(&[7]), Some(Distribution { min: 7, median: 7, p95: 7, max: 7 }));
+    assert_eq!(Distribution::of(&[1, 2]).unwrap().median, 1);
+    assert_eq!(Distribution::of(&[]), None);
+}
+
+#[test]
+fn failures_are_counted_by_category() {
+    let mut stats = AggregateStats::new();
+    stats.record_failure("step limit");
+    stats.record_failure("invalid input");
+    stats.record_failure("step limit");
+    stats.record_success(12, None);
+    let failures: Vec<(&str, usize)> = stats.failures().iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    assert_eq!(failures, [("invalid input", 1), ("step limit", 2)]);
+    assert_eq!(stats.tape_cells(), None);
+    let json = stats.to_json().to_string();
+    assert!(json.contains(r#""failures":{"invalid input":1,"step limit":2}"#), "{}", json);
+    assert!(json.contains(r#""tape_cells":null"#), "{}", json);
+}
+
+#[test]
+fn tm_batch_reports_the_median() {
+    let dir = std::env::temp_dir().join(format!("tm-stats-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let csv: String = pairs().iter().map(|(a, b)| format!("{},{}\n", a, b)).collect();
+    fs::write(dir.join("pairs.csv"), csv).unwrap();
+    let summary = dir.join("summary.json");
+    let args = [dir.join("pairs.csv"), "--summary-json".into(), summary.clone()];
+    let output = Command::cargo_bin("tm").unwrap().arg("batch").args(args).output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("steps: min 12, median 44, p95 70, max 78\n"), "{}", stderr);
+    let json: Json = fs::read_to_string(&summary).unwrap().parse().unwrap();
+    let steps = json.get("payload").and_then(|payload| payload.get("steps")).unwrap();
+    assert_eq!(steps.get("median").and_then(Json::as_i64), Some(44));
+    fs::remove_dir_all(dir).unwrap();
+}
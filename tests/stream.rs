@@ -0,0 +1,81 @@
+use std::sync::mpsc::sync_channel;
+use std::thread;
+use std::time::Duration;
+
+use turing::machine::stream::{Backpressure, StepEvent};
+use turing::prelude::*;
+
+// The adder on 1011+11, which halts after 62 steps.
+fn adder() -> TuringMachine {
+    TuringMachine::new(build_tape("1011", "11", LeadingZeros::Strip).unwrap(), rules::adder())
+}
+
+// Streams a whole run into a channel with room for all of it, returning the run's
+// outcome and the events sent.
+fn stream(backpressure: Backpressure) -> (Result<usize, RunError>, Vec<StepEvent>) {
+    let (events, received) = sync_channel(100);
+    let outcome = adder().run_streaming(events, backpressure);
+    (outcome, received.iter().collect())
+}
+
+#[test]
+fn blocking_sends_every_step() {
+    let (outcome, events) = stream(Backpressure::Block);
+    assert_eq!(outcome, Ok(62));
+    assert_eq!(events.iter().map(|event| event.step).collect::<Vec<_>>(), (1..=62).collect::<Vec<_>>());
+    let first = &events[0];
+    assert_eq!((&first.state, first.read, first.write, &first.next), (&State::FindPlus, '_', '_', &State::FindPlus));
+    let last = &events[61];
+    assert_eq!(last.snapshot.tape.iter().collect::<String>(), "_+++IIIO_");
+}
+
+#[test]
+fn sampling_sends_every_nth_step_and_the_last() {
+    let (outcome, events) = stream(Backpressure::Sample(10));
+    assert_eq!(outcome, Ok(62));
+    assert_eq!(events.iter().map(|event| event.step).collect::<Vec<_>>(), [10, 20, 30, 40, 50, 60, 62]);
+}
+
+#[test]
+fn dropping_keeps_the_newest_steps_for_a_slow_receiver() {
+    let (events, received) = sync_channel(1);
+    let worker = thread::spawn(move || adder().run_streaming(events, Backpressure::DropOldest(4)));
+    // Let the run get ahead of a receiver that isn't reading yet: the channel holds the
+    // first step, and the run the last four.
+    thread::sleep(Duration::from_millis(50));
+    let steps: Vec<usize> = received.iter().map(|event| event.step).collect();
+    assert_eq!(worker.join().unwrap(), Ok(62));
+    assert_eq!(steps, [1, 59, 60, 61, 62]);
+}
+
+#[test]
+fn dropping_loses_nothing_while_the_channel_has_room() {
+    let (outcome, events) = stream(Backpressure::DropOldest(4));
+    assert_eq!(outcome, Ok(62));
+    assert_eq!(events.iter().map(|event| event.step).collect::<Vec<_>>(), (1..=62).collect::<Vec<_>>());
+}
+
+#[test]
+fn dropped_receiver_cancels_the_run() {
+    let cases = [(Backpressure::Block, 1), (Backpressure::DropOldest(4), 1), (Backpressure::Sample(10), 10)];
+    for (backpressure, step) in cases {
+        let (events, received) = sync_channel(1);
+        drop(received);
+        let mut machine = adder();
+        assert_eq!(machine.run_streaming(events, backpressure), Err(RunError::Cancelled(step)), "{:?}", backpressure);
+        assert_eq!(machine.steps(), step);
+    }
+}
+
+#[test]
+fn receiver_dropped_mid_run_cancels_it() {
+    // DropOldest never blocks mid-run, so it can finish before the receiver goes away.
+    for backpressure in [Backpressure::Block, Backpressure::Sample(10)] {
+        let (events, received) = sync_channel(1);
+        let worker = thread::spawn(move || adder().run_streaming(events, backpressure));
+        received.recv().unwrap();
+        drop(received);
+        let outcome = worker.join().unwrap();
+        assert!(matches!(outcome, Err(RunError::Cancelled(steps)) if steps <= 62), "{:?}: {:?}", backpressure, outcome);
+    }
+}
@@ -0,0 +1,53 @@
+use assert_cmd::Command;
+use turing::error::ConfigParseError;
+use turing::error::{ImportErrorKind, ParseError, RuleParseErrorKind};
+use turing::prelude::*;
+use turing::rules::parse_rules;
+use turing::rules::turingmachine_io;
+use turing::tape::input::parse_tape;
+use turing::tape::parse_configuration;
+
+const EMOJI: &str = "\u{1F600}";
+// An `e` followed by a combining acute accent: one letter to a reader, two chars to Rust.
+const COMBINING: &str = "e\u{301}";
+
+#[test]
+fn tapes_reject_emoji_and_combining_characters() {
+    assert_eq!(
+        parse_tape(&format!("_1{}_", EMOJI)),
+        Err(ParseError::TapeSymbol { cell: 2, symbol: '\u{1F600}' })
+    );
+    assert_eq!(parse_tape(&format!("_1{}_", COMBINING)), Err(ParseError::TapeSymbol { cell: 3, symbol: '\u{301}' }));
+    let error = parse_tape(&format!("_{}_", EMOJI)).unwrap_err();
+    assert_eq!(error.to_string(), "cell 1 holds '\u{1F600}' (U+1F600), but a cell holds one printable ASCII symbol");
+    assert_eq!(parse_tape("_1011+11_"), Ok("_1011+11_".chars().collect()));
+}
+
+#[test]
+fn rules_reject_emoji_and_combining_characters() {
+    for symbol in [EMOJI, COMBINING, "\u{e9}"] {
+        let error = parse_rules(&format!("FindPlus 1 {} R FindPlus\n", symbol)).unwrap_err();
+        assert_eq!((error.line, error.kind), (1, RuleParseErrorKind::Symbol(symbol.to_string())));
+    }
+}
+
+#[test]
+fn configurations_reject_emoji() {
+    let error = parse_configuration(&format!("FindPlus _1[{}]_", EMOJI), &rules::adder()).unwrap_err();
+    assert_eq!(error, ConfigParseError::Tape(ParseError::TapeSymbol { cell: 2, symbol: '\u{1F600}' }));
+}
+
+#[test]
+fn imported_machines_reject_emoji() {
+    let yaml = format!("input: '1{}'\nblank: ' '\nstart state: right\ntable:\n  right:\n    1: R\n", EMOJI);
+    let error = turingmachine_io::parse(&yaml).unwrap_err();
+    assert_eq!((error.line, error.kind), (1, ImportErrorKind::Symbol(EMOJI.to_string())));
+}
+
+#[test]
+fn tm_run_rejects_an_emoji_on_the_tape() {
+    let input = format!("_1{}+1_", EMOJI);
+    let assert = Command::cargo_bin("tm").unwrap().args(["run", "--machine", "add", "--input", &input]).assert();
+    let stderr = String::from_utf8(assert.failure().code(2).get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("cell 2 holds '\u{1F600}' (U+1F600)"), "{}", stderr);
+}
@@ -0,0 +1,42 @@
+use turing::error::BuildError;
+use turing::prelude::*;
+
+fn tape(a: &str, b: &str) -> Vec<char> {
+    build_tape(a, b, LeadingZeros::Strip).unwrap()
+}
+
+#[test]
+fn an_input_longer_than_the_limit_fails_to_build() {
+    let error = TuringMachine::builder(tape("1011", "11"), rules::adder()).with_max_tape_len(5).build().unwrap_err();
+    assert_eq!(error, BuildError::TapeTooLong { required: 9, allowed: 5 });
+    assert_eq!(
+        error.to_string(),
+        "the input needs 9 cells but the tape may hold only 5, so the machine can never finish"
+    );
+}
+
+#[test]
+fn a_refused_limit_leaves_the_machine_unlimited() {
+    let mut machine = TuringMachine::new(tape("1011", "11"), rules::adder());
+    assert_eq!(machine.set_max_tape_len(Some(8)), Err(BuildError::TapeTooLong { required: 9, allowed: 8 }));
+    assert_eq!(machine.run_bounded(10_000), Ok(62));
+    assert_eq!(machine.result(), "1110");
+}
+
+#[test]
+fn an_input_that_just_fits_runs_to_the_end() {
+    let machine = TuringMachine::builder(tape("1011", "11"), rules::adder()).with_max_tape_len(9).build();
+    let mut machine = machine.unwrap();
+    assert_eq!(machine.run_bounded(10_000), Ok(62));
+    assert_eq!(machine.result(), "1110");
+}
+
+#[test]
+fn growing_past_the_limit_halts_the_run() {
+    // Without its blanks, the adder has to grow the tape to find the end of the second operand.
+    let mut machine = TuringMachine::builder(vec!['1', '+', '1'], rules::adder()).with_max_tape_len(3).build().unwrap();
+    assert_eq!(machine.run_bounded(10_000), Ok(5));
+    assert_eq!(machine.tape().len(), 3);
+    assert!(!machine.finished_successfully());
+    assert_eq!(machine.halt_reason(), Some(&HaltReason::TapeLimitExceeded(3)));
+}
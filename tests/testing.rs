@@ -0,0 +1,72 @@
+use turing::prelude::*;
+use turing::tape::input::validate_input;
+use turing::testing::SplitMix64;
+use turing::testing::{assert_same_outputs, random_input};
+
+// The operand pairs the tables are compared on.
+const INPUTS: &[(&str, &str)] =
+    &[("0", "0"), ("1", "1"), ("1011", "11"), ("11111111", "1"), ("1", "100000"), ("1", "101")];
+
+// The adder, but leaving a 0 a carry reaches as it is instead of turning it into a 1.
+fn dropped_carry() -> RuleTable {
+    let mut rules = rules::adder();
+    rules.insert((State::Carry, '0'), ('0', Direction::Left, State::BackToStart));
+    rules
+}
+
+#[test]
+fn a_table_has_the_same_outputs_as_itself() {
+    assert_same_outputs(&rules::adder(), &rules::adder(), INPUTS);
+}
+
+#[test]
+fn a_rule_that_never_fires_changes_no_output() {
+    let mut extra = rules::adder();
+    extra.insert((State::Named(String::from("Unused")), '1'), ('0', Direction::Left, State::Halt));
+    assert_same_outputs(&rules::adder(), &extra, INPUTS);
+}
+
+#[test]
+#[should_panic(expected = "rule tables disagree on 1+101")]
+fn a_dropped_carry_is_caught() {
+    assert_same_outputs(&rules::adder(), &dropped_carry(), INPUTS);
+}
+
+#[test]
+fn random_inputs_are_well_formed_adder_inputs() {
+    let mut rng = SplitMix64::new(7);
+    for _ in 0..1_000 {
+        let input = random_input(&mut rng, 12);
+        assert_eq!(validate_input(&input), Ok(()), "{}", input);
+        let (a, b) = input.split_once('+').unwrap();
+        for operand in [a, b] {
+            assert!((1..=12).contains(&operand.len()), "{}", input);
+            assert!(operand.starts_with('1'), "{}", input);
+            assert!(operand.chars().all(|c| c == '0' || c == '1'), "{}", input);
+        }
+    }
+}
+
+#[test]
+fn random_inputs_cover_every_width() {
+    let mut rng = SplitMix64::new(1);
+    let mut widths = [false; 6];
+    for _ in 0..500 {
+        let input = random_input(&mut rng, 6);
+        for operand in input.split('+') {
+            widths[operand.len() - 1] = true;
+        }
+    }
+    assert_eq!(widths, [true; 6]);
+    assert_eq!(random_input(&mut rng, 1), "1+1");
+}
+
+#[test]
+fn the_same_seed_gives_the_same_inputs() {
+    let inputs = |seed| {
+        let mut rng = SplitMix64::new(seed);
+        (0..20).map(|_| random_input(&mut rng, 64)).collect::<Vec<_>>()
+    };
+    assert_eq!(inputs(42), inputs(42));
+    assert_ne!(inputs(42), inputs(43));
+}
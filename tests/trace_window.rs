@@ -0,0 +1,64 @@
+use assert_cmd::Command;
+use turing::tape::display::DisplayMap;
+use turing::trace::render_window;
+
+// A 100-cell tape of the digits 0 to 9 over and over, so every cell shows where it is.
+fn digits() -> Vec<char> {
+    (0..100).map(|index| char::from(b'0' + index as u8 % 10)).collect()
+}
+
+// `render_window` of `tape` with no display map.
+fn window(tape: &[char], head: usize, width: usize) -> String {
+    render_window(tape.iter(), head, width, &DisplayMap::new())
+}
+
+#[test]
+fn window_near_the_left_edge() {
+    assert_eq!(window(&digits(), 3, 10), "[0] 0 1 2 [3] 4 5 6 7 8 9 … [10]");
+    assert_eq!(window(&digits(), 0, 10), "[0] [0] 1 2 3 4 5 6 7 8 9 … [10]");
+}
+
+#[test]
+fn window_mid_tape() {
+    assert_eq!(window(&digits(), 50, 10), "[45] … 5 6 7 8 9 [0] 1 2 3 4 … [55]");
+    assert_eq!(window(&digits(), 51, 9), "[47] … 7 8 9 0 [1] 2 3 4 5 … [56]");
+}
+
+#[test]
+fn window_near_the_right_edge() {
+    assert_eq!(window(&digits(), 97, 10), "[90] … 0 1 2 3 4 5 6 [7] 8 9 [100]");
+    assert_eq!(window(&digits(), 99, 10), "[90] … 0 1 2 3 4 5 6 7 8 [9] [100]");
+}
+
+#[test]
+fn window_wider_than_the_tape_shows_it_all() {
+    let tape: Vec<char> = "_1+1_".chars().collect();
+    assert_eq!(window(&tape, 2, 40), "[0] _ 1 [+] 1 _ [5]");
+}
+
+#[test]
+fn window_follows_the_head_and_shows_markers_as_digits() {
+    let tape: Vec<char> = digits();
+    let lines: Vec<String> = (0..100).map(|head| window(&tape, head, 10)).collect();
+    for (head, line) in lines.iter().enumerate() {
+        assert!(line.contains(&format!("[{}]", tape[head])), "{}: {}", head, line);
+        let start: usize = line[1..line.find(']').unwrap()].parse().unwrap();
+        assert_eq!(start, head.saturating_sub(5).min(90), "{}", line);
+    }
+    let marked: Vec<char> = "_+IO_".chars().collect();
+    assert_eq!(render_window(marked.iter(), 2, 3, &DisplayMap::adder()), "[1] … + [1̲] 0̲ … [4]");
+}
+
+#[test]
+fn tm_trace_windows_long_tapes_unless_asked_for_the_full_tape() {
+    let tm = |args: &[&str]| {
+        let assert = Command::cargo_bin("tm").unwrap().arg("trace").args(args).assert().success();
+        String::from_utf8(assert.get_output().stdout.clone()).unwrap()
+    };
+    let windowed = tm(&["1011011+1", "--window", "6"]);
+    let lines: Vec<&str> = windowed.lines().filter(|line| line.starts_with("Tape: ")).collect();
+    assert_eq!(lines[0], "Tape: [0] [_] 1 0 1 1 0 … [6], Head: 0, State: FindPlus");
+    assert_eq!(lines[6], "Tape: [3] … 1 1 0 [1] 1 + … [9], Head: 6, State: FindPlus");
+    let full = tm(&["1011011+1", "--window", "6", "--full-tape"]);
+    assert!(full.starts_with("Tape: ['_', '1', '0', '1', '1', '0', '1', '1', '+', '1', '_'], Head: 0"), "{}", full);
+}
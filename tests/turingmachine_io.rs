@@ -0,0 +1,86 @@
+use std::fs;
+
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::rules::turingmachine_io::{self, ImportErrorKind};
+
+// The sample machine under machines/, as the simulator would save it.
+fn increment() -> turingmachine_io::Imported {
+    let text = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/machines/increment.yaml")).unwrap();
+    turingmachine_io::parse(&text).unwrap()
+}
+
+// Runs an imported machine on its own input and returns its answer.
+fn run(imported: &turingmachine_io::Imported) -> String {
+    let definition = &imported.definition;
+    let mut machine = TuringMachine::new(imported.input.clone(), definition.rules.clone());
+    machine.set_state(definition.start.clone());
+    machine.set_final_states(definition.final_states.clone());
+    machine.set_result_strategy(definition.result.clone());
+    machine.run_bounded(10_000).unwrap();
+    machine.result()
+}
+
+fn named(name: &str) -> State {
+    State::Named(String::from(name))
+}
+
+#[test]
+fn the_sample_machine_imports_and_runs() {
+    let imported = increment();
+    assert_eq!(imported.input, ['1', '0', '1', '1']);
+    assert_eq!(imported.definition.start, named("right"));
+    let rules = &imported.definition.rules;
+    assert_eq!(rules.len(), 6);
+    assert_eq!(rules.get(&(named("right"), '0')), Some(&('0', Direction::Right, named("right"))));
+    assert_eq!(rules.get(&(named("right"), '_')), Some(&('_', Direction::Left, named("carry"))));
+    assert_eq!(rules.get(&(named("carry"), '_')), Some(&('1', Direction::Left, named("done"))));
+    assert_eq!(run(&imported), "1100");
+}
+
+#[test]
+fn the_sample_machine_round_trips() {
+    let imported = increment();
+    let exported = turingmachine_io::export(&imported.definition, &imported.input);
+    let reimported = turingmachine_io::parse(&exported).unwrap();
+    assert_eq!(reimported.definition.rules, imported.definition.rules);
+    assert_eq!(reimported.definition.start, imported.definition.start);
+    assert_eq!(reimported.input, imported.input);
+    assert_eq!(run(&reimported), "1100");
+    assert_eq!(turingmachine_io::export(&reimported.definition, &reimported.input), exported);
+}
+
+#[test]
+fn the_adder_round_trips() {
+    let definition = rules::parse_machine(&rules::to_text(&rules::adder())).unwrap();
+    let input: Vec<char> = "_1011+11_".chars().collect();
+    let reimported = turingmachine_io::parse(&turingmachine_io::export(&definition, &input)).unwrap();
+    assert_eq!(reimported.definition.rules, rules::adder());
+    assert_eq!(reimported.definition.start, State::FindPlus);
+    let mut machine = TuringMachine::new(reimported.input, reimported.definition.rules);
+    machine.run();
+    assert_eq!(machine.result(), "1110");
+}
+
+#[test]
+fn broken_files_are_reported_by_line() {
+    let error = |text: &str| turingmachine_io::parse(text).unwrap_err();
+    assert_eq!(error("input: '1'\nstart state: a\n").kind, ImportErrorKind::MissingTable);
+    let missing_start = error("input: '1'\ntable:\n  a:\n    1: R\n");
+    assert_eq!(missing_start.kind, ImportErrorKind::MissingStartState);
+    let duplicate = error("start state: a\ntable:\n  a:\n    1: R\n    [0, 1]: L\n");
+    assert_eq!((duplicate.line, duplicate.kind), (5, ImportErrorKind::Duplicate(named("a"), '1')));
+    let no_move = error("start state: a\ntable:\n  a:\n    1: {write: 0}\n");
+    assert_eq!(no_move.line, 4);
+    assert!(matches!(no_move.kind, ImportErrorKind::Move(_)), "{:?}", no_move.kind);
+    assert!(no_move.to_string().starts_with("line 4: "), "{}", no_move);
+}
+
+#[test]
+fn tm_runs_the_sample_machine() {
+    let rules = concat!(env!("CARGO_MANIFEST_DIR"), "/machines/increment.yaml");
+    let args = ["run", "--rules", rules, "--input", "1011"];
+    let output = Command::cargo_bin("tm").unwrap().args(args).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Result: 1100"));
+}
@@ -0,0 +1,61 @@
+use std::env;
+use std::fs;
+
+use assert_cmd::Command;
+use turing::prelude::*;
+use turing::registry::vectors::{self, VectorFailure};
+
+// The canonical test vectors, `tm gen-vectors --count 500 --seed 1`.
+const CANONICAL: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors.json");
+
+// The canonical vectors as checked in.
+fn canonical() -> Vec<vectors::Vector> {
+    vectors::parse(&fs::read_to_string(CANONICAL).expect("the vector file should be readable")).unwrap()
+}
+
+// The canonical file must be what the generator writes today, so a change to the rules
+// that alters a sum or a step count shows up here. A change meant to alter them updates
+// the file along with it: run `BLESS=1 cargo test --test vectors` and commit the result.
+#[test]
+fn canonical_file_is_what_the_generator_writes() {
+    let generated = vectors::export(&vectors::generate(500, 1), 1);
+    if env::var_os("BLESS").is_some() {
+        fs::write(CANONICAL, &generated).expect("the vector file should be writable");
+    }
+    assert_eq!(fs::read_to_string(CANONICAL).unwrap(), generated);
+}
+
+#[test]
+fn adder_passes_the_canonical_vectors() {
+    let vectors = canonical();
+    assert_eq!(vectors.len(), 500);
+    assert_eq!(vectors::verify(&rules::adder(), &vectors, false), []);
+}
+
+#[test]
+fn canonical_vectors_cover_the_edge_cases() {
+    let vectors = canonical();
+    let has = |a: &str, b: &str| vectors.iter().any(|vector| vector.a == a && vector.b == b);
+    assert!(has("0", "0") && has("0", "1") && has("1", "0") && has("1", "1"));
+    assert!(has("1", "11111111") && has("11111111", "1"));
+    assert!(vectors.iter().any(|vector| vector.a.len() > 16 && vector.b.len() > 16));
+}
+
+#[test]
+fn wrong_sums_and_steps_are_reported() {
+    let mut vectors = canonical();
+    vectors[3].expected_sum = String::from("11");
+    vectors[7].expected_steps += 1;
+    let failures = vectors::verify(&rules::adder(), &vectors, false);
+    assert!(matches!(failures[..], [VectorFailure::Sum { index: 3, .. }, VectorFailure::Steps { index: 7, .. }]));
+    let failures = vectors::verify(&rules::adder(), &vectors, true);
+    assert!(matches!(failures[..], [VectorFailure::Sum { index: 3, .. }]));
+}
+
+#[test]
+fn tm_verifies_the_canonical_file() {
+    let assert = Command::cargo_bin("tm").unwrap().args(["verify-vectors", CANONICAL]).assert().success();
+    assert_eq!(String::from_utf8(assert.get_output().stdout.clone()).unwrap(), "500 of 500 vectors passed\n");
+    let assert = Command::cargo_bin("tm").unwrap().args(["gen-vectors", "--count", "500", "--seed", "1"]).assert();
+    assert_eq!(String::from_utf8(assert.get_output().stdout.clone()).unwrap(), fs::read_to_string(CANONICAL).unwrap());
+}
@@ -0,0 +1,67 @@
+use turing::prelude::*;
+use turing::rules::window::{WindowRuleError, WindowTable};
+use turing::testing::adder_vectors;
+
+// Runs an adder on `a+b`, returning its answer and step count.
+fn add(rules: &RuleTable, a: &str, b: &str) -> (String, usize) {
+    let mut machine = TuringMachine::new(build_tape(a, b, LeadingZeros::Strip).unwrap(), rules.clone());
+    machine.run_bounded(1_000_000).unwrap();
+    assert!(machine.finished_successfully(), "{}+{} did not finish", a, b);
+    (machine.result(), machine.steps())
+}
+
+// Number of distinct states named in a rule table, `Halt` included.
+fn state_count(rules: &RuleTable) -> usize {
+    let mut states: Vec<&State> = rules.iter().flat_map(|((state, _), (_, _, next))| [state, next]).collect();
+    states.sort();
+    states.dedup();
+    states.len()
+}
+
+#[test]
+fn window_adder_is_smaller_than_the_classic_one() {
+    let classic = rules::adder();
+    let window = rules::window_adder();
+    assert_eq!((state_count(&classic), classic.len()), (8, 31));
+    assert_eq!((window.states().len(), window.len(), window.width()), (6, 82, 2));
+    // Running it one cell at a time multiplies the states out.
+    let single_cell = window.to_single_cell();
+    assert_eq!((state_count(&single_cell), single_cell.len()), (79, 177));
+}
+
+#[test]
+fn window_adder_adds_every_vector() {
+    let single_cell = rules::window_adder().to_single_cell();
+    let (mut classic_total, mut window_total) = (0, 0);
+    for (a, b, sum) in adder_vectors() {
+        let (classic_sum, classic_steps) = add(&rules::adder(), &a, &b);
+        let (window_sum, window_steps) = add(&single_cell, &a, &b);
+        assert_eq!(classic_sum, sum, "the classic adder got {}+{} wrong", a, b);
+        assert_eq!(window_sum, sum, "the window adder got {}+{} wrong", a, b);
+        // A window of two takes `2 * 2 - 1` single-cell steps per rule.
+        assert_eq!(window_steps % 3, 0, "{}+{}", a, b);
+        classic_total += classic_steps;
+        window_total += window_steps;
+    }
+    assert_eq!((classic_total, window_total), (2792, 4908));
+    assert!(window_total / 3 < classic_total);
+}
+
+#[test]
+fn windows_of_the_wrong_width_or_twice_over_are_rejected() {
+    let state = |name: &str| State::Named(name.to_string());
+    let rule = |read: &str, write: &str| {
+        (state("S"), read.to_string(), write.to_string(), Direction::Right, state("S"))
+    };
+    assert_eq!(
+        WindowTable::new(2, [rule("0", "00")]),
+        Err(WindowRuleError::Width { width: 2, symbols: "0".to_string() })
+    );
+    assert_eq!(
+        WindowTable::new(2, [rule("01", "10"), rule("01", "11")]),
+        Err(WindowRuleError::Duplicate(state("S"), "01".to_string()))
+    );
+    let table = WindowTable::new(2, [rule("01", "10")]).unwrap();
+    assert_eq!(table.get(&state("S"), &['0', '1']), Some(&(vec!['1', '0'], Direction::Right, state("S"))));
+    assert_eq!(table.get(&state("S"), &['1', '1']), None);
+}